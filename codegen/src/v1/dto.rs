@@ -706,11 +706,7 @@ fn can_derive_default(ty: &rust::Struct, rust_types: &RustTypes) -> bool {
         }
 
         match &rust_types[&field.type_] {
-            rust::Type::Provided(ty) => {
-                if ty.name == "CachedTags" {
-                    return true;
-                }
-            }
+            rust::Type::Provided(ty) if ty.name == "CachedTags" => return true,
             rust::Type::List(_) => return true,
             rust::Type::Map(_) => return true,
             _ => {}
@@ -884,21 +880,17 @@ fn codegen_dto_ext(rust_types: &RustTypes) {
             let Some(field_ty) = rust_types.get(&field.type_) else { continue };
 
             match field_ty {
-                rust::Type::Alias(field_ty) => {
-                    if field.option_type && field_ty.type_ == "String" {
-                        g!("if self.{}.as_deref() == Some(\"\") {{", field.name);
-                        g!("    self.{} = None;", field.name);
-                        g!("}}");
-                    }
+                rust::Type::Alias(field_ty) if field.option_type && field_ty.type_ == "String" => {
+                    g!("if self.{}.as_deref() == Some(\"\") {{", field.name);
+                    g!("    self.{} = None;", field.name);
+                    g!("}}");
                 }
-                rust::Type::StrEnum(_) => {
-                    if field.option_type {
-                        g!("if let Some(ref val) = self.{} {{", field.name);
-                        g!("    if val.as_str() == \"\" {{");
-                        g!("        self.{} = None;", field.name);
-                        g!("    }}");
-                        g!("}}");
-                    }
+                rust::Type::StrEnum(_) if field.option_type => {
+                    g!("if let Some(ref val) = self.{} {{", field.name);
+                    g!("    if val.as_str() == \"\" {{");
+                    g!("        self.{} = None;", field.name);
+                    g!("    }}");
+                    g!("}}");
                 }
                 rust::Type::Struct(field_ty) => {
                     if field_ty.fields.is_empty() {
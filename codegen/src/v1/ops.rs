@@ -585,12 +585,11 @@ fn codegen_op_http_de_multipart(op: &Operation, rust_types: &RustTypes) {
         "let bucket = http::unwrap_bucket(req);",
         "let key = http::parse_field_value(&m, \"key\")?.ok_or_else(|| invalid_request!(\"missing key\"))?;",
         "",
-        "let vec_stream = req.s3ext.vec_stream.take().expect(\"missing vec stream\");",
+        "let file_stream = req.s3ext.file_stream.take().expect(\"missing file stream\");",
         "",
-        "let content_length = i64::try_from(vec_stream.exact_remaining_length()).map_err(|e|s3_error!(e, InvalidArgument, \"content-length overflow\"))?;",
-        "let content_length = (content_length != 0).then_some(content_length);",
+        "let content_length = file_stream.file_size().map(i64::try_from).transpose().map_err(|e|s3_error!(e, InvalidArgument, \"content-length overflow\"))?;",
         "",
-        "let body: Option<StreamingBlob> = Some(StreamingBlob::new(vec_stream));",
+        "let body: Option<StreamingBlob> = Some(StreamingBlob::new(file_stream));",
         "",
     ]);
 
@@ -695,14 +694,7 @@ fn codegen_op_http_call(op: &Operation) {
         g!("let overridden_headers = super::get_object::extract_overridden_response_headers(&s3_req)?;");
     }
 
-    g!("let result = s3.{method}(s3_req).await;");
-
-    g([
-        "let s3_resp = match result {",
-        "    Ok(val) => val,",
-        "    Err(err) => return super::serialize_error(err, false),",
-        "};",
-    ]);
+    g!("let s3_resp = s3.{method}(s3_req).await?;");
 
     g!("let mut resp = Self::serialize_http(s3_resp.output)?;");
 
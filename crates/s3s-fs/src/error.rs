@@ -40,7 +40,11 @@ where
 
 impl From<Error> for S3Error {
     fn from(e: Error) -> Self {
-        S3Error::with_source(S3ErrorCode::InternalError, e.source)
+        // errors from request body streams may carry an S3 error (e.g. checksum mismatch)
+        match e.source.downcast::<S3Error>() {
+            Ok(err) => *err,
+            Err(source) => S3Error::with_source(S3ErrorCode::InternalError, source),
+        }
     }
 }
 
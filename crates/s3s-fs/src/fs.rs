@@ -6,6 +6,7 @@ use s3s::crypto::Checksum;
 use s3s::crypto::Md5;
 use s3s::dto;
 use s3s::dto::PartNumber;
+use s3s::xml;
 
 use std::env;
 use std::ops::Not;
@@ -143,6 +144,50 @@ impl FileSystem {
         Ok(())
     }
 
+    /// resolve the path of a bucket configuration, such as `cors` and `website`
+    pub(crate) fn get_bucket_config_path(&self, bucket: &str, name: &str) -> Result<PathBuf> {
+        let encode = |s: &str| base64_simd::URL_SAFE_NO_PAD.encode_to_string(s);
+        let file_path = format!(".bucket-{}.{name}.xml", encode(bucket));
+        self.resolve_abs_path(file_path)
+    }
+
+    /// load a bucket configuration from fs
+    pub(crate) async fn load_bucket_config<T>(&self, bucket: &str, name: &str) -> Result<Option<T>>
+    where
+        T: for<'xml> xml::Deserialize<'xml>,
+    {
+        let path = self.get_bucket_config_path(bucket, name)?;
+        if path.exists().not() {
+            return Ok(None);
+        }
+        let content = fs::read(&path).await?;
+        let mut d = xml::Deserializer::new(&content);
+        let config = T::deserialize(&mut d)?;
+        d.expect_eof()?;
+        Ok(Some(config))
+    }
+
+    /// save a bucket configuration to fs
+    pub(crate) async fn save_bucket_config<T: xml::Serialize>(&self, bucket: &str, name: &str, config: &T) -> Result<()> {
+        let path = self.get_bucket_config_path(bucket, name)?;
+        let mut content = Vec::new();
+        config.serialize(&mut xml::Serializer::new(&mut content))?;
+        let mut file_writer = self.prepare_file_write(&path).await?;
+        file_writer.writer().write_all(&content).await?;
+        file_writer.writer().flush().await?;
+        file_writer.done().await?;
+        Ok(())
+    }
+
+    /// remove a bucket configuration from fs
+    pub(crate) fn delete_bucket_config(&self, bucket: &str, name: &str) -> Result<()> {
+        let path = self.get_bucket_config_path(bucket, name)?;
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
     /// get md5 sum
     pub(crate) async fn get_md5_sum(&self, bucket: &str, key: &str) -> Result<String> {
         let object_path = self.get_object_path(bucket, key)?;
@@ -2,8 +2,12 @@ use s3s_fs::FileSystem;
 use s3s_fs::Result;
 
 use s3s::auth::SimpleAuth;
-use s3s::host::MultiDomain;
+use s3s::connection::{ConnectionInfo, TrustedProxies};
+use s3s::cors::BucketCors;
+use s3s::host::{MultiDomain, WebsiteDomain};
 use s3s::service::S3ServiceBuilder;
+use s3s::throttle::{Throttle, ThrottleLimit};
+use s3s::website::BucketWebsite;
 
 use std::io::IsTerminal;
 use std::ops::Not;
@@ -40,6 +44,30 @@ struct Opt {
     #[arg(long)]
     domain: Vec<String>,
 
+    /// Region of the service, such as `us-east-1`.
+    #[arg(long)]
+    region: Option<String>,
+
+    /// Domain names used for website endpoints, which require `--domain`.
+    #[arg(long)]
+    website_domain: Vec<String>,
+
+    /// Networks of trusted proxies, such as `10.0.0.0/8`, whose `X-Forwarded-For` headers are used.
+    #[arg(long)]
+    trusted_proxy: Vec<String>,
+
+    /// Enable CORS with the configurations of buckets.
+    #[arg(long)]
+    cors: bool,
+
+    /// Maximum requests per second of each bucket.
+    #[arg(long)]
+    bucket_rate_limit: Option<u32>,
+
+    /// Maximum in-flight requests of each bucket.
+    #[arg(long)]
+    bucket_max_in_flight: Option<usize>,
+
     /// Root directory of stored data.
     root: PathBuf,
 }
@@ -68,7 +96,12 @@ fn check_cli_args(opt: &Opt) {
         cmd.error(ErrorKind::MissingRequiredArgument, msg).exit();
     }
 
-    for s in &opt.domain {
+    if opt.domain.is_empty() && opt.website_domain.is_empty().not() {
+        let msg = "website domains require domain names for virtual-hosted-style requests";
+        cmd.error(ErrorKind::MissingRequiredArgument, msg).exit();
+    }
+
+    for s in opt.domain.iter().chain(&opt.website_domain) {
         if s.contains('/') {
             let msg = format!("expected domain name, found URL-like string: {s:?}");
             cmd.error(ErrorKind::InvalidValue, msg).exit();
@@ -100,10 +133,51 @@ async fn run(opt: Opt) -> Result {
             info!("authentication is enabled");
         }
 
+        // Enforce the region of credential scopes
+        if let Some(region) = opt.region {
+            info!(?region, "region enforcement is enabled");
+            b.set_region(region);
+        }
+
         // Enable parsing virtual-hosted-style requests
         if opt.domain.is_empty().not() {
-            b.set_host(MultiDomain::new(&opt.domain)?);
+            let host = MultiDomain::new(&opt.domain)?;
             info!("virtual-hosted-style requests are enabled");
+
+            // Enable website endpoints
+            if opt.website_domain.is_empty() {
+                b.set_host(host);
+            } else {
+                b.set_host(WebsiteDomain::new(&opt.website_domain, host)?);
+                b.set_website(BucketWebsite);
+                info!("website endpoints are enabled");
+            }
+        }
+
+        // Enable throttling
+        if opt.bucket_rate_limit.is_some() || opt.bucket_max_in_flight.is_some() {
+            let mut limit = opt.bucket_rate_limit.map(ThrottleLimit::rate).unwrap_or_default();
+            limit.max_in_flight = opt.bucket_max_in_flight;
+            let mut throttle = Throttle::new();
+            throttle.set_bucket_limit(limit);
+            b.set_throttle(throttle);
+            info!("throttling is enabled");
+        }
+
+        // Enable forwarded headers of trusted proxies
+        if opt.trusted_proxy.is_empty().not() {
+            let mut proxies = TrustedProxies::new();
+            for network in &opt.trusted_proxy {
+                proxies.add(network)?;
+            }
+            b.set_trusted_proxies(proxies);
+            info!("trusted proxies are enabled");
+        }
+
+        // Enable CORS with the configurations of buckets
+        if opt.cors {
+            b.set_cors(BucketCors);
+            info!("CORS is enabled");
         }
 
         b.build()
@@ -121,7 +195,7 @@ async fn run(opt: Opt) -> Result {
     info!("server is running at http://{local_addr}");
 
     loop {
-        let (socket, _) = tokio::select! {
+        let (socket, peer_addr) = tokio::select! {
             res =  listener.accept() => {
                 match res {
                     Ok(conn) => conn,
@@ -136,7 +210,11 @@ async fn run(opt: Opt) -> Result {
             }
         };
 
-        let conn = http_server.serve_connection(TokioIo::new(socket), service.clone());
+        let info = ConnectionInfo {
+            local_addr: Some(local_addr),
+            ..ConnectionInfo::new(peer_addr)
+        };
+        let conn = http_server.serve_connection(TokioIo::new(socket), service.with_connection(info));
         let conn = graceful.watch(conn.into_owned());
         tokio::spawn(async move {
             let _ = conn.await;
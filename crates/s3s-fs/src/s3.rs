@@ -130,9 +130,36 @@ impl S3 for FileSystem {
         } else {
             return Err(s3_error!(NoSuchBucket));
         }
+        self.delete_bucket_config(&input.bucket, "cors")?;
+        self.delete_bucket_config(&input.bucket, "website")?;
         Ok(S3Response::new(DeleteBucketOutput {}))
     }
 
+    #[tracing::instrument]
+    async fn delete_bucket_cors(&self, req: S3Request<DeleteBucketCorsInput>) -> S3Result<S3Response<DeleteBucketCorsOutput>> {
+        let input = req.input;
+        let path = self.get_bucket_path(&input.bucket)?;
+        if path.exists().not() {
+            return Err(s3_error!(NoSuchBucket));
+        }
+        self.delete_bucket_config(&input.bucket, "cors")?;
+        Ok(S3Response::new(DeleteBucketCorsOutput {}))
+    }
+
+    #[tracing::instrument]
+    async fn delete_bucket_website(
+        &self,
+        req: S3Request<DeleteBucketWebsiteInput>,
+    ) -> S3Result<S3Response<DeleteBucketWebsiteOutput>> {
+        let input = req.input;
+        let path = self.get_bucket_path(&input.bucket)?;
+        if path.exists().not() {
+            return Err(s3_error!(NoSuchBucket));
+        }
+        self.delete_bucket_config(&input.bucket, "website")?;
+        Ok(S3Response::new(DeleteBucketWebsiteOutput {}))
+    }
+
     #[tracing::instrument]
     async fn delete_object(&self, req: S3Request<DeleteObjectInput>) -> S3Result<S3Response<DeleteObjectOutput>> {
         let input = req.input;
@@ -183,6 +210,22 @@ impl S3 for FileSystem {
         Ok(S3Response::new(output))
     }
 
+    #[tracing::instrument]
+    async fn get_bucket_cors(&self, req: S3Request<GetBucketCorsInput>) -> S3Result<S3Response<GetBucketCorsOutput>> {
+        let input = req.input;
+        let path = self.get_bucket_path(&input.bucket)?;
+        if path.exists().not() {
+            return Err(s3_error!(NoSuchBucket));
+        }
+        let Some(config) = self.load_bucket_config::<CORSConfiguration>(&input.bucket, "cors").await? else {
+            return Err(s3_error!(NoSuchCORSConfiguration));
+        };
+        let output = GetBucketCorsOutput {
+            cors_rules: Some(config.cors_rules),
+        };
+        Ok(S3Response::new(output))
+    }
+
     #[tracing::instrument]
     async fn get_bucket_location(&self, req: S3Request<GetBucketLocationInput>) -> S3Result<S3Response<GetBucketLocationOutput>> {
         let input = req.input;
@@ -192,7 +235,35 @@ impl S3 for FileSystem {
             return Err(s3_error!(NoSuchBucket));
         }
 
-        let output = GetBucketLocationOutput::default();
+        // `us-east-1` is represented by an empty location constraint
+        let location_constraint = req
+            .region
+            .filter(|region| region != "us-east-1")
+            .map(BucketLocationConstraint::from);
+
+        let output = GetBucketLocationOutput { location_constraint };
+        Ok(S3Response::new(output))
+    }
+
+    #[tracing::instrument]
+    async fn get_bucket_website(&self, req: S3Request<GetBucketWebsiteInput>) -> S3Result<S3Response<GetBucketWebsiteOutput>> {
+        let input = req.input;
+        let path = self.get_bucket_path(&input.bucket)?;
+        if path.exists().not() {
+            return Err(s3_error!(NoSuchBucket));
+        }
+        let Some(config) = self
+            .load_bucket_config::<WebsiteConfiguration>(&input.bucket, "website")
+            .await?
+        else {
+            return Err(s3_error!(NoSuchWebsiteConfiguration));
+        };
+        let output = GetBucketWebsiteOutput {
+            error_document: config.error_document,
+            index_document: config.index_document,
+            redirect_all_requests_to: config.redirect_all_requests_to,
+            routing_rules: config.routing_rules,
+        };
         Ok(S3Response::new(output))
     }
 
@@ -456,6 +527,30 @@ impl S3 for FileSystem {
         Ok(S3Response::new(output))
     }
 
+    #[tracing::instrument]
+    async fn put_bucket_cors(&self, req: S3Request<PutBucketCorsInput>) -> S3Result<S3Response<PutBucketCorsOutput>> {
+        let input = req.input;
+        let path = self.get_bucket_path(&input.bucket)?;
+        if path.exists().not() {
+            return Err(s3_error!(NoSuchBucket));
+        }
+        self.save_bucket_config(&input.bucket, "cors", &input.cors_configuration)
+            .await?;
+        Ok(S3Response::new(PutBucketCorsOutput {}))
+    }
+
+    #[tracing::instrument]
+    async fn put_bucket_website(&self, req: S3Request<PutBucketWebsiteInput>) -> S3Result<S3Response<PutBucketWebsiteOutput>> {
+        let input = req.input;
+        let path = self.get_bucket_path(&input.bucket)?;
+        if path.exists().not() {
+            return Err(s3_error!(NoSuchBucket));
+        }
+        self.save_bucket_config(&input.bucket, "website", &input.website_configuration)
+            .await?;
+        Ok(S3Response::new(PutBucketWebsiteOutput {}))
+    }
+
     #[tracing::instrument]
     async fn put_object(&self, req: S3Request<PutObjectInput>) -> S3Result<S3Response<PutObjectOutput>> {
         let mut input = req.input;
@@ -18,7 +18,12 @@ use aws_sdk_s3::types::BucketLocationConstraint;
 use aws_sdk_s3::types::ChecksumMode;
 use aws_sdk_s3::types::CompletedMultipartUpload;
 use aws_sdk_s3::types::CompletedPart;
+use aws_sdk_s3::types::CorsConfiguration;
+use aws_sdk_s3::types::CorsRule;
 use aws_sdk_s3::types::CreateBucketConfiguration;
+use aws_sdk_s3::types::ErrorDocument;
+use aws_sdk_s3::types::IndexDocument;
+use aws_sdk_s3::types::WebsiteConfiguration;
 
 use anyhow::Result;
 use tokio::sync::Mutex;
@@ -62,6 +67,8 @@ fn config() -> &'static SdkConfig {
             let mut b = S3ServiceBuilder::new(fs);
             b.set_auth(SimpleAuth::from_single(cred.access_key_id(), cred.secret_access_key()));
             b.set_host(SingleDomain::new(DOMAIN_NAME).unwrap());
+            b.set_checksum_verification(true);
+            b.set_region(REGION);
             b.build()
         };
 
@@ -722,6 +729,114 @@ async fn test_relaxed_bucket_validation() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+#[tracing::instrument]
+async fn test_bucket_cors() -> Result<()> {
+    let c = Client::new(config());
+    let bucket = format!("test-bucket-cors-{}", Uuid::new_v4());
+    let bucket = bucket.as_str();
+    create_bucket(&c, bucket).await?;
+
+    {
+        let rule = CorsRule::builder()
+            .allowed_origins("https://*.example.com")
+            .allowed_methods("GET")
+            .allowed_methods("PUT")
+            .allowed_headers("*")
+            .expose_headers("ETag")
+            .max_age_seconds(600)
+            .build()?;
+        let cfg = CorsConfiguration::builder().cors_rules(rule).build()?;
+        c.put_bucket_cors().bucket(bucket).cors_configuration(cfg).send().await?;
+    }
+
+    {
+        let ans = log_and_unwrap!(c.get_bucket_cors().bucket(bucket).send().await);
+        let rules = ans.cors_rules();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].allowed_origins(), ["https://*.example.com"]);
+        assert_eq!(rules[0].allowed_methods(), ["GET", "PUT"]);
+        assert_eq!(rules[0].expose_headers(), ["ETag"]);
+        assert_eq!(rules[0].max_age_seconds(), Some(600));
+    }
+
+    {
+        c.delete_bucket_cors().bucket(bucket).send().await?;
+        let result = c.get_bucket_cors().bucket(bucket).send().await;
+        let err = result.unwrap_err().into_service_error();
+        assert_eq!(err.meta().code(), Some("NoSuchCORSConfiguration"));
+    }
+
+    delete_bucket(&c, bucket).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn test_bucket_website() -> Result<()> {
+    let c = Client::new(config());
+    let bucket = format!("test-bucket-website-{}", Uuid::new_v4());
+    let bucket = bucket.as_str();
+    create_bucket(&c, bucket).await?;
+
+    {
+        let cfg = WebsiteConfiguration::builder()
+            .index_document(IndexDocument::builder().suffix("index.html").build()?)
+            .error_document(ErrorDocument::builder().key("error.html").build()?)
+            .build();
+        c.put_bucket_website()
+            .bucket(bucket)
+            .website_configuration(cfg)
+            .send()
+            .await?;
+    }
+
+    {
+        let ans = log_and_unwrap!(c.get_bucket_website().bucket(bucket).send().await);
+        assert_eq!(ans.index_document().unwrap().suffix(), "index.html");
+        assert_eq!(ans.error_document().unwrap().key(), "error.html");
+    }
+
+    {
+        c.delete_bucket_website().bucket(bucket).send().await?;
+        let result = c.get_bucket_website().bucket(bucket).send().await;
+        let err = result.unwrap_err().into_service_error();
+        assert_eq!(err.meta().code(), Some("NoSuchWebsiteConfiguration"));
+    }
+
+    delete_bucket(&c, bucket).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+#[tracing::instrument]
+async fn test_bucket_location() -> Result<()> {
+    let c = Client::new(config());
+    let bucket = format!("test-bucket-location-{}", Uuid::new_v4());
+    let bucket = bucket.as_str();
+    create_bucket(&c, bucket).await?;
+
+    {
+        let ans = log_and_unwrap!(c.get_bucket_location().bucket(bucket).send().await);
+        assert_eq!(ans.location_constraint().map(BucketLocationConstraint::as_str), Some(REGION));
+    }
+
+    {
+        // credential scopes for other regions are rejected
+        let conf = config().to_builder().region(Region::new("eu-west-1")).build();
+        let c = Client::new(&conf);
+        let result = c.get_bucket_location().bucket(bucket).send().await;
+        let err = result.unwrap_err().into_service_error();
+        assert_eq!(err.meta().code(), Some("AuthorizationHeaderMalformed"));
+    }
+
+    delete_bucket(&c, bucket).await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 #[tracing::instrument]
 async fn test_default_bucket_validation() -> Result<()> {
@@ -0,0 +1,52 @@
+//! Matching a resource against large pattern sets.
+//!
+//! The time of `is_match` should grow sublinearly in the number of patterns,
+//! because only the patterns whose literal segments occur in the input are checked.
+
+use s3s_policy::pattern::PatternSet;
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+fn resource_patterns(n: usize) -> Vec<String> {
+    (0..n).map(|i| format!("arn:aws:s3:::tenant-{i:05}/home/*")).collect()
+}
+
+fn is_match(c: &mut Criterion) {
+    let mut group = c.benchmark_group("PatternSet::is_match");
+
+    for n in [10, 100, 1_000, 10_000] {
+        let patterns = resource_patterns(n);
+        let set = PatternSet::new(patterns.iter().map(String::as_str)).unwrap();
+
+        let hit = format!("arn:aws:s3:::tenant-{:05}/home/alice/key", n / 2);
+        let miss = "arn:aws:s3:::tenant-99999/home/alice/key";
+        assert!(set.is_match(&hit));
+        assert!(!set.is_match(miss));
+
+        group.bench_with_input(BenchmarkId::new("hit", n), &hit, |b, input| b.iter(|| set.is_match(black_box(input))));
+        group.bench_with_input(BenchmarkId::new("miss", n), miss, |b, input| b.iter(|| set.is_match(black_box(input))));
+    }
+
+    group.finish();
+}
+
+fn is_match_ignore_case(c: &mut Criterion) {
+    let mut group = c.benchmark_group("PatternSet::is_match_ignore_case");
+
+    for n in [10, 100, 1_000, 10_000] {
+        let patterns: Vec<String> = (0..n).map(|i| format!("s3:Get{i:05}*")).collect();
+        let set = PatternSet::new_ignore_case(patterns.iter().map(String::as_str)).unwrap();
+
+        let input = format!("S3:GET{:05}OBJECT", n / 2);
+        assert!(set.is_match(&input));
+
+        group.bench_with_input(BenchmarkId::new("hit", n), &input, |b, input| b.iter(|| set.is_match(black_box(input))));
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, is_match, is_match_ignore_case);
+criterion_main!(benches);
@@ -0,0 +1,119 @@
+//! Amazon Resource Names
+//!
+//! See <https://docs.aws.amazon.com/IAM/latest/UserGuide/reference-arns.html>
+
+use std::fmt;
+use std::str::FromStr;
+
+/// An ARN like `arn:aws:s3:::bucket/key`
+///
+/// The resource part can contain colons and slashes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Arn {
+    pub partition: String,
+    pub service: String,
+    pub region: String,
+    pub account_id: String,
+    pub resource: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid ARN: {0}")]
+pub struct ArnError(String);
+
+impl Arn {
+    /// Creates the ARN of an S3 bucket or object, like `arn:aws:s3:::bucket` or `arn:aws:s3:::bucket/key`.
+    #[must_use]
+    pub fn s3(bucket: &str, key: Option<&str>) -> Self {
+        let resource = match key {
+            Some(key) => format!("{bucket}/{key}"),
+            None => bucket.to_owned(),
+        };
+        Self {
+            partition: "aws".to_owned(),
+            service: "s3".to_owned(),
+            region: String::new(),
+            account_id: String::new(),
+            resource,
+        }
+    }
+
+    /// Returns the resource type and the resource id, split by the first `/` or `:`.
+    ///
+    /// For example, `user/path/alice` is split into `user` and `path/alice`.
+    #[must_use]
+    pub fn resource_type(&self) -> Option<(&str, &str)> {
+        let pos = self.resource.find(['/', ':'])?;
+        Some((&self.resource[..pos], &self.resource[pos + 1..]))
+    }
+}
+
+impl FromStr for Arn {
+    type Err = ArnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ArnError(s.to_owned());
+
+        let mut parts = s.splitn(6, ':');
+        let mut next = || parts.next().ok_or_else(invalid);
+        let (prefix, partition, service, region, account_id, resource) = (next()?, next()?, next()?, next()?, next()?, next()?);
+
+        if prefix != "arn" || partition.is_empty() || service.is_empty() || resource.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            partition: partition.to_owned(),
+            service: service.to_owned(),
+            region: region.to_owned(),
+            account_id: account_id.to_owned(),
+            resource: resource.to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for Arn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            partition,
+            service,
+            region,
+            account_id,
+            resource,
+        } = self;
+        write!(f, "arn:{partition}:{service}:{region}:{account_id}:{resource}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let arn: Arn = "arn:aws:iam::123456789012:user/path/alice".parse().unwrap();
+        assert_eq!(arn.partition, "aws");
+        assert_eq!(arn.service, "iam");
+        assert_eq!(arn.region, "");
+        assert_eq!(arn.account_id, "123456789012");
+        assert_eq!(arn.resource_type(), Some(("user", "path/alice")));
+        assert_eq!(arn.to_string(), "arn:aws:iam::123456789012:user/path/alice");
+
+        let arn: Arn = "arn:aws:s3:::bucket/a:b".parse().unwrap();
+        assert_eq!(arn, Arn::s3("bucket", Some("a:b")));
+        assert_eq!(arn.resource_type(), Some(("bucket", "a:b")));
+        assert_eq!(Arn::s3("bucket", None).to_string(), "arn:aws:s3:::bucket");
+
+        for s in [
+            "",
+            "arn",
+            "arn:aws:s3:::",
+            "arn::s3:::bucket",
+            "arn:aws::::bucket",
+            "urn:aws:s3:::bucket",
+            "arn:aws:s3:bucket",
+        ] {
+            assert!(s.parse::<Arn>().is_err(), "{s}");
+        }
+    }
+}
@@ -0,0 +1,668 @@
+//! <https://docs.aws.amazon.com/IAM/latest/UserGuide/reference_policies_elements_condition_operators.html>
+
+use crate::eval::RequestContext;
+use crate::model::ConditionRule;
+use crate::pattern::{Pattern, PatternError, PatternSet, is_wildcard_match};
+use crate::variable::Template;
+
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConditionError {
+    #[error("Unknown condition operator: {0}")]
+    UnknownOperator(String),
+
+    #[error("Invalid value for condition operator {operator}: {value}")]
+    InvalidValue { operator: ConditionOperator, value: String },
+
+    #[error(transparent)]
+    Pattern(#[from] PatternError),
+}
+
+/// A condition operator without qualifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operator {
+    StringEquals,
+    StringNotEquals,
+    StringEqualsIgnoreCase,
+    StringNotEqualsIgnoreCase,
+    StringLike,
+    StringNotLike,
+
+    NumericEquals,
+    NumericNotEquals,
+    NumericLessThan,
+    NumericLessThanEquals,
+    NumericGreaterThan,
+    NumericGreaterThanEquals,
+
+    DateEquals,
+    DateNotEquals,
+    DateLessThan,
+    DateLessThanEquals,
+    DateGreaterThan,
+    DateGreaterThanEquals,
+
+    Bool,
+    BinaryEquals,
+
+    IpAddress,
+    NotIpAddress,
+
+    ArnEquals,
+    ArnLike,
+    ArnNotEquals,
+    ArnNotLike,
+
+    Null,
+}
+
+const OPERATORS: &[Operator] = &[
+    Operator::StringEquals,
+    Operator::StringNotEquals,
+    Operator::StringEqualsIgnoreCase,
+    Operator::StringNotEqualsIgnoreCase,
+    Operator::StringLike,
+    Operator::StringNotLike,
+    Operator::NumericEquals,
+    Operator::NumericNotEquals,
+    Operator::NumericLessThan,
+    Operator::NumericLessThanEquals,
+    Operator::NumericGreaterThan,
+    Operator::NumericGreaterThanEquals,
+    Operator::DateEquals,
+    Operator::DateNotEquals,
+    Operator::DateLessThan,
+    Operator::DateLessThanEquals,
+    Operator::DateGreaterThan,
+    Operator::DateGreaterThanEquals,
+    Operator::Bool,
+    Operator::BinaryEquals,
+    Operator::IpAddress,
+    Operator::NotIpAddress,
+    Operator::ArnEquals,
+    Operator::ArnLike,
+    Operator::ArnNotEquals,
+    Operator::ArnNotLike,
+    Operator::Null,
+];
+
+impl Operator {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Operator::StringEquals => "StringEquals",
+            Operator::StringNotEquals => "StringNotEquals",
+            Operator::StringEqualsIgnoreCase => "StringEqualsIgnoreCase",
+            Operator::StringNotEqualsIgnoreCase => "StringNotEqualsIgnoreCase",
+            Operator::StringLike => "StringLike",
+            Operator::StringNotLike => "StringNotLike",
+            Operator::NumericEquals => "NumericEquals",
+            Operator::NumericNotEquals => "NumericNotEquals",
+            Operator::NumericLessThan => "NumericLessThan",
+            Operator::NumericLessThanEquals => "NumericLessThanEquals",
+            Operator::NumericGreaterThan => "NumericGreaterThan",
+            Operator::NumericGreaterThanEquals => "NumericGreaterThanEquals",
+            Operator::DateEquals => "DateEquals",
+            Operator::DateNotEquals => "DateNotEquals",
+            Operator::DateLessThan => "DateLessThan",
+            Operator::DateLessThanEquals => "DateLessThanEquals",
+            Operator::DateGreaterThan => "DateGreaterThan",
+            Operator::DateGreaterThanEquals => "DateGreaterThanEquals",
+            Operator::Bool => "Bool",
+            Operator::BinaryEquals => "BinaryEquals",
+            Operator::IpAddress => "IpAddress",
+            Operator::NotIpAddress => "NotIpAddress",
+            Operator::ArnEquals => "ArnEquals",
+            Operator::ArnLike => "ArnLike",
+            Operator::ArnNotEquals => "ArnNotEquals",
+            Operator::ArnNotLike => "ArnNotLike",
+            Operator::Null => "Null",
+        }
+    }
+
+    /// Negated operators are satisfied when no value matches.
+    #[must_use]
+    pub fn is_negated(self) -> bool {
+        matches!(
+            self,
+            Operator::StringNotEquals
+                | Operator::StringNotEqualsIgnoreCase
+                | Operator::StringNotLike
+                | Operator::NumericNotEquals
+                | Operator::DateNotEquals
+                | Operator::NotIpAddress
+                | Operator::ArnNotEquals
+                | Operator::ArnNotLike
+        )
+    }
+
+    /// String and ARN operators support policy variables in values.
+    #[must_use]
+    pub fn supports_variables(self) -> bool {
+        matches!(
+            self,
+            Operator::StringEquals
+                | Operator::StringNotEquals
+                | Operator::StringEqualsIgnoreCase
+                | Operator::StringNotEqualsIgnoreCase
+                | Operator::StringLike
+                | Operator::StringNotLike
+                | Operator::ArnEquals
+                | Operator::ArnLike
+                | Operator::ArnNotEquals
+                | Operator::ArnNotLike
+        )
+    }
+}
+
+/// A set operator which tests multivalued condition keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SetQualifier {
+    ForAnyValue,
+    ForAllValues,
+}
+
+/// A condition operator like `ForAnyValue:StringLikeIfExists`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConditionOperator {
+    pub qualifier: Option<SetQualifier>,
+    pub operator: Operator,
+    pub if_exists: bool,
+}
+
+impl ConditionOperator {
+    #[must_use]
+    pub fn new(operator: Operator) -> Self {
+        Self {
+            qualifier: None,
+            operator,
+            if_exists: false,
+        }
+    }
+}
+
+impl From<Operator> for ConditionOperator {
+    fn from(operator: Operator) -> Self {
+        Self::new(operator)
+    }
+}
+
+impl FromStr for ConditionOperator {
+    type Err = ConditionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unknown = || ConditionError::UnknownOperator(s.to_owned());
+
+        let (qualifier, rest) = if let Some(rest) = s.strip_prefix("ForAnyValue:") {
+            (Some(SetQualifier::ForAnyValue), rest)
+        } else if let Some(rest) = s.strip_prefix("ForAllValues:") {
+            (Some(SetQualifier::ForAllValues), rest)
+        } else {
+            (None, s)
+        };
+
+        let (name, if_exists) = match rest.strip_suffix("IfExists") {
+            Some(name) => (name, true),
+            None => (rest, false),
+        };
+
+        let operator = OPERATORS.iter().copied().find(|op| op.as_str() == name).ok_or_else(unknown)?;
+        if operator == Operator::Null && (if_exists || qualifier.is_some()) {
+            return Err(unknown());
+        }
+
+        Ok(Self {
+            qualifier,
+            operator,
+            if_exists,
+        })
+    }
+}
+
+impl fmt::Display for ConditionOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.qualifier {
+            Some(SetQualifier::ForAnyValue) => f.write_str("ForAnyValue:")?,
+            Some(SetQualifier::ForAllValues) => f.write_str("ForAllValues:")?,
+            None => {}
+        }
+        f.write_str(self.operator.as_str())?;
+        if self.if_exists {
+            f.write_str("IfExists")?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for ConditionOperator {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ConditionOperator {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    Equal,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+
+impl Comparison {
+    fn test<T: PartialOrd>(self, lhs: &T, rhs: &T) -> bool {
+        match self {
+            Comparison::Equal => lhs == rhs,
+            Comparison::Less => lhs < rhs,
+            Comparison::LessEqual => lhs <= rhs,
+            Comparison::Greater => lhs > rhs,
+            Comparison::GreaterEqual => lhs >= rhs,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct IpNet {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpNet {
+    fn parse(s: &str) -> Option<Self> {
+        let (addr, prefix) = match s.split_once('/') {
+            Some((addr, prefix)) => (addr.parse::<IpAddr>().ok()?, Some(prefix.parse::<u8>().ok()?)),
+            None => (s.parse::<IpAddr>().ok()?, None),
+        };
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = prefix.unwrap_or(max);
+        (prefix <= max).then_some(Self { addr, prefix })
+    }
+
+    fn contains(&self, ip: &IpAddr) -> bool {
+        let (net, ip, bits) = match (self.addr, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => (u128::from(net.to_bits()), u128::from(ip.to_bits()), 32),
+            (IpAddr::V6(net), IpAddr::V6(ip)) => (net.to_bits(), ip.to_bits(), 128),
+            _ => return false,
+        };
+        let shift = bits - u32::from(self.prefix);
+        shift >= bits || (net >> shift) == (ip >> shift)
+    }
+}
+
+#[derive(Debug)]
+enum Matcher {
+    String(Vec<String>),
+    StringIgnoreCase(Vec<String>),
+    Like(PatternSet),
+    Numeric(Comparison, Vec<f64>),
+    Date(Comparison, Vec<OffsetDateTime>),
+    Bool(bool),
+    Binary(Vec<String>),
+    Ip(Vec<IpNet>),
+    Arn(Vec<String>),
+    Null(bool),
+}
+
+fn parse_number(s: &str) -> Option<f64> {
+    s.parse::<f64>().ok().filter(|n| n.is_finite())
+}
+
+/// Dates are in ISO 8601 format or epoch seconds.
+fn parse_date(s: &str) -> Option<OffsetDateTime> {
+    if let Ok(t) = OffsetDateTime::parse(s, &Rfc3339) {
+        return Some(t);
+    }
+    if let Ok(secs) = s.parse::<i64>() {
+        return OffsetDateTime::from_unix_timestamp(secs).ok();
+    }
+    let date = time::Date::parse(s, time::macros::format_description!("[year]-[month]-[day]")).ok()?;
+    Some(date.midnight().assume_utc())
+}
+
+fn parse_bool(s: &str) -> Option<bool> {
+    if s.eq_ignore_ascii_case("true") {
+        Some(true)
+    } else if s.eq_ignore_ascii_case("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// ARNs are compared segment by segment, and each segment can contain wildcards.
+fn is_arn_match(pattern: &str, arn: &str) -> bool {
+    let mut pattern_parts = pattern.splitn(6, ':');
+    let mut arn_parts = arn.splitn(6, ':');
+    for _ in 0..6 {
+        match (pattern_parts.next(), arn_parts.next()) {
+            (Some(p), Some(a)) if p == a || (!p.is_empty() && is_wildcard_match(p, a)) => {}
+            _ => return false,
+        }
+    }
+    true
+}
+
+impl Matcher {
+    fn compile(op: ConditionOperator, values: &[String]) -> Result<Self, ConditionError> {
+        let invalid = |value: &String| ConditionError::InvalidValue {
+            operator: op,
+            value: value.clone(),
+        };
+        let numbers =
+            || -> Result<Vec<f64>, ConditionError> { values.iter().map(|v| parse_number(v).ok_or_else(|| invalid(v))).collect() };
+        let dates = || -> Result<Vec<OffsetDateTime>, ConditionError> {
+            values.iter().map(|v| parse_date(v).ok_or_else(|| invalid(v))).collect()
+        };
+        let single_bool = || -> Result<bool, ConditionError> {
+            match values {
+                [v] => parse_bool(v).ok_or_else(|| invalid(v)),
+                _ => Err(ConditionError::InvalidValue {
+                    operator: op,
+                    value: values.join(","),
+                }),
+            }
+        };
+
+        Ok(match op.operator {
+            Operator::StringEquals | Operator::StringNotEquals => Matcher::String(values.to_owned()),
+            Operator::StringEqualsIgnoreCase | Operator::StringNotEqualsIgnoreCase => {
+                Matcher::StringIgnoreCase(values.to_owned())
+            }
+            Operator::StringLike | Operator::StringNotLike => {
+                // an empty pattern is valid in conditions, like `"s3:prefix": ["", "home/"]`
+                Matcher::Like(PatternSet::from_patterns(values.iter().map(|v| Pattern::parse(v)).collect())?)
+            }
+            Operator::NumericEquals | Operator::NumericNotEquals => Matcher::Numeric(Comparison::Equal, numbers()?),
+            Operator::NumericLessThan => Matcher::Numeric(Comparison::Less, numbers()?),
+            Operator::NumericLessThanEquals => Matcher::Numeric(Comparison::LessEqual, numbers()?),
+            Operator::NumericGreaterThan => Matcher::Numeric(Comparison::Greater, numbers()?),
+            Operator::NumericGreaterThanEquals => Matcher::Numeric(Comparison::GreaterEqual, numbers()?),
+            Operator::DateEquals | Operator::DateNotEquals => Matcher::Date(Comparison::Equal, dates()?),
+            Operator::DateLessThan => Matcher::Date(Comparison::Less, dates()?),
+            Operator::DateLessThanEquals => Matcher::Date(Comparison::LessEqual, dates()?),
+            Operator::DateGreaterThan => Matcher::Date(Comparison::Greater, dates()?),
+            Operator::DateGreaterThanEquals => Matcher::Date(Comparison::GreaterEqual, dates()?),
+            Operator::Bool => Matcher::Bool(single_bool()?),
+            Operator::BinaryEquals => Matcher::Binary(values.to_owned()),
+            Operator::IpAddress | Operator::NotIpAddress => {
+                let nets = values.iter().map(|v| IpNet::parse(v).ok_or_else(|| invalid(v)));
+                Matcher::Ip(nets.collect::<Result<_, _>>()?)
+            }
+            Operator::ArnEquals | Operator::ArnLike | Operator::ArnNotEquals | Operator::ArnNotLike => {
+                if let Some(v) = values.iter().find(|v| v.splitn(6, ':').count() != 6) {
+                    return Err(invalid(v));
+                }
+                Matcher::Arn(values.to_owned())
+            }
+            Operator::Null => Matcher::Null(single_bool()?),
+        })
+    }
+
+    /// Checks if a value of the request matches any value of the policy.
+    fn is_match(&self, value: &str) -> bool {
+        match self {
+            Matcher::String(expected) | Matcher::Binary(expected) => expected.iter().any(|e| e == value),
+            Matcher::StringIgnoreCase(expected) => expected.iter().any(|e| e.to_lowercase() == value.to_lowercase()),
+            Matcher::Like(patterns) => patterns.is_match(value),
+            Matcher::Numeric(ord, expected) => parse_number(value).is_some_and(|v| expected.iter().any(|e| ord.test(&v, e))),
+            Matcher::Date(ord, expected) => parse_date(value).is_some_and(|v| expected.iter().any(|e| ord.test(&v, e))),
+            Matcher::Bool(expected) => parse_bool(value) == Some(*expected),
+            Matcher::Ip(nets) => value
+                .parse::<IpAddr>()
+                .is_ok_and(|ip| nets.iter().any(|net| net.contains(&ip))),
+            Matcher::Arn(patterns) => patterns.iter().any(|p| is_arn_match(p, value)),
+            Matcher::Null(_) => false,
+        }
+    }
+}
+
+/// A compiled condition of a single key
+#[derive(Debug)]
+pub(crate) struct Condition {
+    op: ConditionOperator,
+    matcher: Matcher,
+    key: String,
+    /// The values with policy variables, which are substituted before matching
+    templates: Option<Vec<Template>>,
+}
+
+impl Condition {
+    /// Compiles all conditions of a statement.
+    ///
+    /// Policy variables are supported by string and ARN operators if `variables` is true.
+    pub(crate) fn compile_rule(rule: &ConditionRule, variables: bool) -> Result<Vec<Condition>, ConditionError> {
+        let mut ans = Vec::new();
+        for (op, kvs) in &rule.0 {
+            for (key, values) in &kvs.0 {
+                let values = values.as_slice();
+                let templates = (variables && op.operator.supports_variables())
+                    .then(|| Self::compile_templates(values))
+                    .flatten();
+                ans.push(Condition {
+                    op: *op,
+                    matcher: Matcher::compile(*op, values)?,
+                    key: key.to_ascii_lowercase(),
+                    templates,
+                });
+            }
+        }
+        Ok(ans)
+    }
+
+    fn compile_templates(values: &[String]) -> Option<Vec<Template>> {
+        let templates: Vec<_> = values.iter().map(|v| Template::parse(v)).collect();
+        if templates.iter().all(Option::is_none) {
+            return None;
+        }
+        let templates = templates
+            .into_iter()
+            .zip(values)
+            .map(|(t, v)| t.unwrap_or_else(|| Template::text(v)));
+        Some(templates.collect())
+    }
+
+    /// Substitutes variables and compiles the values again.
+    fn resolve(&self, templates: &[Template], cx: &RequestContext) -> Option<Matcher> {
+        if let Matcher::Like(_) = self.matcher {
+            let patterns = templates.iter().filter_map(|t| t.resolve_pattern(cx)).collect();
+            return PatternSet::from_patterns(patterns).ok().map(Matcher::Like);
+        }
+        let values: Vec<String> = templates.iter().filter_map(|t| t.resolve_text(cx)).collect();
+        Matcher::compile(self.op, &values).ok()
+    }
+
+    /// Checks if the request context satisfies the condition.
+    pub(crate) fn is_match(&self, cx: &RequestContext) -> bool {
+        let values = cx.condition_values(&self.key).filter(|v| !v.is_empty());
+
+        let resolved;
+        let matcher = match &self.templates {
+            None => &self.matcher,
+            Some(templates) => match self.resolve(templates, cx) {
+                Some(m) => {
+                    resolved = m;
+                    &resolved
+                }
+                None => return false,
+            },
+        };
+
+        if let Matcher::Null(expected) = self.matcher {
+            return values.is_none() == expected;
+        }
+
+        let negated = self.op.operator.is_negated();
+
+        let Some(values) = values else {
+            return match self.op.qualifier {
+                Some(SetQualifier::ForAllValues) => true,
+                Some(SetQualifier::ForAnyValue) => false,
+                None => self.op.if_exists || negated,
+            };
+        };
+
+        let satisfies = |v: &String| matcher.is_match(v) != negated;
+        match self.op.qualifier {
+            Some(SetQualifier::ForAllValues) => values.iter().all(satisfies),
+            None if negated => values.iter().all(satisfies),
+            Some(SetQualifier::ForAnyValue) | None => values.iter().any(satisfies),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::model::Policy;
+
+    fn is_match(condition: &str, cx: &RequestContext) -> bool {
+        let json = format!(r#"{{"Statement": {{"Effect": "Allow", "Action": "*", "Resource": "*", "Condition": {condition}}}}}"#);
+        let policy: Policy = serde_json::from_str(&json).unwrap();
+        let rule = policy.statement.as_slice()[0].condition.as_ref().unwrap();
+        let conditions = Condition::compile_rule(rule, true).unwrap();
+        conditions.iter().all(|c| c.is_match(cx))
+    }
+
+    fn context(kvs: &[(&str, &[&str])]) -> RequestContext {
+        let mut cx = RequestContext::new("s3:GetObject", "arn:aws:s3:::bucket/key");
+        for &(key, values) in kvs {
+            cx.set_condition_values(key, values.iter().map(|&v| v.to_owned()).collect());
+        }
+        cx
+    }
+
+    #[test]
+    fn parse_operator() {
+        let cases = [
+            ("StringEquals", None, Operator::StringEquals, false),
+            ("StringLikeIfExists", None, Operator::StringLike, true),
+            ("ForAnyValue:StringEquals", Some(SetQualifier::ForAnyValue), Operator::StringEquals, false),
+            ("ForAllValues:ArnLikeIfExists", Some(SetQualifier::ForAllValues), Operator::ArnLike, true),
+            ("Null", None, Operator::Null, false),
+        ];
+        for (s, qualifier, operator, if_exists) in cases {
+            let op: ConditionOperator = s.parse().unwrap();
+            assert_eq!(op.qualifier, qualifier);
+            assert_eq!(op.operator, operator);
+            assert_eq!(op.if_exists, if_exists);
+            assert_eq!(op.to_string(), s);
+        }
+
+        for s in [
+            "",
+            "Foo",
+            "StringEqual",
+            "ForAnyValue:",
+            "NullIfExists",
+            "ForSomeValues:StringEquals",
+        ] {
+            assert!(s.parse::<ConditionOperator>().is_err(), "{s}");
+        }
+
+        let json =
+            r#"{"Statement": {"Effect": "Allow", "Action": "*", "Resource": "*", "Condition": {"Foo": {"aws:Bar": "baz"}}}}"#;
+        let err = serde_json::from_str::<Policy>(json).unwrap_err();
+        assert!(err.to_string().contains("Unknown condition operator: Foo"));
+    }
+
+    #[test]
+    fn string() {
+        let cx = context(&[("s3:prefix", &["home/alice/"])]);
+        assert!(is_match(r#"{"StringEquals": {"s3:prefix": ["", "home/alice/"]}}"#, &cx));
+        assert!(is_match(r#"{"StringLike": {"s3:prefix": "home/*"}}"#, &cx));
+        assert!(is_match(r#"{"StringEqualsIgnoreCase": {"S3:Prefix": "HOME/ALICE/"}}"#, &cx));
+        assert!(!is_match(r#"{"StringNotEquals": {"s3:prefix": "home/alice/"}}"#, &cx));
+        assert!(is_match(r#"{"StringNotLike": {"s3:prefix": "home/bob/*"}}"#, &cx));
+
+        let cx = context(&[]);
+        assert!(!is_match(r#"{"StringEquals": {"s3:prefix": "home/"}}"#, &cx));
+        assert!(is_match(r#"{"StringEqualsIfExists": {"s3:prefix": "home/"}}"#, &cx));
+        assert!(is_match(r#"{"StringNotEquals": {"s3:prefix": "home/"}}"#, &cx));
+    }
+
+    #[test]
+    fn numeric_and_date() {
+        let cx = context(&[("s3:max-keys", &["10"]), ("aws:CurrentTime", &["2020-01-01T00:00:00Z"])]);
+        assert!(is_match(r#"{"NumericLessThanEquals": {"s3:max-keys": 10}}"#, &cx));
+        assert!(!is_match(r#"{"NumericLessThan": {"s3:max-keys": "10"}}"#, &cx));
+        assert!(is_match(r#"{"NumericNotEquals": {"s3:max-keys": "5"}}"#, &cx));
+        assert!(is_match(r#"{"DateGreaterThan": {"aws:CurrentTime": "2019-12-31"}}"#, &cx));
+        assert!(is_match(r#"{"DateLessThan": {"aws:CurrentTime": "1577836801"}}"#, &cx));
+        assert!(!is_match(r#"{"DateNotEquals": {"aws:CurrentTime": "2020-01-01T00:00:00Z"}}"#, &cx));
+    }
+
+    #[test]
+    fn bool_and_null() {
+        let cx = context(&[("aws:SecureTransport", &["false"])]);
+        assert!(is_match(r#"{"Bool": {"aws:SecureTransport": "false"}}"#, &cx));
+        assert!(!is_match(r#"{"Bool": {"aws:SecureTransport": true}}"#, &cx));
+        assert!(is_match(
+            r#"{"Null": {"aws:SecureTransport": "false", "aws:TokenIssueTime": "true"}}"#,
+            &cx
+        ));
+        assert!(!is_match(r#"{"Null": {"aws:SecureTransport": "true"}}"#, &cx));
+    }
+
+    #[test]
+    fn ip_address() {
+        let cx = context(&[("aws:SourceIp", &["203.0.113.7"])]);
+        assert!(is_match(r#"{"IpAddress": {"aws:SourceIp": ["192.0.2.0/24", "203.0.113.0/24"]}}"#, &cx));
+        assert!(is_match(r#"{"IpAddress": {"aws:SourceIp": "203.0.113.7"}}"#, &cx));
+        assert!(is_match(r#"{"IpAddress": {"aws:SourceIp": "0.0.0.0/0"}}"#, &cx));
+        assert!(!is_match(r#"{"IpAddress": {"aws:SourceIp": "2001:db8::/32"}}"#, &cx));
+        assert!(!is_match(r#"{"NotIpAddress": {"aws:SourceIp": "203.0.113.0/24"}}"#, &cx));
+
+        let cx = context(&[("aws:SourceIp", &["2001:db8::1"])]);
+        assert!(is_match(r#"{"IpAddress": {"aws:SourceIp": "2001:db8::/32"}}"#, &cx));
+    }
+
+    #[test]
+    fn arn() {
+        let cx = context(&[("aws:SourceArn", &["arn:aws:sns:us-east-1:123456789012:topic"])]);
+        assert!(is_match(r#"{"ArnLike": {"aws:SourceArn": "arn:aws:sns:*:123456789012:*"}}"#, &cx));
+        assert!(is_match(
+            r#"{"ArnEquals": {"aws:SourceArn": "arn:aws:sns:us-east-1:123456789012:topic"}}"#,
+            &cx
+        ));
+        assert!(!is_match(r#"{"ArnLike": {"aws:SourceArn": "arn:aws:s3:::*"}}"#, &cx));
+        assert!(is_match(r#"{"ArnNotLike": {"aws:SourceArn": "arn:aws:sns:*:111122223333:*"}}"#, &cx));
+    }
+
+    #[test]
+    fn set_qualifiers() {
+        let cx = context(&[("aws:TagKeys", &["env", "team"])]);
+        assert!(is_match(
+            r#"{"ForAllValues:StringEquals": {"aws:TagKeys": ["env", "team", "owner"]}}"#,
+            &cx
+        ));
+        assert!(!is_match(r#"{"ForAllValues:StringEquals": {"aws:TagKeys": ["env"]}}"#, &cx));
+        assert!(is_match(r#"{"ForAnyValue:StringEquals": {"aws:TagKeys": ["team"]}}"#, &cx));
+        assert!(!is_match(r#"{"ForAnyValue:StringEquals": {"aws:TagKeys": ["owner"]}}"#, &cx));
+        assert!(is_match(r#"{"ForAnyValue:StringNotEquals": {"aws:TagKeys": ["env"]}}"#, &cx));
+
+        let cx = context(&[]);
+        assert!(is_match(r#"{"ForAllValues:StringEquals": {"aws:TagKeys": ["env"]}}"#, &cx));
+        assert!(!is_match(r#"{"ForAnyValue:StringEquals": {"aws:TagKeys": ["env"]}}"#, &cx));
+    }
+}
@@ -0,0 +1,508 @@
+//! Policy evaluation
+//!
+//! See <https://docs.aws.amazon.com/IAM/latest/UserGuide/reference_policies_evaluation-logic.html>
+
+use crate::condition::{Condition, ConditionError};
+use crate::model::{ActionRule, Effect, Policy, Principal, PrincipalRule, ResourceRule, Statement, Version, WildcardOneOrMore};
+use crate::pattern::{PatternError, PatternSet};
+use crate::variable::Template;
+
+use std::collections::HashMap;
+
+#[derive(Debug, thiserror::Error)]
+pub enum EvalError {
+    #[error(transparent)]
+    Pattern(#[from] PatternError),
+
+    #[error(transparent)]
+    Condition(#[from] ConditionError),
+}
+
+/// The result of policy evaluation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    /// A statement allows the request and no statement denies it.
+    Allow,
+
+    /// A statement denies the request.
+    ExplicitDeny,
+
+    /// No statement allows or denies the request.
+    ImplicitDeny,
+}
+
+impl Decision {
+    /// Combines the decisions of multiple policies.
+    ///
+    /// An explicit deny overrides an allow, and an allow overrides an implicit deny.
+    #[must_use]
+    pub fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Decision::ExplicitDeny, _) | (_, Decision::ExplicitDeny) => Decision::ExplicitDeny,
+            (Decision::Allow, _) | (_, Decision::Allow) => Decision::Allow,
+            _ => Decision::ImplicitDeny,
+        }
+    }
+
+    #[must_use]
+    pub fn is_allowed(self) -> bool {
+        self == Decision::Allow
+    }
+}
+
+/// The request context of policy evaluation
+#[derive(Debug, Clone, Default)]
+pub struct RequestContext {
+    principals: Vec<(String, String)>,
+    action: String,
+    resource: String,
+    condition_values: HashMap<String, Vec<String>>,
+}
+
+impl RequestContext {
+    /// Creates a request context of an anonymous request.
+    ///
+    /// `action` is like `s3:GetObject` and `resource` is like `arn:aws:s3:::bucket/key`.
+    #[must_use]
+    pub fn new(action: impl Into<String>, resource: impl Into<String>) -> Self {
+        Self {
+            action: action.into(),
+            resource: resource.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Adds a principal of the caller, like `("AWS", "arn:aws:iam::123456789012:user/alice")`.
+    ///
+    /// A caller may have multiple principals, such as its own ARN and the root ARN of its account.
+    /// A request without principals is anonymous.
+    pub fn add_principal(&mut self, principal_type: impl Into<String>, id: impl Into<String>) {
+        self.principals.push((principal_type.into(), id.into()));
+    }
+
+    /// Sets the values of a condition key, like `aws:SecureTransport`.
+    ///
+    /// Condition keys are case-insensitive.
+    pub fn set_condition_values(&mut self, key: &str, values: Vec<String>) {
+        self.condition_values.insert(key.to_ascii_lowercase(), values);
+    }
+
+    /// Sets the value of a single-valued condition key.
+    pub fn set_condition_value(&mut self, key: &str, value: impl Into<String>) {
+        self.set_condition_values(key, vec![value.into()]);
+    }
+
+    #[must_use]
+    pub fn principals(&self) -> &[(String, String)] {
+        &self.principals
+    }
+
+    #[must_use]
+    pub fn is_anonymous(&self) -> bool {
+        self.principals.is_empty()
+    }
+
+    #[must_use]
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+
+    #[must_use]
+    pub fn resource(&self) -> &str {
+        &self.resource
+    }
+
+    #[must_use]
+    pub fn condition_values(&self, key: &str) -> Option<&[String]> {
+        match self.condition_values.get(key) {
+            Some(values) => Some(values),
+            None => self.condition_values.get(&key.to_ascii_lowercase()).map(Vec::as_slice),
+        }
+    }
+}
+
+/// A compiled policy
+#[derive(Debug)]
+pub struct Evaluator {
+    statements: Vec<CompiledStatement>,
+}
+
+#[derive(Debug)]
+struct CompiledStatement {
+    effect: Effect,
+    principal: Option<(PrincipalMatcher, bool)>,
+    action: (Matcher, bool),
+    resource: (Matcher, bool),
+    conditions: Vec<Condition>,
+}
+
+#[derive(Debug)]
+enum Matcher {
+    Any,
+    Patterns { patterns: PatternSet, templates: Vec<Template> },
+}
+
+#[derive(Debug)]
+enum PrincipalMatcher {
+    Any,
+    Map(Vec<(String, Vec<String>)>),
+}
+
+impl Evaluator {
+    /// Compiles a policy.
+    ///
+    /// Policy variables like `${aws:username}` are only supported by the version `2012-10-17`.
+    ///
+    /// # Errors
+    /// Returns an error if the policy contains invalid patterns or unsupported conditions.
+    pub fn new(policy: &Policy) -> Result<Self, EvalError> {
+        let variables = policy.version == Some(Version::V2012_10_17);
+        let statements = policy
+            .statement
+            .as_slice()
+            .iter()
+            .map(|s| CompiledStatement::compile(s, variables))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { statements })
+    }
+
+    /// Evaluates the request against the policy.
+    ///
+    /// An explicit deny in any statement overrides all allows.
+    #[must_use]
+    pub fn evaluate(&self, cx: &RequestContext) -> Decision {
+        let mut decision = Decision::ImplicitDeny;
+        for statement in &self.statements {
+            if statement.is_match(cx) {
+                match statement.effect {
+                    Effect::Allow => decision = Decision::Allow,
+                    Effect::Deny => return Decision::ExplicitDeny,
+                }
+            }
+        }
+        decision
+    }
+}
+
+impl Matcher {
+    /// Action names are case-insensitive.
+    fn actions(rule: &WildcardOneOrMore<String>) -> Result<Self, PatternError> {
+        let Some(values) = rule.as_slice() else { return Ok(Matcher::Any) };
+        Ok(Matcher::Patterns {
+            patterns: PatternSet::new_ignore_case(values.iter().map(String::as_str))?,
+            templates: Vec::new(),
+        })
+    }
+
+    fn resources(rule: &WildcardOneOrMore<String>, variables: bool) -> Result<Self, PatternError> {
+        let Some(values) = rule.as_slice() else { return Ok(Matcher::Any) };
+
+        let mut patterns = Vec::new();
+        let mut templates = Vec::new();
+        for value in values {
+            match Template::parse(value).filter(|_| variables) {
+                Some(template) => templates.push(template),
+                None => patterns.push(value.as_str()),
+            }
+        }
+
+        Ok(Matcher::Patterns {
+            patterns: PatternSet::new(patterns)?,
+            templates,
+        })
+    }
+
+    /// Variables are substituted before matching.
+    /// A pattern never matches if any of its variables is missing.
+    fn is_match(&self, input: &str, cx: &RequestContext) -> bool {
+        match self {
+            Matcher::Any => true,
+            Matcher::Patterns { patterns, templates } => {
+                patterns.is_match(input)
+                    || templates
+                        .iter()
+                        .any(|t| t.resolve_pattern(cx).is_some_and(|p| p.is_match(input)))
+            }
+        }
+    }
+}
+
+impl PrincipalMatcher {
+    fn compile(principal: &Principal) -> Self {
+        match principal {
+            Principal::Wildcard => PrincipalMatcher::Any,
+            Principal::Map(map) => {
+                let is_any = map.get("AWS").is_some_and(|ids| ids.as_slice().iter().any(|id| id == "*"));
+                if is_any {
+                    return PrincipalMatcher::Any;
+                }
+                let map = map
+                    .iter()
+                    .map(|(ty, ids)| (ty.clone(), ids.as_slice().iter().map(|id| normalize_principal(ty, id)).collect()))
+                    .collect();
+                PrincipalMatcher::Map(map)
+            }
+        }
+    }
+
+    fn is_match(&self, cx: &RequestContext) -> bool {
+        match self {
+            PrincipalMatcher::Any => true,
+            PrincipalMatcher::Map(map) => cx.principals().iter().any(|(ty, id)| {
+                map.iter()
+                    .any(|(expected_ty, ids)| expected_ty == ty && ids.iter().any(|expected| expected == id))
+            }),
+        }
+    }
+}
+
+/// An AWS account id in principals is the shorthand of the root user of the account.
+fn normalize_principal(ty: &str, id: &str) -> String {
+    if ty == "AWS" && id.len() == 12 && id.bytes().all(|b| b.is_ascii_digit()) {
+        return format!("arn:aws:iam::{id}:root");
+    }
+    id.to_owned()
+}
+
+impl CompiledStatement {
+    fn compile(statement: &Statement, variables: bool) -> Result<Self, EvalError> {
+        let principal = statement.principal.as_ref().map(|rule| match rule {
+            PrincipalRule::Principal(p) => (PrincipalMatcher::compile(p), false),
+            PrincipalRule::NotPrincipal(p) => (PrincipalMatcher::compile(p), true),
+        });
+        let action = match &statement.action {
+            ActionRule::Action(rule) => (Matcher::actions(rule)?, false),
+            ActionRule::NotAction(rule) => (Matcher::actions(rule)?, true),
+        };
+        let resource = match &statement.resource {
+            ResourceRule::Resource(rule) => (Matcher::resources(rule, variables)?, false),
+            ResourceRule::NotResource(rule) => (Matcher::resources(rule, variables)?, true),
+        };
+        let conditions = match &statement.condition {
+            Some(rule) => Condition::compile_rule(rule, variables)?,
+            None => Vec::new(),
+        };
+        Ok(Self {
+            effect: statement.effect.clone(),
+            principal,
+            action,
+            resource,
+            conditions,
+        })
+    }
+
+    fn is_match(&self, cx: &RequestContext) -> bool {
+        if let Some((matcher, negated)) = &self.principal {
+            if matcher.is_match(cx) == *negated {
+                return false;
+            }
+        }
+        let (matcher, negated) = &self.action;
+        if matcher.is_match(cx.action(), cx) == *negated {
+            return false;
+        }
+        let (matcher, negated) = &self.resource;
+        if matcher.is_match(cx.resource(), cx) == *negated {
+            return false;
+        }
+        self.conditions.iter().all(|c| c.is_match(cx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluator(json: &str) -> Evaluator {
+        let policy: Policy = serde_json::from_str(json).unwrap();
+        Evaluator::new(&policy).unwrap()
+    }
+
+    fn user(action: &str, resource: &str, arn: &str) -> RequestContext {
+        let mut cx = RequestContext::new(action, resource);
+        cx.add_principal("AWS", arn);
+        cx
+    }
+
+    #[test]
+    fn example1() {
+        let e = evaluator(crate::tests::example1_json());
+
+        let cx = RequestContext::new("s3:ListAllMyBuckets", "arn:aws:s3:::");
+        assert_eq!(e.evaluate(&cx), Decision::Allow);
+
+        let cx = RequestContext::new("s3:GetObject", "arn:aws:s3:::confidential-data/key");
+        assert_eq!(e.evaluate(&cx), Decision::ImplicitDeny);
+
+        let mut cx = RequestContext::new("s3:GetObject", "arn:aws:s3:::confidential-data/key");
+        cx.set_condition_value("aws:MultiFactorAuthPresent", "true");
+        assert_eq!(e.evaluate(&cx), Decision::Allow);
+
+        let mut cx = RequestContext::new("s3:PutObject", "arn:aws:s3:::confidential-data/key");
+        cx.set_condition_value("aws:MultiFactorAuthPresent", "true");
+        assert_eq!(e.evaluate(&cx), Decision::ImplicitDeny);
+    }
+
+    #[test]
+    fn example2() {
+        let e = evaluator(crate::tests::example2_json());
+
+        let cx = RequestContext::new("s3:ListBucket", "arn:aws:s3:::example_bucket");
+        assert_eq!(e.evaluate(&cx), Decision::Allow);
+
+        let cx = RequestContext::new("s3:ListBucket", "arn:aws:s3:::other_bucket");
+        assert_eq!(e.evaluate(&cx), Decision::ImplicitDeny);
+
+        let cx = RequestContext::new("s3:GetObject", "arn:aws:s3:::example_bucket/key");
+        assert_eq!(e.evaluate(&cx), Decision::ImplicitDeny);
+    }
+
+    #[test]
+    fn explicit_deny() {
+        let e = evaluator(
+            r#"{
+                "Version": "2012-10-17",
+                "Statement": [
+                    {"Effect": "Allow", "Principal": "*", "Action": "s3:*", "Resource": "arn:aws:s3:::bucket/*"},
+                    {"Effect": "Deny", "Principal": "*", "Action": "s3:DeleteObject", "Resource": "arn:aws:s3:::bucket/*"}
+                ]
+            }"#,
+        );
+
+        let cx = RequestContext::new("s3:GetObject", "arn:aws:s3:::bucket/key");
+        assert_eq!(e.evaluate(&cx), Decision::Allow);
+
+        let cx = RequestContext::new("s3:DeleteObject", "arn:aws:s3:::bucket/key");
+        assert_eq!(e.evaluate(&cx), Decision::ExplicitDeny);
+
+        let cx = RequestContext::new("s3:GetObject", "arn:aws:s3:::other/key");
+        assert_eq!(e.evaluate(&cx), Decision::ImplicitDeny);
+    }
+
+    #[test]
+    fn principal() {
+        let e = evaluator(crate::tests::example3_json());
+
+        let cx = user("s3:GetObject", "arn:aws:s3:::mybucket/key", "arn:aws:iam::account-id:root");
+        assert_eq!(e.evaluate(&cx), Decision::Allow);
+
+        let cx = user("s3:GetObject", "arn:aws:s3:::mybucket/key", "arn:aws:iam::111122223333:root");
+        assert_eq!(e.evaluate(&cx), Decision::ImplicitDeny);
+
+        let cx = RequestContext::new("s3:GetObject", "arn:aws:s3:::mybucket/key");
+        assert_eq!(e.evaluate(&cx), Decision::ImplicitDeny);
+
+        let e = evaluator(
+            r#"{
+                "Statement": {"Effect": "Allow", "Principal": {"AWS": "111122223333"}, "Action": "s3:*", "Resource": "*"}
+            }"#,
+        );
+        let cx = user("s3:GetObject", "arn:aws:s3:::mybucket/key", "arn:aws:iam::111122223333:root");
+        assert_eq!(e.evaluate(&cx), Decision::Allow);
+    }
+
+    #[test]
+    fn not_rules() {
+        let e = evaluator(
+            r#"{
+                "Statement": [
+                    {"Effect": "Allow", "Principal": "*", "NotAction": "s3:DeleteObject", "NotResource": "arn:aws:s3:::bucket/private/*"},
+                    {"Effect": "Deny", "NotPrincipal": {"AWS": "arn:aws:iam::111122223333:user/admin"}, "Action": "s3:PutObject", "Resource": "*"}
+                ]
+            }"#,
+        );
+
+        let cx = RequestContext::new("s3:GetObject", "arn:aws:s3:::bucket/public/key");
+        assert_eq!(e.evaluate(&cx), Decision::Allow);
+
+        let cx = RequestContext::new("s3:GetObject", "arn:aws:s3:::bucket/private/key");
+        assert_eq!(e.evaluate(&cx), Decision::ImplicitDeny);
+
+        let cx = RequestContext::new("s3:DeleteObject", "arn:aws:s3:::bucket/public/key");
+        assert_eq!(e.evaluate(&cx), Decision::ImplicitDeny);
+
+        let cx = user("s3:PutObject", "arn:aws:s3:::bucket/public/key", "arn:aws:iam::111122223333:user/alice");
+        assert_eq!(e.evaluate(&cx), Decision::ExplicitDeny);
+
+        let cx = user("s3:PutObject", "arn:aws:s3:::bucket/public/key", "arn:aws:iam::111122223333:user/admin");
+        assert_eq!(e.evaluate(&cx), Decision::Allow);
+    }
+
+    #[test]
+    fn variables() {
+        let json = r#"{
+            "Version": "2012-10-17",
+            "Statement": [
+                {"Effect": "Allow", "Principal": "*", "Action": "s3:ListBucket", "Resource": "arn:aws:s3:::bucket",
+                 "Condition": {"StringLike": {"s3:prefix": ["", "home/", "home/${aws:username}/*"]}}},
+                {"Effect": "Allow", "Principal": "*", "Action": "s3:*", "Resource": "arn:aws:s3:::bucket/home/${aws:username}/*"},
+                {"Effect": "Allow", "Principal": "*", "Action": "s3:GetObject", "Resource": "arn:aws:s3:::bucket/${*}"}
+            ]
+        }"#;
+        let e = evaluator(json);
+
+        let alice = |action: &str, resource: &str| {
+            let mut cx = user(action, resource, "arn:aws:iam::111122223333:user/alice");
+            cx.set_condition_value("aws:username", "alice");
+            cx
+        };
+
+        let cx = alice("s3:PutObject", "arn:aws:s3:::bucket/home/alice/key");
+        assert_eq!(e.evaluate(&cx), Decision::Allow);
+
+        let cx = alice("s3:PutObject", "arn:aws:s3:::bucket/home/bob/key");
+        assert_eq!(e.evaluate(&cx), Decision::ImplicitDeny);
+
+        let cx = user(
+            "s3:PutObject",
+            "arn:aws:s3:::bucket/home/alice/key",
+            "arn:aws:iam::111122223333:user/alice",
+        );
+        assert_eq!(e.evaluate(&cx), Decision::ImplicitDeny);
+
+        let mut cx = alice("s3:ListBucket", "arn:aws:s3:::bucket");
+        cx.set_condition_value("s3:prefix", "home/alice/docs/");
+        assert_eq!(e.evaluate(&cx), Decision::Allow);
+
+        let mut cx = alice("s3:ListBucket", "arn:aws:s3:::bucket");
+        cx.set_condition_value("s3:prefix", "home/bob/");
+        assert_eq!(e.evaluate(&cx), Decision::ImplicitDeny);
+
+        let cx = alice("s3:GetObject", "arn:aws:s3:::bucket/*");
+        assert_eq!(e.evaluate(&cx), Decision::Allow);
+
+        let cx = alice("s3:GetObject", "arn:aws:s3:::bucket/key");
+        assert_eq!(e.evaluate(&cx), Decision::ImplicitDeny);
+
+        let e = evaluator(&json.replace("2012-10-17", "2008-10-17"));
+        let cx = alice("s3:PutObject", "arn:aws:s3:::bucket/home/alice/key");
+        assert_eq!(e.evaluate(&cx), Decision::ImplicitDeny);
+        let cx = alice("s3:PutObject", "arn:aws:s3:::bucket/home/${aws:username}/key");
+        assert_eq!(e.evaluate(&cx), Decision::Allow);
+    }
+
+    #[test]
+    fn action_case() {
+        let e = evaluator(r#"{"Statement": {"Effect": "Allow", "Principal": "*", "Action": "S3:getobject", "Resource": "*"}}"#);
+        let cx = RequestContext::new("s3:GetObject", "arn:aws:s3:::bucket/key");
+        assert_eq!(e.evaluate(&cx), Decision::Allow);
+        let cx = RequestContext::new("s3:GetObject", "arn:aws:s3:::BUCKET/key");
+        assert_eq!(e.evaluate(&cx), Decision::Allow);
+
+        let e = evaluator(
+            r#"{"Statement": {"Effect": "Allow", "Principal": "*", "Action": "*", "Resource": "arn:aws:s3:::bucket/*"}}"#,
+        );
+        let cx = RequestContext::new("s3:GetObject", "arn:aws:s3:::BUCKET/key");
+        assert_eq!(e.evaluate(&cx), Decision::ImplicitDeny);
+    }
+
+    #[test]
+    fn merge() {
+        use Decision::*;
+
+        assert_eq!(Allow.merge(ImplicitDeny), Allow);
+        assert_eq!(ImplicitDeny.merge(Allow), Allow);
+        assert_eq!(Allow.merge(ExplicitDeny), ExplicitDeny);
+        assert_eq!(ImplicitDeny.merge(ImplicitDeny), ImplicitDeny);
+    }
+}
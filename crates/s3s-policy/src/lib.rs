@@ -1,5 +1,11 @@
+pub mod access;
+pub mod arn;
+pub mod condition;
+pub mod eval;
 pub mod model;
 pub mod pattern;
+pub mod validate;
+mod variable;
 
 #[cfg(test)]
 mod tests;
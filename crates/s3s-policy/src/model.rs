@@ -4,6 +4,7 @@ use std::marker::PhantomData;
 use std::slice;
 
 use indexmap::IndexMap;
+use serde::de::IntoDeserializer;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -81,9 +82,9 @@ pub enum ResourceRule {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ConditionRule(pub IndexMap<ConditionOperator, ConditionKeyValues>);
 
-pub type ConditionOperator = String;
+pub use crate::condition::ConditionOperator;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct ConditionKeyValues(pub IndexMap<String, OneOrMore<String>>);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -148,6 +149,83 @@ impl<'de> Deserialize<'de> for Principal {
     }
 }
 
+/// Condition values can be strings, booleans or numbers.
+struct ConditionValue(String);
+
+impl<'de> Deserialize<'de> for ConditionValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = ConditionValue;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a string, a boolean or a number")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(ConditionValue(value.to_owned()))
+            }
+
+            fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(ConditionValue(value.to_string()))
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(ConditionValue(value.to_string()))
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(ConditionValue(value.to_string()))
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(ConditionValue(value.to_string()))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for ConditionKeyValues {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let map = <IndexMap<String, OneOrMore<ConditionValue>>>::deserialize(deserializer)?;
+        let map = map
+            .into_iter()
+            .map(|(key, values)| {
+                let values = match values {
+                    OneOrMore::One(v) => OneOrMore::One(v.0),
+                    OneOrMore::More(vs) => OneOrMore::More(vs.into_iter().map(|v| v.0).collect()),
+                };
+                (key, values)
+            })
+            .collect();
+        Ok(ConditionKeyValues(map))
+    }
+}
+
 impl<T> Serialize for OneOrMore<T>
 where
     T: Serialize,
@@ -191,6 +269,34 @@ where
                 T::deserialize(de).map(OneOrMore::One)
             }
 
+            fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                T::deserialize(value.into_deserializer()).map(OneOrMore::One)
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                T::deserialize(value.into_deserializer()).map(OneOrMore::One)
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                T::deserialize(value.into_deserializer()).map(OneOrMore::One)
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                T::deserialize(value.into_deserializer()).map(OneOrMore::One)
+            }
+
             fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
             where
                 A: serde::de::MapAccess<'de>,
@@ -524,7 +630,7 @@ mod tests {
                     resource: Resource(more(&["arn:aws:s3:::confidential-data", "arn:aws:s3:::confidential-data/*"])),
                     principal: None,
                     condition: Some(ConditionRule(indexmap! {
-                        "Bool".to_owned() => ConditionKeyValues(
+                        ConditionOperator::new(crate::condition::Operator::Bool) => ConditionKeyValues(
                             indexmap! {
                                 "aws:MultiFactorAuthPresent".to_owned() => OneOrMore::One("true".to_owned())
                             }
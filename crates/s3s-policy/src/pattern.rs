@@ -1,17 +1,99 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use aho_corasick::{AhoCorasick, MatchKind};
+
+/// A compiled set of wildcard patterns
+///
+/// The longest literal segment of every pattern is searched in the input by a single automaton,
+/// so that only the patterns whose literal segment occurs in the input are fully checked.
+#[derive(Debug)]
 pub struct PatternSet {
-    // TODO: rewrite the naive implementation with something like Aho-Corasick
     patterns: Vec<Pattern>,
+
+    /// The patterns without literal segments, which are always checked
+    wildcards: Vec<usize>,
+
+    /// The automaton of literal segments
+    prefilter: Option<AhoCorasick>,
+
+    /// The patterns of each literal segment
+    candidates: Vec<Vec<usize>>,
+
+    ignore_case: bool,
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum PatternError {
     #[error("Invalid pattern")]
     InvalidPattern,
+
+    #[error("Too many patterns")]
+    TooManyPatterns,
 }
 
-#[derive(Debug)]
-struct Pattern {
-    bytes: Vec<u8>,
+/// A compiled pattern
+///
+/// Characters from [`push_literal`](Pattern::push_literal) never act as wildcards.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Pattern {
+    tokens: Vec<Token>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Byte(u8),
+    AnyByte,
+    AnyBytes,
+}
+
+impl Pattern {
+    pub(crate) fn parse(pattern: &str) -> Self {
+        let mut ans = Self::default();
+        ans.push_pattern(pattern);
+        ans
+    }
+
+    /// Appends a pattern, where `*` and `?` are wildcards.
+    pub(crate) fn push_pattern(&mut self, pattern: &str) {
+        self.tokens.extend(pattern.bytes().map(|b| match b {
+            b'*' => Token::AnyBytes,
+            b'?' => Token::AnyByte,
+            _ => Token::Byte(b),
+        }));
+    }
+
+    /// Appends a literal string.
+    pub(crate) fn push_literal(&mut self, literal: &str) {
+        self.tokens.extend(literal.bytes().map(Token::Byte));
+    }
+
+    pub(crate) fn is_match(&self, input: &str) -> bool {
+        match_tokens(&self.tokens, input.as_bytes())
+    }
+
+    fn to_ascii_lowercase(&self) -> Self {
+        let tokens = self.tokens.iter().map(|&t| match t {
+            Token::Byte(b) => Token::Byte(b.to_ascii_lowercase()),
+            _ => t,
+        });
+        Self {
+            tokens: tokens.collect(),
+        }
+    }
+
+    /// Returns the longest run of literal bytes.
+    fn longest_literal(&self) -> Vec<u8> {
+        let runs = self.tokens.split(|t| !matches!(t, Token::Byte(_)));
+        let longest = runs.max_by_key(|run| run.len()).unwrap_or_default();
+        longest
+            .iter()
+            .filter_map(|t| match t {
+                Token::Byte(b) => Some(*b),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 impl PatternSet {
@@ -26,75 +108,146 @@ impl PatternSet {
     /// Returns an error if any pattern is invalid.
     pub fn new<'a>(patterns: impl IntoIterator<Item = &'a str>) -> Result<PatternSet, PatternError> {
         let patterns = patterns.into_iter().map(Self::parse_pattern).collect::<Result<_, _>>()?;
-        Ok(PatternSet { patterns })
+        Self::compile(patterns, false)
+    }
+
+    /// Create a new matcher which ignores ASCII case, such as for IAM action names.
+    ///
+    /// # Errors
+    /// Returns an error if any pattern is invalid.
+    pub fn new_ignore_case<'a>(patterns: impl IntoIterator<Item = &'a str>) -> Result<PatternSet, PatternError> {
+        let patterns = patterns.into_iter().map(Self::parse_pattern).collect::<Result<_, _>>()?;
+        Self::compile(patterns, true)
     }
 
     fn parse_pattern(pattern: &str) -> Result<Pattern, PatternError> {
         if pattern.is_empty() {
             return Err(PatternError::InvalidPattern);
         }
-        Ok(Pattern {
-            bytes: pattern.as_bytes().to_owned(),
+        Ok(Pattern::parse(pattern))
+    }
+
+    /// Creates a matcher from compiled patterns.
+    pub(crate) fn from_patterns(patterns: Vec<Pattern>) -> Result<PatternSet, PatternError> {
+        Self::compile(patterns, false)
+    }
+
+    fn compile(mut patterns: Vec<Pattern>, ignore_case: bool) -> Result<PatternSet, PatternError> {
+        if ignore_case {
+            patterns = patterns.iter().map(Pattern::to_ascii_lowercase).collect();
+        }
+
+        let mut wildcards = Vec::new();
+        let mut literals: Vec<Vec<u8>> = Vec::new();
+        let mut candidates: Vec<Vec<usize>> = Vec::new();
+        let mut positions: HashMap<Vec<u8>, usize> = HashMap::new();
+
+        for (i, pattern) in patterns.iter().enumerate() {
+            let literal = pattern.longest_literal();
+            if literal.is_empty() {
+                wildcards.push(i);
+                continue;
+            }
+            let pos = *positions.entry(literal).or_insert_with_key(|literal| {
+                literals.push(literal.clone());
+                candidates.push(Vec::new());
+                literals.len() - 1
+            });
+            candidates[pos].push(i);
+        }
+
+        let prefilter = if literals.is_empty() {
+            None
+        } else {
+            let ac = AhoCorasick::builder()
+                .match_kind(MatchKind::Standard)
+                .build(&literals)
+                .map_err(|_| PatternError::TooManyPatterns)?;
+            Some(ac)
+        };
+
+        Ok(PatternSet {
+            patterns,
+            wildcards,
+            prefilter,
+            candidates,
+            ignore_case,
         })
     }
 
     /// Check if the input matches any of the patterns.
     #[must_use]
     pub fn is_match(&self, input: &str) -> bool {
-        for pattern in &self.patterns {
-            if Self::match_pattern(&pattern.bytes, input.as_bytes()) {
-                return true;
-            }
+        let input = if self.ignore_case {
+            Cow::Owned(input.to_ascii_lowercase())
+        } else {
+            Cow::Borrowed(input)
+        };
+        let input = input.as_bytes();
+
+        let is_match = |i: &usize| match_tokens(&self.patterns[*i].tokens, input);
+
+        if self.wildcards.iter().any(is_match) {
+            return true;
         }
-        false
+
+        let Some(prefilter) = &self.prefilter else { return false };
+        prefilter
+            .find_overlapping_iter(input)
+            .any(|m| self.candidates[m.pattern().as_usize()].iter().any(is_match))
     }
+}
 
-    /// <https://leetcode.com/problems/wildcard-matching/>
-    fn match_pattern(pattern: &[u8], input: &[u8]) -> bool {
-        let mut p_idx = 0;
-        let mut s_idx = 0;
+/// <https://leetcode.com/problems/wildcard-matching/>
+fn match_tokens(pattern: &[Token], input: &[u8]) -> bool {
+    let mut p_idx = 0;
+    let mut s_idx = 0;
 
-        let mut p_back = usize::MAX - 1;
-        let mut s_back = usize::MAX - 1;
+    let mut p_back = usize::MAX - 1;
+    let mut s_back = usize::MAX - 1;
 
-        loop {
-            if p_idx < pattern.len() {
-                let p = pattern[p_idx];
-                if p == b'*' {
+    loop {
+        if p_idx < pattern.len() {
+            let p = pattern[p_idx];
+            if p == Token::AnyBytes {
+                p_idx += 1;
+                p_back = p_idx;
+                s_back = s_idx;
+                continue;
+            }
+
+            if s_idx < input.len() {
+                let c = input[s_idx];
+                if p == Token::Byte(c) || p == Token::AnyByte {
                     p_idx += 1;
-                    p_back = p_idx;
-                    s_back = s_idx;
+                    s_idx += 1;
                     continue;
                 }
-
-                if s_idx < input.len() {
-                    let c = input[s_idx];
-                    if p == c || p == b'?' {
-                        p_idx += 1;
-                        s_idx += 1;
-                        continue;
-                    }
-                }
-            } else if s_idx == input.len() {
-                return true;
-            }
-
-            if p_back == pattern.len() {
-                return true;
             }
+        } else if s_idx == input.len() {
+            return true;
+        }
 
-            if s_back + 1 < input.len() {
-                s_back += 1;
-                p_idx = p_back;
-                s_idx = s_back;
-                continue;
-            }
+        if p_back == pattern.len() {
+            return true;
+        }
 
-            return false;
+        if s_back + 1 < input.len() {
+            s_back += 1;
+            p_idx = p_back;
+            s_idx = s_back;
+            continue;
         }
+
+        return false;
     }
 }
 
+/// Checks if the input matches a single pattern.
+pub(crate) fn is_wildcard_match(pattern: &str, input: &str) -> bool {
+    Pattern::parse(pattern).is_match(input)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,8 +270,44 @@ mod tests {
 
         for &(pattern, input, expected) in cases {
             let pattern = PatternSet::parse_pattern(pattern).unwrap();
-            let ans = PatternSet::match_pattern(&pattern.bytes, input.as_bytes());
+            let ans = pattern.is_match(input);
             assert_eq!(ans, expected, "pattern: {pattern:?}, input: {input:?}");
+
+            let set = PatternSet::from_patterns(vec![pattern]).unwrap();
+            assert_eq!(set.is_match(input), expected, "set: {set:?}, input: {input:?}");
         }
     }
+
+    #[test]
+    fn test_literal() {
+        let mut pattern = Pattern::parse("home/");
+        pattern.push_literal("a*?");
+        pattern.push_pattern("/*");
+        assert!(pattern.is_match("home/a*?/key"));
+        assert!(!pattern.is_match("home/abc/key"));
+    }
+
+    #[test]
+    fn test_set() {
+        let patterns: Vec<String> = (0..1000).map(|i| format!("arn:aws:s3:::tenant-{i}/*")).collect();
+        let set = PatternSet::new(patterns.iter().map(String::as_str)).unwrap();
+        assert!(set.is_match("arn:aws:s3:::tenant-42/key"));
+        assert!(set.is_match("arn:aws:s3:::tenant-999/a/b"));
+        assert!(!set.is_match("arn:aws:s3:::tenant-1000/key"));
+        assert!(!set.is_match("arn:aws:s3:::tenant-42"));
+
+        let set = PatternSet::new(["arn:aws:s3:::a/*", "arn:aws:s3:::b/*", "*/public/*", "?"]).unwrap();
+        assert!(set.is_match("arn:aws:s3:::b/key"));
+        assert!(set.is_match("arn:aws:s3:::c/public/key"));
+        assert!(set.is_match("x"));
+        assert!(!set.is_match("arn:aws:s3:::c/key"));
+
+        let set = PatternSet::new(["s3:Get*", "s3:ListBucket"]).unwrap();
+        assert!(!set.is_match("s3:getobject"));
+
+        let set = PatternSet::new_ignore_case(["s3:Get*", "s3:ListBucket"]).unwrap();
+        assert!(set.is_match("s3:getobject"));
+        assert!(set.is_match("S3:LISTBUCKET"));
+        assert!(!set.is_match("s3:PutObject"));
+    }
 }
@@ -0,0 +1,610 @@
+//! Validation of bucket policies
+//!
+//! See <https://docs.aws.amazon.com/AmazonS3/latest/userguide/bucket-policies.html>
+
+use crate::arn::Arn;
+use crate::condition::{Operator, SetQualifier};
+use crate::eval::{EvalError, Evaluator};
+use crate::model::{
+    ActionRule, ConditionRule, Effect, Policy, Principal, PrincipalRule, ResourceRule, Statement, WildcardOneOrMore,
+};
+use crate::pattern::is_wildcard_match;
+
+use s3s::S3Error;
+use s3s::dto::PolicyStatus;
+
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+/// The maximum size of a bucket policy in bytes
+pub const MAX_BUCKET_POLICY_SIZE: usize = 20 * 1024;
+
+/// The IAM actions defined by Amazon S3
+///
+/// See <https://docs.aws.amazon.com/service-authorization/latest/reference/list_amazons3.html>
+pub const S3_ACTIONS: &[&str] = &[
+    "s3:AbortMultipartUpload",
+    "s3:BypassGovernanceRetention",
+    "s3:CreateAccessPoint",
+    "s3:CreateBucket",
+    "s3:CreateJob",
+    "s3:DeleteAccessPoint",
+    "s3:DeleteAccessPointPolicy",
+    "s3:DeleteBucket",
+    "s3:DeleteBucketMetadataTableConfiguration",
+    "s3:DeleteBucketOwnershipControls",
+    "s3:DeleteBucketPolicy",
+    "s3:DeleteBucketWebsite",
+    "s3:DeleteObject",
+    "s3:DeleteObjectTagging",
+    "s3:DeleteObjectVersion",
+    "s3:DeleteObjectVersionTagging",
+    "s3:GetAccelerateConfiguration",
+    "s3:GetAnalyticsConfiguration",
+    "s3:GetBucketAcl",
+    "s3:GetBucketCORS",
+    "s3:GetBucketLocation",
+    "s3:GetBucketLogging",
+    "s3:GetBucketMetadataTableConfiguration",
+    "s3:GetBucketNotification",
+    "s3:GetBucketObjectLockConfiguration",
+    "s3:GetBucketOwnershipControls",
+    "s3:GetBucketPolicy",
+    "s3:GetBucketPolicyStatus",
+    "s3:GetBucketPublicAccessBlock",
+    "s3:GetBucketRequestPayment",
+    "s3:GetBucketTagging",
+    "s3:GetBucketVersioning",
+    "s3:GetBucketWebsite",
+    "s3:GetEncryptionConfiguration",
+    "s3:GetIntelligentTieringConfiguration",
+    "s3:GetInventoryConfiguration",
+    "s3:GetLifecycleConfiguration",
+    "s3:GetMetricsConfiguration",
+    "s3:GetObject",
+    "s3:GetObjectAcl",
+    "s3:GetObjectAttributes",
+    "s3:GetObjectLegalHold",
+    "s3:GetObjectRetention",
+    "s3:GetObjectTagging",
+    "s3:GetObjectTorrent",
+    "s3:GetObjectVersion",
+    "s3:GetObjectVersionAcl",
+    "s3:GetObjectVersionAttributes",
+    "s3:GetObjectVersionForReplication",
+    "s3:GetObjectVersionTagging",
+    "s3:GetObjectVersionTorrent",
+    "s3:GetReplicationConfiguration",
+    "s3:ListAllMyBuckets",
+    "s3:ListBucket",
+    "s3:ListBucketMultipartUploads",
+    "s3:ListBucketVersions",
+    "s3:ListMultipartUploadParts",
+    "s3:ObjectOwnerOverrideToBucketOwner",
+    "s3:PutAccelerateConfiguration",
+    "s3:PutAnalyticsConfiguration",
+    "s3:PutBucketAcl",
+    "s3:PutBucketCORS",
+    "s3:PutBucketLogging",
+    "s3:PutBucketMetadataTableConfiguration",
+    "s3:PutBucketNotification",
+    "s3:PutBucketObjectLockConfiguration",
+    "s3:PutBucketOwnershipControls",
+    "s3:PutBucketPolicy",
+    "s3:PutBucketPublicAccessBlock",
+    "s3:PutBucketRequestPayment",
+    "s3:PutBucketTagging",
+    "s3:PutBucketVersioning",
+    "s3:PutBucketWebsite",
+    "s3:PutEncryptionConfiguration",
+    "s3:PutIntelligentTieringConfiguration",
+    "s3:PutInventoryConfiguration",
+    "s3:PutLifecycleConfiguration",
+    "s3:PutMetricsConfiguration",
+    "s3:PutObject",
+    "s3:PutObjectAcl",
+    "s3:PutObjectLegalHold",
+    "s3:PutObjectRetention",
+    "s3:PutObjectTagging",
+    "s3:PutObjectVersionAcl",
+    "s3:PutObjectVersionTagging",
+    "s3:PutReplicationConfiguration",
+    "s3:ReplicateDelete",
+    "s3:ReplicateObject",
+    "s3:ReplicateTags",
+    "s3:RestoreObject",
+];
+
+/// Condition keys which restrict a statement to fixed callers,
+/// so that a statement granting access to everyone is not public.
+///
+/// See <https://docs.aws.amazon.com/AmazonS3/latest/userguide/access-control-block-public-access.html#access-control-block-public-access-policy-status>
+const RESTRICTING_CONDITION_KEYS: &[&str] = &[
+    "aws:PrincipalAccount",
+    "aws:PrincipalArn",
+    "aws:PrincipalOrgID",
+    "aws:SourceAccount",
+    "aws:SourceArn",
+    "aws:SourceIp",
+    "aws:SourceOwner",
+    "aws:SourceVpc",
+    "aws:SourceVpce",
+    "aws:userid",
+    "s3:DataAccessPointAccount",
+    "s3:DataAccessPointArn",
+];
+
+const POLICY_FIELDS: &[&str] = &["Version", "Id", "Statement"];
+
+const STATEMENT_FIELDS: &[&str] = &[
+    "Sid",
+    "Effect",
+    "Principal",
+    "NotPrincipal",
+    "Action",
+    "NotAction",
+    "Resource",
+    "NotResource",
+    "Condition",
+];
+
+/// An error of bucket policy validation
+///
+/// It is converted to a `MalformedPolicy` error.
+#[derive(Debug, thiserror::Error)]
+pub enum ValidationError {
+    #[error("Policy exceeds the maximum allowed document size of {MAX_BUCKET_POLICY_SIZE} bytes")]
+    TooLarge,
+
+    #[error("Policies must be valid JSON and the first byte must be '{{'")]
+    InvalidJson,
+
+    #[error("The policy must contain a valid version string: {0}")]
+    InvalidVersion(String),
+
+    #[error("Unknown field {0}")]
+    UnknownField(String),
+
+    #[error("Missing required field {0}")]
+    MissingField(&'static str),
+
+    #[error("Statement must not contain both {0} and {1}")]
+    ConflictingFields(&'static str, &'static str),
+
+    #[error("{0} must not be empty")]
+    EmptyField(&'static str),
+
+    #[error("Statement is not well-formed")]
+    MalformedStatement,
+
+    #[error("Could not parse the policy: {0}")]
+    Parse(serde_json::Error),
+
+    #[error("Statement IDs (SID) in a single policy must be unique: {0}")]
+    DuplicateSid(String),
+
+    #[error("Policy has invalid action: {0}")]
+    InvalidAction(String),
+
+    #[error("Policy has invalid resource: {0}")]
+    InvalidResource(String),
+
+    #[error("Policy has invalid resource: {resource} does not belong to bucket {bucket}")]
+    ResourceNotInBucket { resource: String, bucket: String },
+
+    #[error("Invalid principal in policy: {0}")]
+    InvalidPrincipal(String),
+
+    #[error("Invalid policy: {0}")]
+    Eval(#[from] EvalError),
+}
+
+impl From<ValidationError> for S3Error {
+    fn from(err: ValidationError) -> Self {
+        s3s::s3_error!(MalformedPolicy, "{err}")
+    }
+}
+
+/// Parses and validates a bucket policy before it is stored.
+///
+/// # Errors
+/// Returns an error if the policy is malformed or does not apply to the bucket.
+pub fn validate_bucket_policy(bucket: &str, policy: &str) -> Result<Policy, ValidationError> {
+    if policy.len() > MAX_BUCKET_POLICY_SIZE {
+        return Err(ValidationError::TooLarge);
+    }
+
+    let value: Value = serde_json::from_str(policy).map_err(|_| ValidationError::InvalidJson)?;
+    check_document(&value)?;
+
+    let policy: Policy = serde_json::from_value(value).map_err(ValidationError::Parse)?;
+
+    let mut sids = HashSet::new();
+    for statement in policy.statement.as_slice() {
+        if let Some(sid) = &statement.sid {
+            if !sids.insert(sid.as_str()) {
+                return Err(ValidationError::DuplicateSid(sid.clone()));
+            }
+        }
+        check_statement(bucket, statement)?;
+    }
+
+    Evaluator::new(&policy)?;
+
+    Ok(policy)
+}
+
+/// Checks the structure of a policy document, which is hard to report precisely after deserialization.
+fn check_document(value: &Value) -> Result<(), ValidationError> {
+    let Some(document) = value.as_object() else { return Err(ValidationError::InvalidJson) };
+
+    if let Some(field) = document.keys().find(|k| !POLICY_FIELDS.contains(&k.as_str())) {
+        return Err(ValidationError::UnknownField(field.clone()));
+    }
+
+    if let Some(version) = document.get("Version") {
+        if !matches!(version.as_str(), Some("2012-10-17" | "2008-10-17")) {
+            return Err(ValidationError::InvalidVersion(version.to_string()));
+        }
+    }
+
+    let statements = match document.get("Statement") {
+        None => return Err(ValidationError::MissingField("Statement")),
+        Some(Value::Array(statements)) => statements.as_slice(),
+        Some(statement) => std::slice::from_ref(statement),
+    };
+    if statements.is_empty() {
+        return Err(ValidationError::EmptyField("Statement"));
+    }
+
+    for statement in statements {
+        let Some(statement) = statement.as_object() else {
+            return Err(ValidationError::MalformedStatement);
+        };
+
+        if let Some(field) = statement.keys().find(|k| !STATEMENT_FIELDS.contains(&k.as_str())) {
+            return Err(ValidationError::UnknownField(field.clone()));
+        }
+
+        if !statement.contains_key("Effect") {
+            return Err(ValidationError::MissingField("Effect"));
+        }
+        for (field, not_field) in [
+            ("Principal", "NotPrincipal"),
+            ("Action", "NotAction"),
+            ("Resource", "NotResource"),
+        ] {
+            match (statement.contains_key(field), statement.contains_key(not_field)) {
+                (false, false) => return Err(ValidationError::MissingField(field)),
+                (true, true) => return Err(ValidationError::ConflictingFields(field, not_field)),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn check_statement(bucket: &str, statement: &Statement) -> Result<(), ValidationError> {
+    match &statement.principal {
+        Some(PrincipalRule::Principal(principal) | PrincipalRule::NotPrincipal(principal)) => check_principal(principal)?,
+        None => return Err(ValidationError::MissingField("Principal")),
+    }
+
+    let (ActionRule::Action(actions) | ActionRule::NotAction(actions)) = &statement.action;
+    for action in non_empty(actions, "Action")? {
+        check_action(action)?;
+    }
+
+    let (ResourceRule::Resource(resources) | ResourceRule::NotResource(resources)) = &statement.resource;
+    let Some(resources) = resources.as_slice() else {
+        return Err(ValidationError::InvalidResource("*".to_owned()));
+    };
+    if resources.is_empty() {
+        return Err(ValidationError::EmptyField("Resource"));
+    }
+    for resource in resources {
+        check_resource(bucket, resource)?;
+    }
+
+    Ok(())
+}
+
+fn non_empty<'a>(values: &'a WildcardOneOrMore<String>, field: &'static str) -> Result<&'a [String], ValidationError> {
+    match values.as_slice() {
+        None => Ok(&[]),
+        Some([]) => Err(ValidationError::EmptyField(field)),
+        Some(values) => Ok(values),
+    }
+}
+
+fn check_action(action: &str) -> Result<(), ValidationError> {
+    if action == "*" {
+        return Ok(());
+    }
+
+    let invalid = || ValidationError::InvalidAction(action.to_owned());
+
+    let (service, _) = action.split_once(':').ok_or_else(invalid)?;
+    if !service.eq_ignore_ascii_case("s3") {
+        return Err(invalid());
+    }
+
+    let action = action.to_ascii_lowercase();
+    let is_known = S3_ACTIONS
+        .iter()
+        .any(|known| is_wildcard_match(&action, &known.to_ascii_lowercase()));
+    if is_known { Ok(()) } else { Err(invalid()) }
+}
+
+fn check_resource(bucket: &str, resource: &str) -> Result<(), ValidationError> {
+    let invalid = || ValidationError::InvalidResource(resource.to_owned());
+
+    let arn: Arn = resource.parse().map_err(|_| invalid())?;
+    if arn.service != "s3" || !arn.region.is_empty() || !arn.account_id.is_empty() {
+        return Err(invalid());
+    }
+
+    let bucket_pattern = arn.resource.split_once('/').map_or(arn.resource.as_str(), |(b, _)| b);
+    if bucket_pattern.is_empty() {
+        return Err(invalid());
+    }
+    if !is_wildcard_match(bucket_pattern, bucket) {
+        return Err(ValidationError::ResourceNotInBucket {
+            resource: resource.to_owned(),
+            bucket: bucket.to_owned(),
+        });
+    }
+
+    Ok(())
+}
+
+fn check_principal(principal: &Principal) -> Result<(), ValidationError> {
+    let map = match principal {
+        Principal::Wildcard => return Ok(()),
+        Principal::Map(map) if map.is_empty() => return Err(ValidationError::InvalidPrincipal("{}".to_owned())),
+        Principal::Map(map) => map,
+    };
+
+    for (kind, values) in map {
+        let is_valid: fn(&str) -> bool = match kind.as_str() {
+            "AWS" => is_valid_aws_principal,
+            "CanonicalUser" => |s| s.len() == 64 && s.bytes().all(|b| b.is_ascii_hexdigit()),
+            "Service" => |s| s.contains('.') && !s.contains(char::is_whitespace),
+            "Federated" => |s| !s.is_empty() && !s.contains(char::is_whitespace),
+            _ => return Err(ValidationError::InvalidPrincipal(kind.clone())),
+        };
+        let values = values.as_slice();
+        if values.is_empty() {
+            return Err(ValidationError::InvalidPrincipal(format!("{kind}: []")));
+        }
+        if let Some(value) = values.iter().find(|v| !is_valid(v)) {
+            return Err(ValidationError::InvalidPrincipal(format!("{kind}: {value}")));
+        }
+    }
+
+    Ok(())
+}
+
+fn is_account_id(s: &str) -> bool {
+    s.len() == 12 && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn is_valid_aws_principal(s: &str) -> bool {
+    if s == "*" || is_account_id(s) {
+        return true;
+    }
+    s.parse::<Arn>()
+        .is_ok_and(|arn| matches!(arn.service.as_str(), "iam" | "sts") && arn.region.is_empty() && is_account_id(&arn.account_id))
+}
+
+/// Returns whether a bucket policy grants access to everyone.
+///
+/// A statement is public if it allows a wildcard principal
+/// and is not restricted to fixed values of condition keys like `aws:SourceIp` or `aws:SourceArn`.
+#[must_use]
+pub fn is_public(policy: &Policy) -> bool {
+    policy.statement.as_slice().iter().any(|statement| {
+        statement.effect == Effect::Allow
+            && grants_everyone(statement.principal.as_ref())
+            && !statement.condition.as_ref().is_some_and(is_restricting)
+    })
+}
+
+/// Returns the status of a bucket policy for `GetBucketPolicyStatus`.
+#[must_use]
+pub fn policy_status(policy: &Policy) -> PolicyStatus {
+    PolicyStatus {
+        is_public: Some(is_public(policy)),
+    }
+}
+
+fn grants_everyone(rule: Option<&PrincipalRule>) -> bool {
+    match rule {
+        Some(PrincipalRule::Principal(Principal::Wildcard) | PrincipalRule::NotPrincipal(_)) => true,
+        Some(PrincipalRule::Principal(Principal::Map(map))) => map
+            .get("AWS")
+            .is_some_and(|values| values.as_slice().iter().any(|v| v == "*")),
+        None => false,
+    }
+}
+
+fn is_restricting(condition: &ConditionRule) -> bool {
+    condition.0.iter().any(|(op, key_values)| {
+        let is_fixed = !op.if_exists
+            && op.qualifier != Some(SetQualifier::ForAllValues)
+            && !op.operator.is_negated()
+            && op.operator != Operator::Null;
+
+        is_fixed
+            && key_values.0.iter().any(|(key, values)| {
+                RESTRICTING_CONDITION_KEYS.iter().any(|k| k.eq_ignore_ascii_case(key))
+                    && values
+                        .as_slice()
+                        .iter()
+                        .all(|v| !v.contains(['*', '?']) && v != "0.0.0.0/0" && v != "::/0")
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(policy: &str) -> String {
+        validate_bucket_policy("bucket", policy).unwrap_err().to_string()
+    }
+
+    fn statement(statement: &str) -> String {
+        format!(r#"{{"Version": "2012-10-17", "Statement": [{statement}]}}"#)
+    }
+
+    #[test]
+    fn valid() {
+        let policy = statement(
+            r#"{
+                "Sid": "PublicRead",
+                "Effect": "Allow",
+                "Principal": {"AWS": ["*", "123456789012", "arn:aws:iam::123456789012:user/alice"]},
+                "Action": ["s3:GetObject", "s3:list*"],
+                "Resource": ["arn:aws:s3:::bucket", "arn:aws:s3:::bucket/*", "arn:aws:s3:::buck*/logs/*"],
+                "Condition": {"IpAddress": {"aws:SourceIp": "192.0.2.0/24"}}
+            }"#,
+        );
+        let policy = validate_bucket_policy("bucket", &policy).unwrap();
+        assert!(!is_public(&policy));
+    }
+
+    #[test]
+    fn malformed() {
+        let cases = [
+            ("[]", "Policies must be valid JSON and the first byte must be '{'"),
+            ("{", "Policies must be valid JSON and the first byte must be '{'"),
+            (
+                r#"{"Version": "2010-01-01", "Statement": []}"#,
+                r#"The policy must contain a valid version string: "2010-01-01""#,
+            ),
+            (r#"{"Version": "2012-10-17"}"#, "Missing required field Statement"),
+            (r#"{"Statement": [], "Foo": 1}"#, "Unknown field Foo"),
+            (r#"{"Statement": []}"#, "Statement must not be empty"),
+            (r#"{"Statement": [1]}"#, "Statement is not well-formed"),
+            (&statement("{}"), "Missing required field Effect"),
+            (
+                &statement(r#"{"Effect": "Allow", "Action": "s3:GetObject", "Resource": "arn:aws:s3:::bucket/*"}"#),
+                "Missing required field Principal",
+            ),
+            (
+                &statement(r#"{"Effect": "Allow", "Principal": "*", "Resource": "arn:aws:s3:::bucket/*"}"#),
+                "Missing required field Action",
+            ),
+            (
+                &statement(
+                    r#"{"Effect": "Allow", "Principal": "*", "Action": [], "NotAction": [], "Resource": "arn:aws:s3:::bucket/*"}"#,
+                ),
+                "Statement must not contain both Action and NotAction",
+            ),
+            (
+                &statement(r#"{"Effect": "Allow", "Principal": "*", "Action": [], "Resource": "arn:aws:s3:::bucket/*"}"#),
+                "Action must not be empty",
+            ),
+            (
+                &statement(
+                    r#"{"Effect": "Allow", "Principal": "*", "Action": "s3:GetObjekt", "Resource": "arn:aws:s3:::bucket/*"}"#,
+                ),
+                "Policy has invalid action: s3:GetObjekt",
+            ),
+            (
+                &statement(r#"{"Effect": "Allow", "Principal": "*", "Action": "iam:*", "Resource": "arn:aws:s3:::bucket/*"}"#),
+                "Policy has invalid action: iam:*",
+            ),
+            (
+                &statement(
+                    r#"{"Effect": "Allow", "Principal": "*", "Action": "s3:GetObject", "Resource": "arn:aws:s3:bucket/*"}"#,
+                ),
+                "Policy has invalid resource: arn:aws:s3:bucket/*",
+            ),
+            (
+                &statement(r#"{"Effect": "Allow", "Principal": "*", "Action": "s3:GetObject", "Resource": "*"}"#),
+                "Policy has invalid resource: *",
+            ),
+            (
+                &statement(
+                    r#"{"Effect": "Allow", "Principal": "*", "Action": "s3:GetObject", "Resource": "arn:aws:s3:::other/*"}"#,
+                ),
+                "Policy has invalid resource: arn:aws:s3:::other/* does not belong to bucket bucket",
+            ),
+            (
+                &statement(
+                    r#"{"Effect": "Allow", "Principal": {"AWS": "alice"}, "Action": "s3:GetObject", "Resource": "arn:aws:s3:::bucket/*"}"#,
+                ),
+                "Invalid principal in policy: AWS: alice",
+            ),
+            (
+                &statement(
+                    r#"{"Effect": "Allow", "Principal": {"User": "alice"}, "Action": "s3:GetObject", "Resource": "arn:aws:s3:::bucket/*"}"#,
+                ),
+                "Invalid principal in policy: User",
+            ),
+            (
+                &statement(
+                    r#"{"Sid": "A", "Effect": "Allow", "Principal": "*", "Action": "s3:GetObject", "Resource": "arn:aws:s3:::bucket/*"},
+                       {"Sid": "A", "Effect": "Deny", "Principal": "*", "Action": "s3:GetObject", "Resource": "arn:aws:s3:::bucket/*"}"#,
+                ),
+                "Statement IDs (SID) in a single policy must be unique: A",
+            ),
+            (
+                &statement(
+                    r#"{"Effect": "Allow", "Principal": "*", "Action": "s3:GetObject", "Resource": "arn:aws:s3:::bucket/*",
+                        "Condition": {"IpAddress": {"aws:SourceIp": "not-an-ip"}}}"#,
+                ),
+                "Invalid policy: Invalid value for condition operator IpAddress: not-an-ip",
+            ),
+        ];
+
+        for (policy, expected) in cases {
+            assert_eq!(error(policy), expected, "{policy}");
+        }
+
+        let err = S3Error::from(validate_bucket_policy("bucket", "[]").unwrap_err());
+        assert_eq!(*err.code(), s3s::S3ErrorCode::MalformedPolicy);
+    }
+
+    #[test]
+    fn public() {
+        let cases = [
+            (r#""Principal": "*""#, true),
+            (r#""Principal": {"AWS": "*"}"#, true),
+            (r#""NotPrincipal": {"AWS": "123456789012"}"#, true),
+            (r#""Principal": {"AWS": "123456789012"}"#, false),
+            (
+                r#""Principal": "*", "Condition": {"StringEquals": {"aws:SourceAccount": "123456789012"}}"#,
+                false,
+            ),
+            (r#""Principal": "*", "Condition": {"StringLike": {"aws:SourceArn": "arn:aws:*"}}"#, true),
+            (r#""Principal": "*", "Condition": {"IpAddress": {"aws:SourceIp": "0.0.0.0/0"}}"#, true),
+            (
+                r#""Principal": "*", "Condition": {"NotIpAddress": {"aws:SourceIp": "192.0.2.0/24"}}"#,
+                true,
+            ),
+            (
+                r#""Principal": "*", "Condition": {"StringEqualsIfExists": {"aws:SourceVpc": "vpc-1"}}"#,
+                true,
+            ),
+            (r#""Principal": "*", "Condition": {"StringEquals": {"s3:prefix": "public/"}}"#, true),
+        ];
+
+        for (principal, expected) in cases {
+            let policy = statement(&format!(
+                r#"{{"Effect": "Allow", {principal}, "Action": "s3:GetObject", "Resource": "arn:aws:s3:::bucket/*"}}"#
+            ));
+            let policy = validate_bucket_policy("bucket", &policy).unwrap();
+            assert_eq!(is_public(&policy), expected, "{principal}");
+            assert_eq!(policy_status(&policy).is_public, Some(expected));
+        }
+
+        let policy =
+            statement(r#"{"Effect": "Deny", "Principal": "*", "Action": "s3:GetObject", "Resource": "arn:aws:s3:::bucket/*"}"#);
+        assert!(!is_public(&validate_bucket_policy("bucket", &policy).unwrap()));
+    }
+}
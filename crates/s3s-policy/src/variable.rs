@@ -0,0 +1,184 @@
+//! Policy variables
+//!
+//! See <https://docs.aws.amazon.com/IAM/latest/UserGuide/reference_policies_variables.html>
+
+use crate::eval::RequestContext;
+use crate::pattern::Pattern;
+
+/// A string with policy variables like `arn:aws:s3:::bucket/home/${aws:username}/*`
+///
+/// + `${key}` is replaced by the value of the condition key `key`.
+/// + `${key, 'default'}` is replaced by `default` if the key is missing.
+/// + `${*}`, `${?}` and `${$}` are replaced by literal `*`, `?` and `$`.
+#[derive(Debug, Clone)]
+pub(crate) struct Template {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone)]
+enum Part {
+    Text(String),
+    Literal(&'static str),
+    Variable { key: String, default: Option<String> },
+}
+
+impl Template {
+    /// Parses a string which contains variables.
+    ///
+    /// Returns `None` if the string contains no variables or escapes.
+    pub(crate) fn parse(s: &str) -> Option<Self> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut rest = s;
+
+        while let Some(pos) = rest.find("${") {
+            text.push_str(&rest[..pos]);
+            rest = &rest[pos..];
+
+            let Some((part, len)) = parse_part(rest) else {
+                text.push_str("${");
+                rest = &rest[2..];
+                continue;
+            };
+
+            if !text.is_empty() {
+                parts.push(Part::Text(std::mem::take(&mut text)));
+            }
+            parts.push(part);
+            rest = &rest[len..];
+        }
+
+        if parts.is_empty() {
+            return None;
+        }
+
+        text.push_str(rest);
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+        Some(Self { parts })
+    }
+
+    /// Creates a template without variables.
+    pub(crate) fn text(s: &str) -> Self {
+        Self {
+            parts: vec![Part::Text(s.to_owned())],
+        }
+    }
+
+    /// Substitutes variables with the values of the request.
+    ///
+    /// Returns `None` if a variable has neither a single value nor a default value.
+    pub(crate) fn resolve_text(&self, cx: &RequestContext) -> Option<String> {
+        let mut ans = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Text(s) => ans.push_str(s),
+                Part::Literal(s) => ans.push_str(s),
+                Part::Variable { key, default } => ans.push_str(resolve_variable(cx, key, default.as_deref())?),
+            }
+        }
+        Some(ans)
+    }
+
+    /// Substitutes variables with the values of the request, which are matched literally.
+    pub(crate) fn resolve_pattern(&self, cx: &RequestContext) -> Option<Pattern> {
+        let mut ans = Pattern::default();
+        for part in &self.parts {
+            match part {
+                Part::Text(s) => ans.push_pattern(s),
+                Part::Literal(s) => ans.push_literal(s),
+                Part::Variable { key, default } => ans.push_literal(resolve_variable(cx, key, default.as_deref())?),
+            }
+        }
+        Some(ans)
+    }
+}
+
+fn resolve_variable<'a>(cx: &'a RequestContext, key: &str, default: Option<&'a str>) -> Option<&'a str> {
+    match cx.condition_values(key) {
+        Some([value]) => Some(value),
+        _ => default,
+    }
+}
+
+/// Parses a variable at the start of the input, returning the part and its length.
+fn parse_part(input: &str) -> Option<(Part, usize)> {
+    let end = input.find('}')?;
+    let inner = input[2..end].trim();
+    let len = end + 1;
+
+    let literal = match inner {
+        "*" => Some("*"),
+        "?" => Some("?"),
+        "$" => Some("$"),
+        _ => None,
+    };
+    if let Some(literal) = literal {
+        return Some((Part::Literal(literal), len));
+    }
+
+    let (key, default) = match inner.split_once(',') {
+        Some((key, default)) => {
+            let default = default.trim().strip_prefix('\'')?.strip_suffix('\'')?;
+            (key.trim(), Some(default.to_owned()))
+        }
+        None => (inner, None),
+    };
+
+    let is_valid_key = !key.is_empty()
+        && key
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b':' | b'-' | b'_' | b'/' | b'.'));
+    if !is_valid_key {
+        return None;
+    }
+
+    let key = key.to_ascii_lowercase();
+    Some((Part::Variable { key, default }, len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve() {
+        let mut cx = RequestContext::new("s3:ListBucket", "arn:aws:s3:::bucket");
+        cx.set_condition_value("aws:username", "alice");
+        cx.set_condition_values("aws:SourceIp", vec![]);
+
+        let cases = [
+            ("home/${aws:username}/*", Some("home/alice/*")),
+            ("home/${ AWS:UserName }", Some("home/alice")),
+            ("home/${aws:userid}", None),
+            ("home/${aws:userid, 'nobody'}", Some("home/nobody")),
+            ("home/${aws:SourceIp,''}", Some("home/")),
+            ("${*}${?}${$}", Some("*?$")),
+            ("${$}{aws:username}", Some("${aws:username}")),
+        ];
+        for (s, expected) in cases {
+            let template = Template::parse(s).unwrap();
+            assert_eq!(template.resolve_text(&cx).as_deref(), expected, "{s}");
+        }
+
+        assert!(Template::parse("home/*").is_none());
+        assert!(Template::parse("${not a variable}").is_none());
+        assert!(Template::parse("${}${aws:username").is_none());
+
+        let pattern = Template::parse("home/${aws:username}/${*}/*")
+            .unwrap()
+            .resolve_pattern(&cx)
+            .unwrap();
+        assert!(pattern.is_match("home/alice/*/key"));
+        assert!(!pattern.is_match("home/alice/a/key"));
+
+        cx.set_condition_value("aws:username", "*");
+        let pattern = Template::parse("home/${aws:username}/*")
+            .unwrap()
+            .resolve_pattern(&cx)
+            .unwrap();
+        assert!(pattern.is_match("home/*/key"));
+        assert!(!pattern.is_match("home/bob/key"));
+    }
+}
@@ -1,4 +1,5 @@
 use s3s::auth::SimpleAuth;
+use s3s::connection::ConnectionInfo;
 use s3s::host::SingleDomain;
 use s3s::service::S3ServiceBuilder;
 use tokio::net::TcpListener;
@@ -82,7 +83,7 @@ async fn main() -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
     info!("server is forwarding requests to {}", opt.endpoint_url);
 
     loop {
-        let (socket, _) = tokio::select! {
+        let (socket, peer_addr) = tokio::select! {
             res =  listener.accept() => {
                 match res {
                     Ok(conn) => conn,
@@ -97,7 +98,7 @@ async fn main() -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
             }
         };
 
-        let conn = http_server.serve_connection(TokioIo::new(socket), service.clone());
+        let conn = http_server.serve_connection(TokioIo::new(socket), service.with_connection(ConnectionInfo::new(peer_addr)));
         let conn = graceful.watch(conn.into_owned());
         tokio::spawn(async move {
             let _ = conn.await;
@@ -77,6 +77,9 @@ impl S3Route for CustomRoute {
 mod handlers {
     use std::collections::HashMap;
 
+    use s3s::connection::ConnectionInfo;
+
+    use axum::Extension;
     use axum::Json;
     use axum::Router;
     use axum::body::Body;
@@ -109,6 +112,15 @@ mod handlers {
         response::Json(json)
     }
 
+    /// The connection info is passed through the request extensions,
+    /// when the service is created by `S3Service::with_connection`.
+    pub async fn show_peer(info: Option<Extension<ConnectionInfo>>) -> String {
+        match info.and_then(|Extension(info)| info.source_ip()) {
+            Some(ip) => ip.to_string(),
+            None => "unknown".to_owned(),
+        }
+    }
+
     pub const PREFIX: &str = "/custom";
 
     pub fn register() -> Router {
@@ -117,7 +129,8 @@ mod handlers {
             .route("/hello", get(hello))
             .route("/show_path/{*path}", get(show_path))
             .route("/show_query", get(show_query))
-            .route("/show_json", post(show_json));
+            .route("/show_json", post(show_json))
+            .route("/show_peer", get(show_peer));
 
         Router::new().nest(PREFIX, router)
     }
@@ -1,5 +1,6 @@
 use crate::S3Operation;
-use crate::auth::Credentials;
+use crate::auth::{Credentials, Identity};
+use crate::connection::ConnectionInfo;
 use crate::path::S3Path;
 
 use hyper::HeaderMap;
@@ -9,6 +10,7 @@ use hyper::http::Extensions;
 
 pub struct S3AccessContext<'a> {
     pub(crate) credentials: Option<&'a Credentials>,
+    pub(crate) identity: Option<&'a Identity>,
     pub(crate) s3_path: &'a S3Path,
     pub(crate) s3_op: &'a S3Operation,
 
@@ -28,6 +30,14 @@ impl S3AccessContext<'_> {
         self.credentials
     }
 
+    /// Returns the identity of current request, which is returned by the auth provider.
+    ///
+    /// `None` means anonymous request or no identity.
+    #[must_use]
+    pub fn identity(&self) -> Option<&Identity> {
+        self.identity
+    }
+
     /// Returns the S3 path of current request.
     ///
     /// An S3 path can be root, bucket, or object.
@@ -57,6 +67,15 @@ impl S3AccessContext<'_> {
         self.headers
     }
 
+    /// Returns the connection of current request.
+    ///
+    /// It provides the inputs of `aws:SourceIp` and `aws:SecureTransport`.
+    /// See [`crate::connection`] for details.
+    #[must_use]
+    pub fn connection_info(&self) -> Option<&ConnectionInfo> {
+        self.extensions.get()
+    }
+
     /// Returns the extensions of current request.
     ///
     /// It is used to pass custom data between middlewares.
@@ -0,0 +1,531 @@
+//! Server access logs
+//!
+//! Access log records are emitted to [`AccessLogSink`] after the response body finishes streaming.
+//! If the error response cannot be built, the record is emitted with `500 Internal Server Error`.
+//! The sink is registered by [`S3ServiceBuilder::set_access_log`](crate::service::S3ServiceBuilder::set_access_log).
+//!
+//! [`AccessLogRecord`] is formatted in the AWS S3 server access log format by [`Display`](fmt::Display).
+//!
+//! See <https://docs.aws.amazon.com/AmazonS3/latest/userguide/LogFormat.html>
+
+use crate::S3ErrorCode;
+use crate::connection::ConnectionInfo;
+use crate::header;
+use crate::http::{OrderedQs, Request, Response};
+use crate::path::S3Path;
+use crate::request_id::RequestId;
+
+use std::fmt::{self, Write as _};
+use std::net::IpAddr;
+use std::time::Duration;
+
+use hyper::HeaderMap;
+use hyper::Method;
+use hyper::StatusCode;
+use hyper::header::{AsHeaderName, REFERER, USER_AGENT};
+use time::OffsetDateTime;
+use time::format_description::FormatItem;
+use time::macros::format_description;
+
+pub trait AccessLogSink: Send + Sync + 'static {
+    /// Emits an access log record.
+    fn log(&self, record: AccessLogRecord);
+}
+
+/// A record of the server access log
+///
+/// `None` fields are formatted as `-`.
+#[derive(Debug, Clone)]
+pub struct AccessLogRecord {
+    pub bucket_owner: Option<String>,
+    pub bucket: Option<String>,
+    /// The time at which the request was received
+    pub time: OffsetDateTime,
+    pub remote_ip: Option<IpAddr>,
+    /// The principal or the access key of the requester, `None` for anonymous requests
+    ///
+    /// It is quoted because a principal may contain spaces.
+    pub requester: Option<String>,
+    pub request_id: String,
+    /// The operation, such as `REST.GET.OBJECT`
+    pub operation: String,
+    pub key: Option<String>,
+    /// The request line, such as `GET /bucket/key HTTP/1.1`
+    pub request_uri: String,
+    pub status: StatusCode,
+    pub error_code: Option<S3ErrorCode>,
+    pub bytes_sent: u64,
+    pub object_size: Option<u64>,
+    /// The time from receiving the request to sending the last byte of the response
+    pub total_time: Duration,
+    /// The time from receiving the request to the response being ready
+    pub turn_around_time: Duration,
+    pub referer: Option<String>,
+    pub user_agent: Option<String>,
+    pub version_id: Option<String>,
+    pub host_id: String,
+    pub signature_version: Option<SignatureVersion>,
+    pub cipher_suite: Option<String>,
+    pub auth_type: Option<AuthType>,
+    pub host_header: Option<String>,
+    pub tls_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureVersion {
+    V2,
+    V4,
+}
+
+/// The authentication type of a request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthType {
+    /// `Authorization` header
+    AuthHeader,
+    /// Presigned URL
+    QueryString,
+}
+
+impl fmt::Display for SignatureVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::V2 => "SigV2",
+            Self::V4 => "SigV4",
+        })
+    }
+}
+
+impl fmt::Display for AuthType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::AuthHeader => "AuthHeader",
+            Self::QueryString => "QueryString",
+        })
+    }
+}
+
+/// Subresources which name the operation, such as `REST.GET.ACL`
+const SUBRESOURCES: &[&str] = &[
+    "accelerate",
+    "acl",
+    "analytics",
+    "attributes",
+    "cors",
+    "encryption",
+    "intelligent-tiering",
+    "inventory",
+    "legal-hold",
+    "lifecycle",
+    "location",
+    "logging",
+    "metrics",
+    "notification",
+    "object-lock",
+    "ownershipControls",
+    "policy",
+    "policyStatus",
+    "publicAccessBlock",
+    "replication",
+    "requestPayment",
+    "restore",
+    "retention",
+    "select",
+    "tagging",
+    "torrent",
+    "versioning",
+    "website",
+];
+
+/// Returns the operation of the access log, such as `REST.GET.OBJECT`.
+fn operation(method: &Method, op: Option<&str>, qs: Option<&OrderedQs>, is_object: bool, is_root: bool) -> String {
+    let verb = match op {
+        Some("CopyObject" | "UploadPartCopy") => "COPY",
+        _ => method.as_str(),
+    };
+    let resource = match op {
+        Some("ListBuckets") => "SERVICE",
+        Some("UploadPart" | "UploadPartCopy") => "PART",
+        Some("CreateMultipartUpload" | "ListMultipartUploads") => "UPLOADS",
+        Some("CompleteMultipartUpload" | "AbortMultipartUpload" | "ListParts") => "UPLOAD",
+        Some("DeleteObjects") => "MULTI_OBJECT_DELETE",
+        Some("ListObjectVersions") => "BUCKETVERSIONS",
+        None if *method == Method::OPTIONS => "PREFLIGHT",
+        _ => match qs.and_then(|qs| SUBRESOURCES.iter().find(|s| qs.has(s))) {
+            Some(subresource) => {
+                let resource = subresource.to_ascii_uppercase().replace('-', "_");
+                return format!("REST.{verb}.{resource}");
+            }
+            None if is_object => "OBJECT",
+            None if is_root => "SERVICE",
+            None => "BUCKET",
+        },
+    };
+    format!("REST.{verb}.{resource}")
+}
+
+fn header_str(headers: &HeaderMap, name: impl AsHeaderName) -> Option<&str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+fn header_u64(headers: &HeaderMap, name: impl AsHeaderName) -> Option<u64> {
+    header_str(headers, name).and_then(|v| v.parse().ok())
+}
+
+impl AccessLogRecord {
+    /// Starts a record when the request is received.
+    pub(crate) fn start(req: &Request, request_id: &RequestId) -> Self {
+        let path_and_query = req.uri.path_and_query().map_or("/", |p| p.as_str());
+
+        let object_size = if matches!(req.method, Method::PUT | Method::POST) {
+            header_u64(&req.headers, header::X_AMZ_DECODED_CONTENT_LENGTH)
+                .or_else(|| header_u64(&req.headers, header::CONTENT_LENGTH))
+        } else {
+            None
+        };
+
+        let conn = req.extensions.get::<ConnectionInfo>();
+        let tls = conn.and_then(|c| c.tls.as_ref());
+
+        Self {
+            bucket_owner: None,
+            bucket: None,
+            time: OffsetDateTime::now_utc(),
+            remote_ip: conn.and_then(ConnectionInfo::source_ip),
+            requester: None,
+            request_id: request_id.request_id.clone(),
+            operation: String::new(),
+            key: None,
+            request_uri: format!("{} {path_and_query} {:?}", req.method, req.version),
+            status: StatusCode::OK,
+            error_code: None,
+            bytes_sent: 0,
+            object_size,
+            total_time: Duration::ZERO,
+            turn_around_time: Duration::ZERO,
+            referer: header_str(&req.headers, REFERER).map(str::to_owned),
+            user_agent: header_str(&req.headers, USER_AGENT).map(str::to_owned),
+            version_id: None,
+            host_id: request_id.host_id.clone(),
+            signature_version: None,
+            cipher_suite: tls.and_then(|t| t.cipher_suite.clone()),
+            auth_type: None,
+            host_header: header_str(&req.headers, header::HOST).map(str::to_owned),
+            tls_version: tls.and_then(|t| t.version.clone()),
+        }
+    }
+
+    /// Sets the bucket and the key of the request.
+    pub(crate) fn set_path(&mut self, s3_path: &S3Path) {
+        self.bucket = s3_path.get_bucket_name().map(str::to_owned);
+        self.key = s3_path
+            .get_object_key()
+            .map(|key| urlencoding::encode(key).replace("%2F", "/"));
+    }
+
+    /// Fills the fields of the response when it is ready.
+    pub(crate) fn finish(&mut self, req: &Request, resp: &Response, turn_around_time: Duration) {
+        self.operation = operation(
+            &req.method,
+            req.s3ext.op,
+            req.s3ext.qs.as_ref(),
+            self.key.is_some(),
+            self.bucket.is_none(),
+        );
+        self.status = resp.status;
+        self.error_code.clone_from(&req.s3ext.error_code);
+        self.turn_around_time = turn_around_time;
+
+        if matches!(req.method, Method::GET | Method::HEAD) && self.key.is_some() {
+            let total = header_str(&resp.headers, header::CONTENT_RANGE).and_then(|v| v.rsplit_once('/')?.1.parse().ok());
+            self.object_size = total.or_else(|| header_u64(&resp.headers, header::CONTENT_LENGTH));
+        }
+        let version_id = header_str(&resp.headers, header::X_AMZ_VERSION_ID);
+        let version_id = version_id.or_else(|| req.s3ext.qs.as_ref()?.get_unique("versionId"));
+        self.version_id = version_id.map(str::to_owned);
+    }
+}
+
+static TIME_FORMAT: &[FormatItem<'static>] = format_description!(
+    "[[[day]/[month repr:short]/[year]:[hour]:[minute]:[second] [offset_hour sign:mandatory][offset_minute]]"
+);
+
+/// Formats `None` as `-`.
+struct Field<T>(Option<T>);
+
+impl<T: fmt::Display> fmt::Display for Field<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(val) => val.fmt(f),
+            None => f.write_str("-"),
+        }
+    }
+}
+
+/// Formats a quoted field, escaping `"` and `\` so that the field cannot be broken out of.
+struct Quoted<'a>(Option<&'a str>);
+
+impl fmt::Display for Quoted<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(val) = self.0 else { return f.write_str("\"-\"") };
+        f.write_char('"')?;
+        for c in val.chars() {
+            if matches!(c, '"' | '\\') {
+                f.write_char('\\')?;
+            }
+            f.write_char(c)?;
+        }
+        f.write_char('"')
+    }
+}
+
+impl fmt::Display for AccessLogRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = self.time.format(TIME_FORMAT).map_err(|_| fmt::Error)?;
+        let bytes_sent = (self.bytes_sent > 0).then_some(self.bytes_sent);
+
+        write!(f, "{} ", Field(self.bucket_owner.as_deref()))?;
+        write!(f, "{} ", Field(self.bucket.as_deref()))?;
+        write!(f, "{time} ")?;
+        write!(f, "{} ", Field(self.remote_ip))?;
+        write!(f, "{} ", Field(self.requester.as_deref().map(Some).map(Quoted)))?;
+        write!(f, "{} ", self.request_id)?;
+        write!(f, "{} ", self.operation)?;
+        write!(f, "{} ", Field(self.key.as_deref()))?;
+        write!(f, "{} ", Quoted(Some(&self.request_uri)))?;
+        write!(f, "{} ", self.status.as_u16())?;
+        write!(f, "{} ", Field(self.error_code.as_ref().map(S3ErrorCode::as_str)))?;
+        write!(f, "{} ", Field(bytes_sent))?;
+        write!(f, "{} ", Field(self.object_size))?;
+        write!(f, "{} ", self.total_time.as_millis())?;
+        write!(f, "{} ", self.turn_around_time.as_millis())?;
+        write!(f, "{} ", Quoted(self.referer.as_deref()))?;
+        write!(f, "{} ", Quoted(self.user_agent.as_deref()))?;
+        write!(f, "{} ", Field(self.version_id.as_deref()))?;
+        write!(f, "{} ", self.host_id)?;
+        write!(f, "{} ", Field(self.signature_version))?;
+        write!(f, "{} ", Field(self.cipher_suite.as_deref()))?;
+        write!(f, "{} ", Field(self.auth_type))?;
+        write!(f, "{} ", Field(self.host_header.as_deref().map(Some).map(Quoted)))?;
+        write!(f, "{} ", Field(self.tls_version.as_deref()))?;
+        // access point ARN and aclRequired
+        write!(f, "- -")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::auth::SimpleAuth;
+    use crate::connection::TlsInfo;
+    use crate::dto::{GetObjectInput, GetObjectOutput, StreamingBlob};
+    use crate::http::Body;
+    use crate::service::S3ServiceBuilder;
+    use crate::signer::{SigV4Signer, SignPayload};
+    use crate::tests::{ACCESS_KEY, FixedGenerator, SECRET_KEY, credentials};
+    use crate::{S3, S3Request, S3Response, S3Result};
+
+    use std::sync::{Arc, Mutex};
+    use std::time::SystemTime;
+
+    use bytes::Bytes;
+
+    struct ObjectS3;
+
+    #[async_trait::async_trait]
+    impl S3 for ObjectS3 {
+        async fn get_object(&self, _req: S3Request<GetObjectInput>) -> S3Result<S3Response<GetObjectOutput>> {
+            let output = GetObjectOutput {
+                body: Some(StreamingBlob::from(Body::from(Bytes::from_static(b"hello")))),
+                content_length: Some(5),
+                version_id: Some("v1".to_owned()),
+                ..Default::default()
+            };
+            Ok(S3Response::new(output))
+        }
+    }
+
+    type Records = Arc<Mutex<Vec<AccessLogRecord>>>;
+
+    impl AccessLogSink for Records {
+        fn log(&self, record: AccessLogRecord) {
+            self.lock().unwrap().push(record);
+        }
+    }
+
+    #[test]
+    fn operations() {
+        let qs = |s: &str| OrderedQs::parse(s).unwrap();
+        let cases = [
+            (Method::GET, Some("GetObject"), None, true, false, "REST.GET.OBJECT"),
+            (Method::GET, Some("ListBuckets"), None, false, true, "REST.GET.SERVICE"),
+            (
+                Method::GET,
+                Some("ListObjectsV2"),
+                Some(qs("list-type=2&prefix=")),
+                false,
+                false,
+                "REST.GET.BUCKET",
+            ),
+            (Method::PUT, Some("PutBucketAcl"), Some(qs("acl")), false, false, "REST.PUT.ACL"),
+            (
+                Method::GET,
+                Some("GetObjectLegalHold"),
+                Some(qs("legal-hold")),
+                true,
+                false,
+                "REST.GET.LEGAL_HOLD",
+            ),
+            (
+                Method::PUT,
+                Some("UploadPartCopy"),
+                Some(qs("partNumber=1&uploadId=x")),
+                true,
+                false,
+                "REST.COPY.PART",
+            ),
+            (Method::OPTIONS, None, None, true, false, "REST.OPTIONS.PREFLIGHT"),
+            (
+                Method::POST,
+                Some("CreateMultipartUpload"),
+                Some(qs("uploads")),
+                true,
+                false,
+                "REST.POST.UPLOADS",
+            ),
+            (
+                Method::POST,
+                Some("DeleteObjects"),
+                Some(qs("delete")),
+                false,
+                false,
+                "REST.POST.MULTI_OBJECT_DELETE",
+            ),
+            (Method::GET, None, None, true, false, "REST.GET.OBJECT"),
+        ];
+        for (method, op, qs, is_object, is_root, expected) in cases {
+            assert_eq!(operation(&method, op, qs.as_ref(), is_object, is_root), expected);
+        }
+    }
+
+    #[test]
+    fn format() {
+        let record = AccessLogRecord {
+            bucket_owner: Some("owner".to_owned()),
+            bucket: Some("bucket".to_owned()),
+            time: time::macros::datetime!(2019-02-06 00:00:38 UTC),
+            remote_ip: Some(IpAddr::from([192, 0, 2, 3])),
+            requester: None,
+            request_id: "3E57427F3EXAMPLE".to_owned(),
+            operation: "REST.GET.OBJECT".to_owned(),
+            key: Some("photos/puppy.jpg".to_owned()),
+            request_uri: "GET /bucket/photos/puppy.jpg HTTP/1.1".to_owned(),
+            status: StatusCode::OK,
+            error_code: None,
+            bytes_sent: 2662,
+            object_size: Some(2662),
+            total_time: Duration::from_millis(70),
+            turn_around_time: Duration::from_millis(10),
+            referer: None,
+            user_agent: Some("curl/8.0".to_owned()),
+            version_id: None,
+            host_id: "HOST".to_owned(),
+            signature_version: Some(SignatureVersion::V4),
+            cipher_suite: None,
+            auth_type: Some(AuthType::AuthHeader),
+            host_header: Some("bucket.s3.amazonaws.com".to_owned()),
+            tls_version: None,
+        };
+        assert_eq!(
+            record.to_string(),
+            concat!(
+                "owner bucket [06/Feb/2019:00:00:38 +0000] 192.0.2.3 - 3E57427F3EXAMPLE REST.GET.OBJECT photos/puppy.jpg ",
+                "\"GET /bucket/photos/puppy.jpg HTTP/1.1\" 200 - 2662 2662 70 10 \"-\" \"curl/8.0\" - HOST ",
+                "SigV4 - AuthHeader \"bucket.s3.amazonaws.com\" - - -"
+            )
+        );
+
+        let record = AccessLogRecord {
+            request_uri: r#"GET /bucket/a"b\c HTTP/1.1"#.to_owned(),
+            referer: Some(r#"x" 200 "#.to_owned()),
+            user_agent: Some(r"agent\".to_owned()),
+            requester: Some(r#"alice" bob"#.to_owned()),
+            host_header: Some("a b".to_owned()),
+            ..record
+        };
+        let line = record.to_string();
+        assert!(line.contains(r#" "GET /bucket/a\"b\\c HTTP/1.1" 200 "#));
+        assert!(line.contains(r#" "x\" 200 " "agent\\" "#));
+        assert!(line.contains(r#" 192.0.2.3 "alice\" bob" 3E57427F3EXAMPLE "#));
+        assert!(line.contains(r#" AuthHeader "a b" - "#));
+    }
+
+    #[tokio::test]
+    async fn sink() {
+        let records = Records::default();
+        let mut builder = S3ServiceBuilder::new(ObjectS3);
+        builder.set_auth(SimpleAuth::from_single(ACCESS_KEY, SECRET_KEY));
+        builder.set_request_id_generator(FixedGenerator);
+        builder.set_access_log(Arc::clone(&records));
+        let service = builder.build();
+
+        let mut req = http::Request::builder()
+            .uri("http://localhost/bucket/a%20b/c")
+            .header("host", "localhost")
+            .header("user-agent", "test")
+            .body(Body::empty())
+            .unwrap();
+        let signer = SigV4Signer::new(credentials(), "us-east-1");
+        signer.sign(&mut req, SignPayload::Empty, SystemTime::now()).unwrap();
+
+        let info = ConnectionInfo {
+            tls: Some(TlsInfo {
+                version: Some("TLSv1.3".to_owned()),
+                cipher_suite: Some("TLS_AES_128_GCM_SHA256".to_owned()),
+                ..Default::default()
+            }),
+            ..ConnectionInfo::new(([192, 0, 2, 3], 1234).into())
+        };
+        let mut resp = service.with_connection(info).call(req).await.unwrap();
+        assert!(records.lock().unwrap().is_empty());
+        resp.body_mut().store_all_unlimited().await.unwrap();
+        drop(resp);
+
+        let req = http::Request::builder()
+            .uri("http://localhost/bucket/missing")
+            .body(Body::empty())
+            .unwrap();
+        drop(service.call(req).await.unwrap());
+
+        let records = records.lock().unwrap();
+        assert_eq!(records.len(), 2);
+
+        let record = &records[0];
+        assert_eq!(record.bucket.as_deref(), Some("bucket"));
+        assert_eq!(record.key.as_deref(), Some("a%20b/c"));
+        assert_eq!(record.requester.as_deref(), Some(ACCESS_KEY));
+        assert_eq!(record.request_id, "REQUEST");
+        assert_eq!(record.operation, "REST.GET.OBJECT");
+        assert_eq!(record.request_uri, "GET /bucket/a%20b/c HTTP/1.1");
+        assert_eq!(record.status, StatusCode::OK);
+        assert_eq!(record.bytes_sent, 5);
+        assert_eq!(record.object_size, Some(5));
+        assert_eq!(record.user_agent.as_deref(), Some("test"));
+        assert_eq!(record.version_id.as_deref(), Some("v1"));
+        assert_eq!(record.host_id, "HOST");
+        assert_eq!(record.signature_version, Some(SignatureVersion::V4));
+        assert_eq!(record.auth_type, Some(AuthType::AuthHeader));
+        assert_eq!(record.host_header.as_deref(), Some("localhost"));
+        assert_eq!(record.remote_ip, Some(IpAddr::from([192, 0, 2, 3])));
+        assert_eq!(record.tls_version.as_deref(), Some("TLSv1.3"));
+        assert_eq!(record.cipher_suite.as_deref(), Some("TLS_AES_128_GCM_SHA256"));
+
+        let record = &records[1];
+        assert_eq!(record.remote_ip, None);
+        assert_eq!(record.requester, None);
+        assert_eq!(record.operation, "REST.GET.OBJECT");
+        assert_eq!(record.status, StatusCode::FORBIDDEN);
+        assert_eq!(record.error_code, Some(S3ErrorCode::AccessDenied));
+        assert_eq!(record.signature_version, None);
+    }
+}
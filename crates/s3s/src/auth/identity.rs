@@ -0,0 +1,28 @@
+use std::collections::HashMap;
+
+use hyper::http::Extensions;
+
+/// The identity of an authenticated caller
+///
+/// It is returned by [`S3Auth::get_identity`](super::S3Auth::get_identity)
+/// and passed to [`S3AccessContext`](crate::access::S3AccessContext) and [`S3Request`](crate::S3Request).
+#[derive(Debug, Clone, Default)]
+pub struct Identity {
+    /// An opaque principal, such as a user name or an ARN
+    pub principal: Option<String>,
+
+    /// The account which the principal belongs to
+    pub account_id: Option<String>,
+
+    /// The groups which the principal belongs to
+    pub groups: Vec<String>,
+
+    /// The tags attached to the principal
+    pub tags: HashMap<String, String>,
+
+    /// The policies attached to the principal
+    pub policies: Vec<String>,
+
+    /// Custom data of the identity
+    pub extensions: Extensions,
+}
@@ -3,9 +3,15 @@
 mod secret_key;
 pub use self::secret_key::{Credentials, SecretKey};
 
+mod identity;
+pub use self::identity::Identity;
+
 mod simple_auth;
 pub use self::simple_auth::SimpleAuth;
 
+mod signature_policy;
+pub use self::signature_policy::*;
+
 use crate::error::S3Result;
 
 /// S3 Authentication Provider
@@ -15,4 +21,33 @@ pub trait S3Auth: Send + Sync + 'static {
     ///
     /// This method is usually implemented as a database query.
     async fn get_secret_key(&self, access_key: &str) -> S3Result<SecretKey>;
+
+    /// Gets the corresponding secret key of temporary credentials.
+    ///
+    /// This method is called instead of [`get_secret_key`](S3Auth::get_secret_key)
+    /// when the request carries a session token (`x-amz-security-token`).
+    /// It should return `ExpiredToken` if the credentials have expired,
+    /// or `InvalidToken` if the token does not belong to the access key.
+    ///
+    /// The default implementation rejects all session tokens.
+    async fn get_secret_key_with_session_token(&self, access_key: &str, session_token: &str) -> S3Result<SecretKey> {
+        let _ = (access_key, session_token);
+        Err(s3_error!(InvalidToken, "The provided token is malformed or otherwise invalid."))
+    }
+
+    /// Gets the secret key and the identity of the access key.
+    ///
+    /// The identity is passed to [`S3AccessContext`](crate::access::S3AccessContext) and [`S3Request`](crate::S3Request),
+    /// so that access providers and handlers don't need to query it again.
+    ///
+    /// The default implementation calls [`get_secret_key`](S3Auth::get_secret_key)
+    /// or [`get_secret_key_with_session_token`](S3Auth::get_secret_key_with_session_token)
+    /// and returns no identity.
+    async fn get_identity(&self, access_key: &str, session_token: Option<&str>) -> S3Result<(SecretKey, Option<Identity>)> {
+        let secret_key = match session_token {
+            Some(token) => self.get_secret_key_with_session_token(access_key, token).await?,
+            None => self.get_secret_key(access_key).await?,
+        };
+        Ok((secret_key, None))
+    }
 }
@@ -6,9 +6,38 @@ use subtle::ConstantTimeEq;
 use zeroize::Zeroize;
 
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct Credentials {
     pub access_key: String,
     pub secret_key: SecretKey,
+    /// session token of temporary credentials
+    pub session_token: Option<String>,
+}
+
+impl Credentials {
+    /// Constructs long-term credentials.
+    #[must_use]
+    pub fn new(access_key: impl Into<String>, secret_key: impl Into<SecretKey>) -> Self {
+        Self {
+            access_key: access_key.into(),
+            secret_key: secret_key.into(),
+            session_token: None,
+        }
+    }
+
+    /// Constructs temporary credentials with a session token.
+    #[must_use]
+    pub fn with_session_token(
+        access_key: impl Into<String>,
+        secret_key: impl Into<SecretKey>,
+        session_token: impl Into<String>,
+    ) -> Self {
+        Self {
+            access_key: access_key.into(),
+            secret_key: secret_key.into(),
+            session_token: Some(session_token.into()),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+/// Limits applied when checking request signatures
+///
+/// See [`S3ServiceBuilder::set_signature_policy`](crate::service::S3ServiceBuilder::set_signature_policy)
+#[derive(Debug, Clone)]
+pub struct SignaturePolicy {
+    /// The maximum difference between the request time and the server time.
+    ///
+    /// The default value is 15 minutes.
+    pub max_skew_time: Duration,
+
+    /// The maximum lifetime of presigned URLs.
+    ///
+    /// The default value is 7 days, which is the limit of AWS.
+    pub max_presigned_url_expires: Duration,
+
+    /// Whether signature v2 is accepted.
+    ///
+    /// The default value is `true`.
+    pub allow_sigv2: bool,
+}
+
+/// The default maximum skew time (15 minutes)
+pub const DEFAULT_MAX_SKEW_TIME: Duration = Duration::from_secs(15 * 60);
+
+/// The default maximum lifetime of presigned URLs (7 days)
+pub const DEFAULT_MAX_PRESIGNED_URL_EXPIRES: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+impl Default for SignaturePolicy {
+    fn default() -> Self {
+        Self {
+            max_skew_time: DEFAULT_MAX_SKEW_TIME,
+            max_presigned_url_expires: DEFAULT_MAX_PRESIGNED_URL_EXPIRES,
+            allow_sigv2: true,
+        }
+    }
+}
+
+impl SignaturePolicy {
+    pub(crate) fn max_skew_time(&self) -> time::Duration {
+        time::Duration::try_from(self.max_skew_time).unwrap_or(time::Duration::MAX)
+    }
+
+    pub(crate) fn max_presigned_url_expires(&self) -> time::Duration {
+        time::Duration::try_from(self.max_presigned_url_expires).unwrap_or(time::Duration::MAX)
+    }
+}
@@ -1,22 +1,43 @@
 use super::S3Auth;
 
-use crate::auth::SecretKey;
+use crate::auth::{Credentials, Identity, SecretKey};
 use crate::error::S3Result;
 
 use std::collections::HashMap;
+use std::ops::Not;
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use subtle::ConstantTimeEq;
 
 /// A simple authentication provider
-#[derive(Debug, Default)]
+///
+/// It accepts temporary credentials registered by [`SimpleAuth::register_temporary`]
+/// or minted by [`SimpleIssuer`](crate::sts::SimpleIssuer).
+/// Clones share the temporary credentials.
+#[derive(Debug, Default, Clone)]
 pub struct SimpleAuth {
     /// key map
     map: HashMap<String, SecretKey>,
+    /// identity map
+    identities: HashMap<String, Identity>,
+    /// temporary credentials
+    sessions: Arc<RwLock<HashMap<String, Session>>>,
+}
+
+#[derive(Debug)]
+struct Session {
+    secret_key: SecretKey,
+    token: SecretKey,
+    expiration: SystemTime,
+    identity: Option<Identity>,
 }
 
 impl SimpleAuth {
     /// Constructs a new `SimpleAuth`
     #[must_use]
     pub fn new() -> Self {
-        Self { map: HashMap::new() }
+        Self::default()
     }
 
     #[must_use]
@@ -24,7 +45,7 @@ impl SimpleAuth {
         let access_key = access_key.into();
         let secret_key = secret_key.into();
         let map = [(access_key, secret_key)].into_iter().collect();
-        Self { map }
+        Self { map, ..Self::default() }
     }
 
     /// register a pair of keys
@@ -32,6 +53,39 @@ impl SimpleAuth {
         self.map.insert(access_key, secret_key)
     }
 
+    /// attach an identity to an access key
+    pub fn set_identity(&mut self, access_key: String, identity: Identity) -> Option<Identity> {
+        self.identities.insert(access_key, identity)
+    }
+
+    /// register temporary credentials which expire at `expiration`
+    ///
+    /// Expired temporary credentials are removed.
+    pub fn register_temporary(&self, access_key: String, secret_key: SecretKey, session_token: String, expiration: SystemTime) {
+        self.register_session(access_key, secret_key, session_token, expiration, None);
+    }
+
+    /// register temporary credentials with the identity of the session
+    pub(crate) fn register_session(
+        &self,
+        access_key: String,
+        secret_key: SecretKey,
+        session_token: String,
+        expiration: SystemTime,
+        identity: Option<Identity>,
+    ) {
+        let session = Session {
+            secret_key,
+            token: session_token.into(),
+            expiration,
+            identity,
+        };
+        let now = SystemTime::now();
+        let mut sessions = self.sessions.write().unwrap_or_else(std::sync::PoisonError::into_inner);
+        sessions.retain(|_, s| s.expiration > now);
+        sessions.insert(access_key, session);
+    }
+
     /// lookup a secret key
     #[must_use]
     pub fn lookup(&self, access_key: &str) -> Option<&SecretKey> {
@@ -47,4 +101,97 @@ impl S3Auth for SimpleAuth {
             Some(s) => Ok(s.clone()),
         }
     }
+
+    async fn get_secret_key_with_session_token(&self, access_key: &str, session_token: &str) -> S3Result<SecretKey> {
+        self.check_session(access_key, session_token)
+            .map(|(secret_key, _)| secret_key)
+    }
+
+    async fn get_identity(&self, access_key: &str, session_token: Option<&str>) -> S3Result<(SecretKey, Option<Identity>)> {
+        match session_token {
+            Some(token) => self.check_session(access_key, token),
+            None => {
+                let secret_key = self.get_secret_key(access_key).await?;
+                Ok((secret_key, self.identities.get(access_key).cloned()))
+            }
+        }
+    }
+}
+
+impl SimpleAuth {
+    fn check_session(&self, access_key: &str, session_token: &str) -> S3Result<(SecretKey, Option<Identity>)> {
+        let sessions = self.sessions.read().unwrap_or_else(std::sync::PoisonError::into_inner);
+        let Some(session) = sessions.get(access_key) else {
+            return Err(s3_error!(InvalidToken, "The provided token is malformed or otherwise invalid."));
+        };
+        if bool::from(session.token.ct_eq(&SecretKey::from(session_token))).not() {
+            return Err(s3_error!(InvalidToken, "The provided token is malformed or otherwise invalid."));
+        }
+        if SystemTime::now() >= session.expiration {
+            return Err(s3_error!(ExpiredToken, "The provided token has expired."));
+        }
+        Ok((session.secret_key.clone(), session.identity.clone()))
+    }
+
+    /// Returns the identity of the caller and the expiration of its session if the credentials are temporary.
+    pub(crate) fn caller(&self, credentials: &Credentials) -> (Option<Identity>, Option<SystemTime>) {
+        if credentials.session_token.is_none() {
+            return (self.identities.get(&credentials.access_key).cloned(), None);
+        }
+        let sessions = self.sessions.read().unwrap_or_else(std::sync::PoisonError::into_inner);
+        match sessions.get(&credentials.access_key) {
+            Some(session) => (session.identity.clone(), Some(session.expiration)),
+            None => (None, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::S3ErrorCode;
+
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn temporary_credentials() {
+        let auth = SimpleAuth::new();
+        let expiration = SystemTime::now() + Duration::from_secs(3600);
+        auth.register_temporary("ASIA1".into(), "secret1".into(), "token1".into(), expiration);
+        let expiration = SystemTime::now() - Duration::from_secs(1);
+        auth.register_temporary("ASIA2".into(), "secret2".into(), "token2".into(), expiration);
+
+        let secret_key = auth.get_secret_key_with_session_token("ASIA1", "token1").await.unwrap();
+        assert_eq!(secret_key.expose(), "secret1");
+
+        let err = auth.get_secret_key_with_session_token("ASIA1", "token2").await.unwrap_err();
+        assert_eq!(err.code(), &S3ErrorCode::InvalidToken);
+
+        let err = auth.get_secret_key_with_session_token("ASIA2", "token2").await.unwrap_err();
+        assert_eq!(err.code(), &S3ErrorCode::ExpiredToken);
+
+        let err = auth.get_secret_key("ASIA1").await.unwrap_err();
+        assert_eq!(err.code(), &S3ErrorCode::NotSignedUp);
+    }
+
+    #[tokio::test]
+    async fn identity() {
+        let mut auth = SimpleAuth::from_single("AKIA1", "secret1");
+        let identity = Identity {
+            principal: Some("alice".into()),
+            account_id: Some("123456789012".into()),
+            ..Default::default()
+        };
+        auth.set_identity("AKIA1".into(), identity);
+
+        let (secret_key, identity) = auth.get_identity("AKIA1", None).await.unwrap();
+        assert_eq!(secret_key.expose(), "secret1");
+        assert_eq!(identity.unwrap().principal.as_deref(), Some("alice"));
+
+        let expiration = SystemTime::now() + Duration::from_secs(3600);
+        auth.register_temporary("ASIA1".into(), "secret2".into(), "token1".into(), expiration);
+        let (_, identity) = auth.get_identity("ASIA1", Some("token1")).await.unwrap();
+        assert!(identity.is_none());
+    }
 }
@@ -0,0 +1,248 @@
+//! Connection information
+//!
+//! [`ConnectionInfo`] describes the connection of a request, such as the peer address and the TLS state.
+//! It is inserted into the request extensions by [`S3Service::with_connection`](crate::service::S3Service::with_connection),
+//! or by any middleware before the request reaches [`S3Service`](crate::service::S3Service).
+//!
+//! Behind proxies, the `X-Forwarded-For` and `X-Forwarded-Proto` headers are used
+//! if the peer is one of [`TrustedProxies`].
+//!
+//! The source IP and the secure transport flag are used by access logs and access control,
+//! such as the `aws:SourceIp` and `aws:SecureTransport` condition keys.
+
+use std::net::{IpAddr, SocketAddr};
+
+use hyper::HeaderMap;
+
+/// The connection of a request
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionInfo {
+    /// The address of the peer of the connection
+    pub peer_addr: Option<SocketAddr>,
+
+    /// The local address of the connection
+    pub local_addr: Option<SocketAddr>,
+
+    /// The TLS state, `None` means plain text.
+    pub tls: Option<TlsInfo>,
+
+    /// The client IP forwarded by trusted proxies
+    pub forwarded_for: Option<IpAddr>,
+
+    /// The protocol forwarded by trusted proxies, such as `https`
+    pub forwarded_proto: Option<String>,
+}
+
+/// The TLS state of a connection
+#[derive(Debug, Clone, Default)]
+pub struct TlsInfo {
+    /// The server name indication
+    pub server_name: Option<String>,
+
+    /// The protocol version, such as `TLSv1.3`
+    pub version: Option<String>,
+
+    /// The cipher suite, such as `ECDHE-RSA-AES128-GCM-SHA256`
+    pub cipher_suite: Option<String>,
+}
+
+impl ConnectionInfo {
+    /// Creates a plain text connection from the peer address.
+    #[must_use]
+    pub fn new(peer_addr: SocketAddr) -> Self {
+        Self {
+            peer_addr: Some(peer_addr),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the IP address of the client.
+    ///
+    /// It is the address forwarded by trusted proxies or the address of the peer.
+    #[must_use]
+    pub fn source_ip(&self) -> Option<IpAddr> {
+        self.forwarded_for
+            .or_else(|| self.peer_addr.map(|addr| addr.ip().to_canonical()))
+    }
+
+    /// Returns whether the request is sent over TLS.
+    ///
+    /// The protocol forwarded by trusted proxies takes precedence over the TLS state.
+    #[must_use]
+    pub fn is_secure(&self) -> bool {
+        match self.forwarded_proto.as_deref() {
+            Some(proto) => proto.eq_ignore_ascii_case("https"),
+            None => self.tls.is_some(),
+        }
+    }
+}
+
+impl From<SocketAddr> for ConnectionInfo {
+    fn from(peer_addr: SocketAddr) -> Self {
+        Self::new(peer_addr)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("The network is invalid")]
+pub struct InvalidNetwork;
+
+/// The networks of trusted proxies
+///
+/// It is registered by [`S3ServiceBuilder::set_trusted_proxies`](crate::service::S3ServiceBuilder::set_trusted_proxies).
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    networks: Vec<(IpAddr, u8)>,
+}
+
+impl TrustedProxies {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a network, such as `10.0.0.0/8`, or an address, such as `127.0.0.1`.
+    ///
+    /// # Errors
+    /// Returns an error if the network is invalid.
+    pub fn add(&mut self, network: &str) -> Result<(), InvalidNetwork> {
+        let (addr, prefix_len) = match network.split_once('/') {
+            Some((addr, len)) => (addr, Some(len)),
+            None => (network, None),
+        };
+        let addr: IpAddr = addr.parse().map_err(|_| InvalidNetwork)?;
+        let max_len = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix_len {
+            Some(len) => len.parse::<u8>().map_err(|_| InvalidNetwork)?,
+            None => max_len,
+        };
+        if prefix_len > max_len {
+            return Err(InvalidNetwork);
+        }
+        self.networks.push((addr, prefix_len));
+        Ok(())
+    }
+
+    /// Returns whether the address is in any of the networks.
+    #[must_use]
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.networks.iter().any(|&(network, prefix_len)| match (network, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => prefix_eq(u32::from(net).into(), u32::from(ip).into(), 32, prefix_len),
+            (IpAddr::V6(net), IpAddr::V6(ip)) => prefix_eq(u128::from(net), u128::from(ip), 128, prefix_len),
+            _ => false,
+        })
+    }
+}
+
+fn prefix_eq(a: u128, b: u128, bits: u8, prefix_len: u8) -> bool {
+    let shift = bits - prefix_len;
+    shift == bits || (a >> shift) == (b >> shift)
+}
+
+fn parse_forwarded_ip(s: &str) -> Option<IpAddr> {
+    let s = s.trim();
+    let ip = s
+        .parse::<IpAddr>()
+        .or_else(|_| s.parse::<SocketAddr>().map(|addr| addr.ip()))
+        .ok()?;
+    Some(ip.to_canonical())
+}
+
+/// Resolves the forwarded client IP and protocol if the peer is a trusted proxy.
+///
+/// `X-Forwarded-For` is walked from right to left and the first untrusted address is the client.
+/// The rightmost `X-Forwarded-Proto` value is used, which is set by the nearest proxy.
+pub(crate) fn resolve_forwarded(info: &mut ConnectionInfo, proxies: &TrustedProxies, headers: &HeaderMap) {
+    let Some(peer_addr) = info.peer_addr else { return };
+    if !proxies.contains(peer_addr.ip()) {
+        return;
+    }
+
+    let values = headers.get_all("x-forwarded-for").iter().filter_map(|v| v.to_str().ok());
+    let hops: Vec<&str> = values.flat_map(|v| v.split(',')).collect();
+
+    let mut client = None;
+    for hop in hops.into_iter().rev() {
+        let Some(ip) = parse_forwarded_ip(hop) else { break };
+        client = Some(ip);
+        if !proxies.contains(ip) {
+            break;
+        }
+    }
+    if client.is_some() {
+        info.forwarded_for = client;
+    }
+
+    let values = headers.get_all("x-forwarded-proto").iter().filter_map(|v| v.to_str().ok());
+    let proto = values.flat_map(|v| v.split(',')).map(str::trim).rfind(|v| !v.is_empty());
+    if let Some(proto) = proto {
+        info.forwarded_proto = Some(proto.to_owned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trusted_proxies() {
+        let mut proxies = TrustedProxies::new();
+        proxies.add("10.0.0.0/8").unwrap();
+        proxies.add("192.0.2.1").unwrap();
+        proxies.add("2001:db8::/32").unwrap();
+        assert!(proxies.add("10.0.0.0/33").is_err());
+        assert!(proxies.add("example.com").is_err());
+
+        assert!(proxies.contains("10.1.2.3".parse().unwrap()));
+        assert!(proxies.contains("::ffff:10.1.2.3".parse().unwrap()));
+        assert!(proxies.contains("192.0.2.1".parse().unwrap()));
+        assert!(!proxies.contains("192.0.2.2".parse().unwrap()));
+        assert!(proxies.contains("2001:db8::1".parse().unwrap()));
+        assert!(!proxies.contains("2001:db9::1".parse().unwrap()));
+
+        let mut any = TrustedProxies::new();
+        any.add("0.0.0.0/0").unwrap();
+        assert!(any.contains("203.0.113.7".parse().unwrap()));
+    }
+
+    #[test]
+    fn forwarded() {
+        let mut proxies = TrustedProxies::new();
+        proxies.add("10.0.0.0/8").unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "198.51.100.1, 203.0.113.7, 10.0.0.2".parse().unwrap());
+        headers.insert("x-forwarded-proto", "https".parse().unwrap());
+
+        let mut info = ConnectionInfo::new("10.0.0.1:1234".parse().unwrap());
+        resolve_forwarded(&mut info, &proxies, &headers);
+        assert_eq!(info.source_ip(), Some("203.0.113.7".parse().unwrap()));
+        assert!(info.is_secure());
+
+        // untrusted peers cannot forward
+        let mut info = ConnectionInfo::new("192.0.2.1:1234".parse().unwrap());
+        resolve_forwarded(&mut info, &proxies, &headers);
+        assert_eq!(info.source_ip(), Some("192.0.2.1".parse().unwrap()));
+        assert!(!info.is_secure());
+
+        // all hops are trusted
+        headers.insert("x-forwarded-for", "10.0.0.3, 10.0.0.2".parse().unwrap());
+        let mut info = ConnectionInfo::new("10.0.0.1:1234".parse().unwrap());
+        resolve_forwarded(&mut info, &proxies, &headers);
+        assert_eq!(info.source_ip(), Some("10.0.0.3".parse().unwrap()));
+
+        // the client cannot spoof the protocol set by the proxy
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-proto", "https, http".parse().unwrap());
+        let mut info = ConnectionInfo::new("10.0.0.1:1234".parse().unwrap());
+        resolve_forwarded(&mut info, &proxies, &headers);
+        assert!(!info.is_secure());
+
+        headers.insert("x-forwarded-proto", "https".parse().unwrap());
+        headers.append("x-forwarded-proto", "http".parse().unwrap());
+        let mut info = ConnectionInfo::new("10.0.0.1:1234".parse().unwrap());
+        resolve_forwarded(&mut info, &proxies, &headers);
+        assert!(!info.is_secure());
+    }
+}
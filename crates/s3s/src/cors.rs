@@ -0,0 +1,420 @@
+//! Cross-origin resource sharing (CORS)
+//!
+//! The CORS rules of a bucket are provided by [`S3Cors`], which is registered by
+//! [`S3ServiceBuilder::set_cors`](crate::service::S3ServiceBuilder::set_cors).
+//!
+//! When it is set, [`S3Service`](crate::service::S3Service) answers `OPTIONS` preflight requests to buckets and objects,
+//! and adds `Access-Control-*` headers to responses of requests with an `Origin` header,
+//! according to the first matching [`CORSRule`].
+//! Preflight requests are answered before the signature is checked, because browsers send them without credentials.
+//! The headers are added after the access is checked, so requests rejected by the service itself do not have them.
+//! Responses which already have `Access-Control-Allow-Origin` are not changed, except for `Vary`.
+//! `Vary` is added to all responses which have passed the access check, even if no rule matches,
+//! so that caches do not serve a response to another origin.
+
+use crate::S3Request;
+use crate::dto::{CORSRule, GetBucketCorsInput};
+use crate::error::{S3Error, S3ErrorCode, S3Result};
+use crate::http::{Request, Response};
+use crate::path::S3Path;
+use crate::s3_trait::S3;
+
+use std::sync::Arc;
+
+use bytestring::ByteString;
+use hyper::HeaderMap;
+use hyper::Method;
+use hyper::StatusCode;
+use hyper::header::{self, HeaderValue};
+use tracing::debug;
+
+/// A source of CORS rules
+#[async_trait::async_trait]
+pub trait S3Cors: Send + Sync + 'static {
+    /// Returns the CORS rules of a bucket.
+    ///
+    /// `None` means CORS is not enabled for the bucket.
+    /// It is called for every preflight request and every request with an `Origin` header.
+    async fn get_rules(&self, cx: &S3CorsContext<'_>) -> S3Result<Option<Vec<CORSRule>>>;
+}
+
+pub struct S3CorsContext<'a> {
+    s3: &'a Arc<dyn S3>,
+    bucket: &'a str,
+}
+
+impl S3CorsContext<'_> {
+    /// Returns the S3 implementation of the service.
+    #[must_use]
+    pub fn s3(&self) -> &Arc<dyn S3> {
+        self.s3
+    }
+
+    /// Returns the bucket of current request.
+    #[must_use]
+    pub fn bucket(&self) -> &str {
+        self.bucket
+    }
+}
+
+/// A CORS source which calls [`S3::get_bucket_cors`] of the service.
+///
+/// `NoSuchCORSConfiguration` and `NotImplemented` errors mean CORS is not enabled for the bucket.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BucketCors;
+
+#[async_trait::async_trait]
+impl S3Cors for BucketCors {
+    async fn get_rules(&self, cx: &S3CorsContext<'_>) -> S3Result<Option<Vec<CORSRule>>> {
+        let input = GetBucketCorsInput {
+            bucket: cx.bucket.to_owned(),
+            expected_bucket_owner: None,
+        };
+        let req = S3Request {
+            input,
+            method: Method::GET,
+            uri: format!("/{}?cors", cx.bucket).parse().unwrap_or_default(),
+            headers: HeaderMap::new(),
+            extensions: hyper::http::Extensions::new(),
+            credentials: None,
+            identity: None,
+            region: None,
+            service: None,
+            trailing_headers: None,
+        };
+        match cx.s3.get_bucket_cors(req).await {
+            Ok(resp) => Ok(resp.output.cors_rules),
+            Err(err) if matches!(err.code(), S3ErrorCode::NoSuchCORSConfiguration | S3ErrorCode::NotImplemented) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+fn cors_error(code: &'static str, status: StatusCode, msg: &'static str) -> S3Error {
+    let mut err = S3Error::with_message(S3ErrorCode::Custom(ByteString::from_static(code)), msg);
+    err.set_status_code(status);
+    err
+}
+
+fn forbidden(msg: &'static str) -> S3Error {
+    cors_error("AccessForbidden", StatusCode::FORBIDDEN, msg)
+}
+
+const NOT_ENABLED: &str = "CORSResponse: CORS is not enabled for this bucket.";
+
+const NOT_ALLOWED: &str = "CORSResponse: This CORS request is not allowed. \
+    This is usually because the evaluation of Origin, request method / Access-Control-Request-Method \
+    or Access-Control-Request-Headers are not whitelisted by the resource's CORS spec.";
+
+/// Matches a pattern with at most one `*` wildcard.
+fn wildcard_match(pattern: &str, s: &str) -> bool {
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => s.len() >= prefix.len() + suffix.len() && s.starts_with(prefix) && s.ends_with(suffix),
+        None => pattern == s,
+    }
+}
+
+fn find_rule<'r>(rules: &'r [CORSRule], origin: &str, method: &str, headers: &[String]) -> Option<(&'r CORSRule, bool)> {
+    rules.iter().find_map(|rule| {
+        let allowed_origin = rule.allowed_origins.iter().find(|o| wildcard_match(o, origin))?;
+        if !rule.allowed_methods.iter().any(|m| m == method) {
+            return None;
+        }
+        let allowed_headers = rule.allowed_headers.as_deref().unwrap_or_default();
+        let headers_allowed = headers
+            .iter()
+            .all(|h| allowed_headers.iter().any(|p| wildcard_match(&p.to_ascii_lowercase(), h)));
+        headers_allowed.then_some((rule, allowed_origin == "*"))
+    })
+}
+
+fn header_str<'h>(headers: &'h HeaderMap, name: &header::HeaderName) -> Option<&'h str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+fn insert(headers: &mut HeaderMap, name: header::HeaderName, val: &str) {
+    if let Ok(val) = HeaderValue::try_from(val) {
+        headers.insert(name, val);
+    }
+}
+
+fn set_headers(headers: &mut HeaderMap, rule: &CORSRule, any_origin: bool, origin: &str) {
+    if any_origin {
+        insert(headers, header::ACCESS_CONTROL_ALLOW_ORIGIN, "*");
+    } else {
+        insert(headers, header::ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+        insert(headers, header::ACCESS_CONTROL_ALLOW_CREDENTIALS, "true");
+    }
+    insert(headers, header::ACCESS_CONTROL_ALLOW_METHODS, &rule.allowed_methods.join(", "));
+    if let Some(expose_headers) = rule.expose_headers.as_deref().filter(|h| !h.is_empty()) {
+        insert(headers, header::ACCESS_CONTROL_EXPOSE_HEADERS, &expose_headers.join(", "));
+    }
+    if let Some(max_age) = rule.max_age_seconds {
+        insert(headers, header::ACCESS_CONTROL_MAX_AGE, &max_age.to_string());
+    }
+}
+
+fn set_vary(headers: &mut HeaderMap) {
+    insert(
+        headers,
+        header::VARY,
+        "Origin, Access-Control-Request-Headers, Access-Control-Request-Method",
+    );
+}
+
+async fn get_rules(cors: &dyn S3Cors, s3: &Arc<dyn S3>, bucket: &str) -> S3Result<Option<Vec<CORSRule>>> {
+    cors.get_rules(&S3CorsContext { s3, bucket }).await
+}
+
+/// Answers a preflight request.
+pub(crate) async fn preflight(cors: &dyn S3Cors, s3: &Arc<dyn S3>, req: &Request) -> S3Result<Response> {
+    let origin = header_str(&req.headers, &header::ORIGIN);
+    let method = header_str(&req.headers, &header::ACCESS_CONTROL_REQUEST_METHOD);
+    let (Some(origin), Some(method)) = (origin, method) else {
+        return Err(cors_error(
+            "BadRequest",
+            StatusCode::BAD_REQUEST,
+            "Insufficient information. Origin request header needed.",
+        ));
+    };
+    let request_headers: Vec<String> = header_str(&req.headers, &header::ACCESS_CONTROL_REQUEST_HEADERS)
+        .into_iter()
+        .flat_map(|s| s.split(','))
+        .map(|h| h.trim().to_ascii_lowercase())
+        .filter(|h| !h.is_empty())
+        .collect();
+
+    let bucket = req.s3ext.s3_path.as_ref().and_then(S3Path::get_bucket_name);
+    let Some(bucket) = bucket else { return Err(forbidden(NOT_ENABLED)) };
+    let Some(rules) = get_rules(cors, s3, bucket).await? else {
+        return Err(forbidden(NOT_ENABLED));
+    };
+    let Some((rule, any_origin)) = find_rule(&rules, origin, method, &request_headers) else {
+        return Err(forbidden(NOT_ALLOWED));
+    };
+
+    let mut resp = Response::with_status(StatusCode::OK);
+    set_headers(&mut resp.headers, rule, any_origin, origin);
+    if !request_headers.is_empty() {
+        insert(&mut resp.headers, header::ACCESS_CONTROL_ALLOW_HEADERS, &request_headers.join(", "));
+    }
+    set_vary(&mut resp.headers);
+    Ok(resp)
+}
+
+/// Returns the CORS headers of an actual request.
+///
+/// `Vary` is always set, because the response depends on the origin even if no rule matches.
+pub(crate) async fn actual_headers(
+    cors: &dyn S3Cors,
+    s3: &Arc<dyn S3>,
+    method: &Method,
+    headers: &HeaderMap,
+    s3_path: &S3Path,
+) -> HeaderMap {
+    let mut cors_headers = HeaderMap::new();
+    set_vary(&mut cors_headers);
+
+    let Some(origin) = header_str(headers, &header::ORIGIN) else { return cors_headers };
+    let Some(bucket) = s3_path.get_bucket_name() else { return cors_headers };
+
+    let rules = match get_rules(cors, s3, bucket).await {
+        Ok(Some(rules)) => rules,
+        Ok(None) => return cors_headers,
+        Err(err) => {
+            debug!(?err, "failed to get cors rules");
+            return cors_headers;
+        }
+    };
+    if let Some((rule, any_origin)) = find_rule(&rules, origin, method.as_str(), &[]) {
+        set_headers(&mut cors_headers, rule, any_origin, origin);
+    }
+    cors_headers
+}
+
+/// Adds the CORS headers to a response, unless the response already has `Access-Control-Allow-Origin`.
+///
+/// `Vary` is always appended.
+pub(crate) fn decorate(resp: &mut Response, mut cors_headers: HeaderMap) {
+    if let Some(vary) = cors_headers.remove(header::VARY) {
+        resp.headers.append(header::VARY, vary);
+    }
+    if resp.headers.contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN) {
+        return;
+    }
+    resp.headers.extend(cors_headers);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::auth::SimpleAuth;
+    use crate::dto::{GetBucketCorsOutput, GetObjectInput, GetObjectOutput};
+    use crate::http::Body;
+    use crate::service::S3ServiceBuilder;
+    use crate::signer::SigV4Signer;
+    use crate::tests::{ACCESS_KEY, SECRET_KEY, credentials};
+
+    use crate::{S3Request, S3Response};
+    use std::time::{Duration, SystemTime};
+
+    struct CorsS3;
+
+    #[async_trait::async_trait]
+    impl S3 for CorsS3 {
+        async fn get_bucket_cors(&self, req: S3Request<GetBucketCorsInput>) -> S3Result<S3Response<GetBucketCorsOutput>> {
+            if req.input.bucket != "web" {
+                return Err(s3_error!(NoSuchCORSConfiguration));
+            }
+            let rules = vec![
+                CORSRule {
+                    allowed_headers: Some(vec!["x-amz-*".to_owned(), "Content-Type".to_owned()]),
+                    allowed_methods: vec!["GET".to_owned(), "PUT".to_owned()],
+                    allowed_origins: vec!["https://*.example.com".to_owned()],
+                    expose_headers: Some(vec!["ETag".to_owned()]),
+                    id: None,
+                    max_age_seconds: Some(600),
+                },
+                CORSRule {
+                    allowed_headers: None,
+                    allowed_methods: vec!["GET".to_owned()],
+                    allowed_origins: vec!["*".to_owned()],
+                    expose_headers: None,
+                    id: None,
+                    max_age_seconds: None,
+                },
+            ];
+            Ok(S3Response::new(GetBucketCorsOutput { cors_rules: Some(rules) }))
+        }
+
+        async fn get_object(&self, _req: S3Request<GetObjectInput>) -> S3Result<S3Response<GetObjectOutput>> {
+            Ok(S3Response::new(GetObjectOutput::default()))
+        }
+    }
+
+    #[test]
+    fn wildcard() {
+        assert!(wildcard_match("*", "https://a.com"));
+        assert!(wildcard_match("https://*.example.com", "https://app.example.com"));
+        assert!(!wildcard_match("https://*.example.com", "https://example.com"));
+        assert!(!wildcard_match("https://*.example.com", "http://app.example.com"));
+        assert!(wildcard_match("x-amz-*", "x-amz-date"));
+        assert!(wildcard_match("content-type", "content-type"));
+    }
+
+    #[tokio::test]
+    async fn service() {
+        let mut builder = S3ServiceBuilder::new(CorsS3);
+        builder.set_cors(BucketCors);
+        let service = builder.build();
+
+        let preflight = |uri: &str, origin: &str, method: &str, headers: &str| {
+            let mut req = http::Request::builder()
+                .method(Method::OPTIONS)
+                .uri(uri)
+                .header(header::ORIGIN, origin)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, method);
+            if !headers.is_empty() {
+                req = req.header(header::ACCESS_CONTROL_REQUEST_HEADERS, headers);
+            }
+            req.body(Body::empty()).unwrap()
+        };
+        let get = |h: &http::HeaderMap, name| h.get(name).map(|v: &HeaderValue| v.to_str().unwrap().to_owned());
+
+        let req = preflight("http://localhost/web/key", "https://app.example.com", "PUT", "X-Amz-Date, content-type");
+        let resp = service.call(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let h = resp.headers();
+        assert_eq!(get(h, header::ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(), "https://app.example.com");
+        assert_eq!(get(h, header::ACCESS_CONTROL_ALLOW_CREDENTIALS).unwrap(), "true");
+        assert_eq!(get(h, header::ACCESS_CONTROL_ALLOW_METHODS).unwrap(), "GET, PUT");
+        assert_eq!(get(h, header::ACCESS_CONTROL_ALLOW_HEADERS).unwrap(), "x-amz-date, content-type");
+        assert_eq!(get(h, header::ACCESS_CONTROL_EXPOSE_HEADERS).unwrap(), "ETag");
+        assert_eq!(get(h, header::ACCESS_CONTROL_MAX_AGE).unwrap(), "600");
+
+        let req = preflight("http://localhost/web/key", "https://other.com", "GET", "");
+        let resp = service.call(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(get(resp.headers(), header::ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(), "*");
+        assert!(get(resp.headers(), header::ACCESS_CONTROL_ALLOW_CREDENTIALS).is_none());
+
+        let cases = [
+            ("http://localhost/web/key", "https://other.com", "PUT", "", 403),
+            ("http://localhost/web/key", "https://app.example.com", "PUT", "authorization", 403),
+            ("http://localhost/other/key", "https://app.example.com", "GET", "", 403),
+            ("http://localhost/", "https://app.example.com", "GET", "", 403),
+        ];
+        for (uri, origin, method, headers, status) in cases {
+            let resp = service.call(preflight(uri, origin, method, headers)).await.unwrap();
+            assert_eq!(resp.status().as_u16(), status, "{uri} {origin} {method} {headers}");
+            assert!(get(resp.headers(), header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+        }
+
+        let req = http::Request::builder()
+            .method(Method::OPTIONS)
+            .uri("http://localhost/web/key")
+            .body(Body::empty())
+            .unwrap();
+        let resp = service.call(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        for (origin, expected) in [
+            ("https://app.example.com", Some("https://app.example.com")),
+            ("https://other.com", Some("*")),
+        ] {
+            let req = http::Request::builder()
+                .uri("http://localhost/web/key")
+                .header(header::ORIGIN, origin)
+                .body(Body::empty())
+                .unwrap();
+            let resp = service.call(req).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert_eq!(get(resp.headers(), header::ACCESS_CONTROL_ALLOW_ORIGIN).as_deref(), expected);
+        }
+
+        let req = http::Request::builder()
+            .uri("http://localhost/other/key")
+            .header(header::ORIGIN, "https://app.example.com")
+            .body(Body::empty())
+            .unwrap();
+        let resp = service.call(req).await.unwrap();
+        assert!(get(resp.headers(), header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+        assert!(get(resp.headers(), header::VARY).unwrap().starts_with("Origin"));
+
+        // responses without `Origin` may be cached and served to other origins
+        let req = http::Request::builder()
+            .uri("http://localhost/web/key")
+            .body(Body::empty())
+            .unwrap();
+        let resp = service.call(req).await.unwrap();
+        assert!(get(resp.headers(), header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+        assert!(get(resp.headers(), header::VARY).unwrap().starts_with("Origin"));
+    }
+
+    #[tokio::test]
+    async fn presigned_url() {
+        let mut builder = S3ServiceBuilder::new(CorsS3);
+        builder.set_auth(SimpleAuth::from_single(ACCESS_KEY, SECRET_KEY));
+        builder.set_cors(BucketCors);
+        let service = builder.build();
+
+        let signer = SigV4Signer::new(credentials(), "us-east-1");
+        let uri = "http://localhost/web/key".parse().unwrap();
+        let url = signer
+            .presign(&Method::PUT, &uri, Duration::from_secs(3600), SystemTime::now())
+            .unwrap();
+
+        let req = http::Request::builder()
+            .method(Method::OPTIONS)
+            .uri(url)
+            .header(header::ORIGIN, "https://app.example.com")
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "PUT")
+            .body(Body::empty())
+            .unwrap();
+        let resp = service.call(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let origin = resp.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).unwrap();
+        assert_eq!(origin, "https://app.example.com");
+    }
+}
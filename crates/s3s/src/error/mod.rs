@@ -33,6 +33,7 @@ struct Inner {
     code: S3ErrorCode,
     message: Option<Cow<'static, str>>,
     // resource: Option<String>,
+    region: Option<String>,
     request_id: Option<String>,
     status_code: Option<StatusCode>,
     source: Option<StdError>,
@@ -46,6 +47,7 @@ impl S3Error {
             code,
             message: None,
             // resource: None,
+            region: None,
             request_id: None,
             status_code: None,
             source: None,
@@ -86,6 +88,11 @@ impl S3Error {
         self.0.message = Some(val.into());
     }
 
+    /// Sets the region in the error response, such as the expected region of a wrong credential scope.
+    pub fn set_region(&mut self, val: impl Into<String>) {
+        self.0.region = Some(val.into());
+    }
+
     pub fn set_request_id(&mut self, val: impl Into<String>) {
         self.0.request_id = Some(val.into());
     }
@@ -112,6 +119,11 @@ impl S3Error {
         self.0.message.as_deref()
     }
 
+    #[must_use]
+    pub fn region(&self) -> Option<&str> {
+        self.0.region.as_deref()
+    }
+
     #[must_use]
     pub fn request_id(&self) -> Option<&str> {
         self.0.request_id.as_deref()
@@ -172,6 +184,9 @@ impl xml::SerializeContent for S3Error {
         // if let Some(val) = self.0.resource.as_deref() {
         //     s.content("Resource", val)?;
         // }
+        if let Some(val) = self.0.region.as_deref() {
+            s.content("Region", val)?;
+        }
         if let Some(val) = self.0.request_id.as_deref() {
             s.content("RequestId", val)?;
         }
@@ -198,6 +213,9 @@ impl fmt::Display for S3Error {
         if let Some(ref message) = self.0.message {
             d.field("message", &message);
         }
+        if let Some(ref region) = self.0.region {
+            d.field("region", &region);
+        }
         if let Some(ref request_id) = self.0.request_id {
             d.field("request_id", &request_id);
         }
@@ -8,7 +8,8 @@ use stdx::default::default;
 pub struct VirtualHost<'a> {
     domain: Cow<'a, str>,
     bucket: Option<Cow<'a, str>>,
-    // pub(crate) region: Option<Cow<'a, str>>,
+    region: Option<Cow<'a, str>>,
+    website: bool,
 }
 
 impl<'a> VirtualHost<'a> {
@@ -16,6 +17,8 @@ impl<'a> VirtualHost<'a> {
         Self {
             domain: domain.into(),
             bucket: None,
+            region: None,
+            website: false,
         }
     }
 
@@ -23,6 +26,8 @@ impl<'a> VirtualHost<'a> {
         Self {
             domain: domain.into(),
             bucket: Some(bucket.into()),
+            region: None,
+            website: false,
         }
     }
 
@@ -37,6 +42,30 @@ impl<'a> VirtualHost<'a> {
     pub fn bucket(&self) -> Option<&str> {
         self.bucket.as_deref()
     }
+
+    #[inline]
+    #[must_use]
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
+    /// Sets the region of the endpoint, such as `us-west-2` in `bucket.s3.us-west-2.example.com`.
+    pub fn set_region(&mut self, region: impl Into<Cow<'a, str>>) {
+        self.region = Some(region.into());
+    }
+
+    /// Marks the host as a website endpoint.
+    ///
+    /// See [`crate::website`] for details.
+    pub fn set_website(&mut self, website: bool) {
+        self.website = website;
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_website(&self) -> bool {
+        self.website
+    }
 }
 
 pub trait S3Host: Send + Sync + 'static {
@@ -90,7 +119,74 @@ fn is_valid_domain(mut s: &str) -> bool {
     true
 }
 
+/// Checks a base domain which may contain a `*` label.
+fn is_valid_domain_pattern(s: &str) -> bool {
+    let (name, _) = split_port(s);
+    match name.split('.').filter(|&part| part == "*").count() {
+        0 => is_valid_domain(s),
+        1 => is_valid_domain(&s.replacen('*', "x", 1)),
+        _ => false,
+    }
+}
+
+fn split_port(s: &str) -> (&str, Option<&str>) {
+    match s.split_once(':') {
+        Some((name, port)) => (name, Some(port)),
+        None => (s, None),
+    }
+}
+
+/// Checks whether the label looks like a region, such as `us-west-2`.
+fn is_region_label(label: &str) -> bool {
+    let valid_char = |b: u8| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-';
+    label.bytes().all(valid_char) && !label.starts_with('-') && !label.ends_with('-')
+}
+
+/// Matches the host against a base domain whose `*` label matches the region.
+fn parse_host_header_with_region<'a>(base_domain: &str, host: &'a str) -> Option<VirtualHost<'a>> {
+    let (pattern, pattern_port) = split_port(base_domain);
+    let (name, port) = split_port(host);
+    if pattern_port != port {
+        return None;
+    }
+
+    let mut rest = Some(name);
+    let mut region = None;
+    for pattern_label in pattern.rsplit('.') {
+        let (left, label) = match rest?.rsplit_once('.') {
+            Some((left, label)) => (Some(left), label),
+            None => (None, rest?),
+        };
+        if label.is_empty() {
+            return None;
+        }
+        if pattern_label == "*" {
+            if !is_region_label(label) {
+                return None;
+            }
+            region = Some(label);
+        } else if pattern_label != label {
+            return None;
+        }
+        rest = left;
+    }
+
+    let mut vh = match rest {
+        None => VirtualHost::new(host),
+        Some(bucket) if !bucket.is_empty() => VirtualHost::with_bucket(&host[bucket.len() + 1..], bucket),
+        Some(_) => return None,
+    };
+    if let Some(region) = region {
+        vh.set_region(region);
+    }
+    Some(vh)
+}
+
 fn parse_host_header<'a>(base_domain: &'a str, host: &'a str) -> Option<VirtualHost<'a>> {
+    if base_domain.contains('*') {
+        return parse_host_header_with_region(base_domain, host);
+    }
+
     if host == base_domain {
         return Some(VirtualHost::new(base_domain));
     }
@@ -110,10 +206,13 @@ pub struct SingleDomain {
 impl SingleDomain {
     /// Create a new `SingleDomain` with the base domain.
     ///
+    /// The base domain may contain a `*` label, which matches the region,
+    /// such as `s3.*.example.com` or `s3.dualstack.*.example.com`.
+    ///
     /// # Errors
     /// Returns an error if the base domain is invalid.
     pub fn new(base_domain: &str) -> Result<Self, DomainError> {
-        if !is_valid_domain(base_domain) {
+        if !is_valid_domain_pattern(base_domain) {
             return Err(DomainError::InvalidDomain);
         }
 
@@ -140,6 +239,36 @@ impl S3Host for SingleDomain {
     }
 }
 
+fn check_base_domains<I>(base_domains: I) -> Result<Vec<String>, DomainError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut v: Vec<String> = default();
+
+    for domain in base_domains {
+        let domain = domain.as_ref();
+
+        if !is_valid_domain_pattern(domain) {
+            return Err(DomainError::InvalidDomain);
+        }
+
+        for other in &v {
+            if domain.ends_with(other) || other.ends_with(domain) {
+                return Err(DomainError::OverlappingSubdomains);
+            }
+        }
+
+        v.push(domain.to_owned());
+    }
+
+    if v.is_empty() {
+        return Err(DomainError::ZeroDomains);
+    }
+
+    Ok(v)
+}
+
 #[derive(Debug)]
 pub struct MultiDomain {
     base_domains: Vec<String>,
@@ -148,6 +277,9 @@ pub struct MultiDomain {
 impl MultiDomain {
     /// Create a new `MultiDomain` with the base domains.
     ///
+    /// The base domains may contain a `*` label, which matches the region.
+    /// See [`SingleDomain::new`] for details.
+    ///
     /// # Errors
     /// Returns an error if
     /// + any of the base domains are invalid.
@@ -158,29 +290,9 @@ impl MultiDomain {
         I: IntoIterator,
         I::Item: AsRef<str>,
     {
-        let mut v: Vec<String> = default();
-
-        for domain in base_domains {
-            let domain = domain.as_ref();
-
-            if !is_valid_domain(domain) {
-                return Err(DomainError::InvalidDomain);
-            }
-
-            for other in &v {
-                if domain.ends_with(other) || other.ends_with(domain) {
-                    return Err(DomainError::OverlappingSubdomains);
-                }
-            }
-
-            v.push(domain.to_owned());
-        }
-
-        if v.is_empty() {
-            return Err(DomainError::ZeroDomains);
-        }
-
-        Ok(Self { base_domains: v })
+        Ok(Self {
+            base_domains: check_base_domains(base_domains)?,
+        })
     }
 }
 
@@ -201,6 +313,49 @@ impl S3Host for MultiDomain {
     }
 }
 
+/// Website endpoints, such as `bucket.s3-website.example.com`
+///
+/// Hosts under the website domains are parsed as website endpoints.
+/// Other hosts are parsed by the inner [`S3Host`].
+#[derive(Debug)]
+pub struct WebsiteDomain<H> {
+    base_domains: Vec<String>,
+    inner: H,
+}
+
+impl<H: S3Host> WebsiteDomain<H> {
+    /// Create a new `WebsiteDomain` with the website domains and the host of the S3 API.
+    ///
+    /// # Errors
+    /// Returns an error if
+    /// + any of the website domains are invalid.
+    /// + any of the website domains overlap with each other.
+    /// + no website domains are specified.
+    pub fn new<I>(base_domains: I, inner: H) -> Result<Self, DomainError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        Ok(Self {
+            base_domains: check_base_domains(base_domains)?,
+            inner,
+        })
+    }
+}
+
+impl<H: S3Host> S3Host for WebsiteDomain<H> {
+    fn parse_host_header<'a>(&'a self, host: &'a str) -> S3Result<VirtualHost<'a>> {
+        for base_domain in &self.base_domains {
+            if let Some(mut vh) = parse_host_header(base_domain, host) {
+                vh.set_website(true);
+                return Ok(vh);
+            }
+        }
+
+        self.inner.parse_host_header(host)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -291,4 +446,65 @@ mod tests {
         assert_eq!(vh.domain(), "example.com");
         assert_eq!(vh.bucket(), Some("example.com.org"));
     }
+
+    #[test]
+    fn region_domain_parse() {
+        assert!(SingleDomain::new("s3.*.example.com").is_ok());
+        assert!(SingleDomain::new("s3.*.*.example.com").is_err());
+        assert!(SingleDomain::new("s3.us-*.example.com").is_err());
+
+        let md = MultiDomain::new(["s3.*.example.com", "s3.dualstack.*.example.com", "s3.*.example.org:9000"]).unwrap();
+
+        let vh = md.parse_host_header("bucket.s3.us-west-2.example.com").unwrap();
+        assert_eq!(vh.domain(), "s3.us-west-2.example.com");
+        assert_eq!(vh.bucket(), Some("bucket"));
+        assert_eq!(vh.region(), Some("us-west-2"));
+
+        let vh = md.parse_host_header("a.b.s3.dualstack.eu-west-1.example.com").unwrap();
+        assert_eq!(vh.domain(), "s3.dualstack.eu-west-1.example.com");
+        assert_eq!(vh.bucket(), Some("a.b"));
+        assert_eq!(vh.region(), Some("eu-west-1"));
+
+        let vh = md.parse_host_header("s3.us-east-1.example.com").unwrap();
+        assert_eq!(vh.domain(), "s3.us-east-1.example.com");
+        assert_eq!(vh.bucket(), None);
+        assert_eq!(vh.region(), Some("us-east-1"));
+
+        let vh = md.parse_host_header("bucket.s3.us-east-1.example.org:9000").unwrap();
+        assert_eq!(vh.domain(), "s3.us-east-1.example.org:9000");
+        assert_eq!(vh.bucket(), Some("bucket"));
+        assert_eq!(vh.region(), Some("us-east-1"));
+
+        // not matched, parsed as a CNAME
+        let vh = md.parse_host_header("bucket.s3.us-east-1.example.org").unwrap();
+        assert_eq!(vh.bucket(), Some("bucket.s3.us-east-1.example.org"));
+        assert_eq!(vh.region(), None);
+
+        let vh = md.parse_host_header("s3.example.com").unwrap();
+        assert_eq!(vh.bucket(), Some("s3.example.com"));
+        assert_eq!(vh.region(), None);
+
+        // invalid region labels are not matched
+        let vh = md.parse_host_header("bucket.s3.us-west-2-.example.com").unwrap();
+        assert_eq!(vh.bucket(), Some("bucket.s3.us-west-2-.example.com"));
+        assert_eq!(vh.region(), None);
+
+        assert!(md.parse_host_header("bucket.s3.us_west_2.example.com").is_err());
+    }
+
+    #[test]
+    fn website_domain_parse() {
+        let api = SingleDomain::new("s3.example.com").unwrap();
+        let wd = WebsiteDomain::new(["s3-website.example.com"], api).unwrap();
+
+        let vh = wd.parse_host_header("docs.s3-website.example.com").unwrap();
+        assert_eq!(vh.domain(), "s3-website.example.com");
+        assert_eq!(vh.bucket(), Some("docs"));
+        assert!(vh.is_website());
+
+        let vh = wd.parse_host_header("docs.s3.example.com").unwrap();
+        assert_eq!(vh.domain(), "s3.example.com");
+        assert_eq!(vh.bucket(), Some("docs"));
+        assert!(!vh.is_website());
+    }
 }
@@ -0,0 +1,352 @@
+//! Content-MD5 and x-amz-checksum-* verification
+
+use super::Body;
+
+use crate::checksum::ChecksumHasher;
+use crate::crypto::Checksum as _;
+use crate::crypto::{Crc32, Crc32c, Crc64Nvme, Md5, Sha1, Sha256};
+use crate::dto::Checksum;
+use crate::error::*;
+use crate::header;
+use crate::protocol::TrailingHeaders;
+use crate::stream::{ByteStream, RemainingLength};
+
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use bytes::Bytes;
+use futures::Stream;
+use hyper::HeaderMap;
+
+/// Checksum headers and the lengths of decoded checksums
+const CHECKSUM_HEADERS: [(&str, usize); 5] = [
+    ("x-amz-checksum-crc32", 4),
+    ("x-amz-checksum-crc32c", 4),
+    ("x-amz-checksum-sha1", 20),
+    ("x-amz-checksum-sha256", 32),
+    ("x-amz-checksum-crc64nvme", 8),
+];
+
+/// A stream which verifies `Content-MD5` and `x-amz-checksum-*` of a request body.
+///
+/// The expected checksums are taken from the request headers,
+/// or from the trailing headers of an aws-chunked body (declared by `x-amz-trailer`).
+///
+/// The stream yields a `BadDigest` error at the end if any checksum does not match.
+pub struct ChecksumStream {
+    /// body
+    body: Body,
+    /// verifier, `None` if the stream has finished
+    verifier: Option<ChecksumVerifier>,
+}
+
+/// Expected checksums of a request body
+pub struct ChecksumVerifier {
+    hasher: ChecksumHasher,
+    md5: Option<Md5>,
+
+    /// expected base64 `Content-MD5`
+    content_md5: Option<String>,
+    /// expected checksums from headers
+    checksums: Vec<(&'static str, String)>,
+    /// checksums declared by `x-amz-trailer`
+    trailer_names: Vec<&'static str>,
+    trailing_headers: Option<TrailingHeaders>,
+}
+
+impl fmt::Debug for ChecksumStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChecksumStream").finish_non_exhaustive()
+    }
+}
+
+fn decoded_len(value: &str) -> Option<usize> {
+    base64_simd::STANDARD.decode_to_vec(value).ok().map(|v| v.len())
+}
+
+fn check_checksum_value(name: &str, expected_len: usize, value: &str) -> S3Result<()> {
+    if decoded_len(value) != Some(expected_len) {
+        return Err(invalid_request!("Value for {name} header is invalid."));
+    }
+    Ok(())
+}
+
+fn enable_hasher(hasher: &mut ChecksumHasher, name: &str) {
+    match name {
+        "x-amz-checksum-crc32" => hasher.crc32 = Some(Crc32::default()),
+        "x-amz-checksum-crc32c" => hasher.crc32c = Some(Crc32c::default()),
+        "x-amz-checksum-sha1" => hasher.sha1 = Some(Sha1::default()),
+        "x-amz-checksum-sha256" => hasher.sha256 = Some(Sha256::default()),
+        "x-amz-checksum-crc64nvme" => hasher.crc64nvme = Some(Crc64Nvme::default()),
+        _ => {}
+    }
+}
+
+fn computed_checksum<'a>(checksum: &'a Checksum, name: &str) -> Option<&'a str> {
+    match name {
+        "x-amz-checksum-crc32" => checksum.checksum_crc32.as_deref(),
+        "x-amz-checksum-crc32c" => checksum.checksum_crc32c.as_deref(),
+        "x-amz-checksum-sha1" => checksum.checksum_sha1.as_deref(),
+        "x-amz-checksum-sha256" => checksum.checksum_sha256.as_deref(),
+        "x-amz-checksum-crc64nvme" => checksum.checksum_crc64nvme.as_deref(),
+        _ => None,
+    }
+}
+
+impl ChecksumVerifier {
+    /// Creates a verifier from the request headers.
+    ///
+    /// Returns `None` if there is nothing to verify.
+    ///
+    /// # Errors
+    /// + Returns `InvalidDigest` if `Content-MD5` is invalid.
+    /// + Returns `InvalidRequest` if any `x-amz-checksum-*` header is invalid.
+    pub fn new(headers: &HeaderMap, trailing_headers: Option<TrailingHeaders>) -> S3Result<Option<Self>> {
+        let header_str = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+
+        let mut hasher = ChecksumHasher::default();
+        let mut md5 = None;
+
+        let content_md5 = match headers.get(header::CONTENT_MD5) {
+            Some(val) => {
+                let val = val.to_str().ok().filter(|s| decoded_len(s) == Some(16));
+                let val = val.ok_or_else(|| s3_error!(InvalidDigest, "The Content-MD5 you specified was invalid."))?;
+                md5 = Some(Md5::new());
+                Some(val.to_owned())
+            }
+            None => None,
+        };
+
+        let mut checksums = Vec::new();
+        for (name, len) in CHECKSUM_HEADERS {
+            if let Some(val) = headers.get(name) {
+                let val = val
+                    .to_str()
+                    .map_err(|_| invalid_request!("Value for {name} header is invalid."))?;
+                check_checksum_value(name, len, val)?;
+                enable_hasher(&mut hasher, name);
+                checksums.push((name, val.to_owned()));
+            }
+        }
+
+        let mut trailer_names = Vec::new();
+        if trailing_headers.is_some() {
+            if let Some(val) = header_str("x-amz-trailer") {
+                for declared in val.split(',').map(str::trim) {
+                    let declared = declared.to_ascii_lowercase();
+                    if let Some(&(name, _)) = CHECKSUM_HEADERS.iter().find(|(name, _)| *name == declared) {
+                        enable_hasher(&mut hasher, name);
+                        trailer_names.push(name);
+                    }
+                }
+            }
+        }
+
+        if content_md5.is_none() && checksums.is_empty() && trailer_names.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            hasher,
+            md5,
+            content_md5,
+            checksums,
+            trailer_names,
+            trailing_headers,
+        }))
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+        if let Some(md5) = &mut self.md5 {
+            md5.update(data);
+        }
+    }
+
+    fn verify(self) -> S3Result<()> {
+        if let (Some(md5), Some(expected)) = (self.md5, self.content_md5) {
+            let computed = base64_simd::STANDARD.encode_to_string(md5.finalize());
+            if computed != expected {
+                return Err(s3_error!(BadDigest, "The Content-MD5 you specified did not match what we received."));
+            }
+        }
+
+        let mut checksums = self.checksums;
+        for name in self.trailer_names {
+            let trailing_headers = self.trailing_headers.as_ref();
+            let value = trailing_headers.and_then(|h| h.read(|h| h.get(name).and_then(|v| v.to_str().ok()).map(str::to_owned)));
+            let value = value
+                .flatten()
+                .ok_or_else(|| invalid_request!("missing trailing header: {name}"))?;
+            let len = CHECKSUM_HEADERS.iter().find(|(n, _)| *n == name).map_or(0, |&(_, len)| len);
+            check_checksum_value(name, len, &value)?;
+            checksums.push((name, value));
+        }
+
+        let computed = self.hasher.finalize();
+        for (name, expected) in &checksums {
+            if computed_checksum(&computed, name) != Some(expected.as_str()) {
+                let algorithm = name.trim_start_matches("x-amz-checksum-").to_ascii_uppercase();
+                return Err(s3_error!(
+                    BadDigest,
+                    "The {algorithm} you specified did not match the calculated checksum."
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ChecksumStream {
+    #[must_use]
+    pub fn new(body: Body, verifier: ChecksumVerifier) -> Self {
+        Self {
+            body,
+            verifier: Some(verifier),
+        }
+    }
+}
+
+impl Stream for ChecksumStream {
+    type Item = Result<Bytes, StdError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        let Some(verifier) = this.verifier.as_mut() else { return Poll::Ready(None) };
+
+        match ready!(Pin::new(&mut this.body).poll_next(cx)) {
+            Some(Ok(bytes)) => {
+                verifier.update(&bytes);
+                Poll::Ready(Some(Ok(bytes)))
+            }
+            Some(Err(err)) => {
+                this.verifier = None;
+                Poll::Ready(Some(Err(err)))
+            }
+            None => {
+                let verifier = this.verifier.take().unwrap();
+                match verifier.verify() {
+                    Ok(()) => Poll::Ready(None),
+                    Err(err) => Poll::Ready(Some(Err(Box::new(err)))),
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        Stream::size_hint(&self.body)
+    }
+}
+
+impl ByteStream for ChecksumStream {
+    fn remaining_length(&self) -> RemainingLength {
+        self.body.remaining_length()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dto::StreamingBlob;
+
+    use std::sync::{Arc, Mutex};
+
+    use futures::StreamExt;
+
+    async fn collect(mut stream: ChecksumStream) -> S3Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        while let Some(bytes) = stream.next().await {
+            let bytes = bytes.map_err(|e| *e.downcast::<S3Error>().unwrap())?;
+            buf.extend_from_slice(&bytes);
+        }
+        Ok(buf)
+    }
+
+    fn chunked_body(chunks: &[&'static str]) -> Body {
+        let chunks: Vec<Result<Bytes, std::io::Error>> = chunks.iter().map(|&s| Ok(Bytes::from_static(s.as_bytes()))).collect();
+        Body::from(StreamingBlob::wrap(futures::stream::iter(chunks)))
+    }
+
+    fn header_map(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for &(name, value) in pairs {
+            map.insert(name, value.parse().unwrap());
+        }
+        map
+    }
+
+    const CONTENT_MD5: &str = "bNNVbesNpUvKBgtMOUeYOQ=="; // md5("Hello, world!")
+    const CRC32: &str = "6+bG5g=="; // crc32("Hello, world!")
+
+    #[tokio::test]
+    async fn no_checksum() {
+        let verifier = ChecksumVerifier::new(&HeaderMap::new(), None).unwrap();
+        assert!(verifier.is_none());
+    }
+
+    #[tokio::test]
+    async fn matched() {
+        let headers = header_map(&[("content-md5", CONTENT_MD5), ("x-amz-checksum-crc32", CRC32)]);
+        let body = chunked_body(&["Hello", ", ", "world!"]);
+        let stream = ChecksumStream::new(body, ChecksumVerifier::new(&headers, None).unwrap().unwrap());
+        assert_eq!(collect(stream).await.unwrap(), b"Hello, world!");
+    }
+
+    #[tokio::test]
+    async fn mismatched() {
+        let headers = header_map(&[("content-md5", CONTENT_MD5)]);
+        let body = chunked_body(&["Hello", ", ", "world?"]);
+        let stream = ChecksumStream::new(body, ChecksumVerifier::new(&headers, None).unwrap().unwrap());
+        let err = collect(stream).await.unwrap_err();
+        assert_eq!(err.code(), &S3ErrorCode::BadDigest);
+
+        let headers = header_map(&[("x-amz-checksum-crc32", CRC32)]);
+        let body = chunked_body(&["Hello", ", ", "world?"]);
+        let stream = ChecksumStream::new(body, ChecksumVerifier::new(&headers, None).unwrap().unwrap());
+        let err = collect(stream).await.unwrap_err();
+        assert_eq!(err.code(), &S3ErrorCode::BadDigest);
+    }
+
+    #[tokio::test]
+    async fn invalid() {
+        let headers = header_map(&[("content-md5", "invalid")]);
+        let err = ChecksumVerifier::new(&headers, None).err().unwrap();
+        assert_eq!(err.code(), &S3ErrorCode::InvalidDigest);
+
+        let headers = header_map(&[("x-amz-checksum-crc32", CONTENT_MD5)]);
+        let err = ChecksumVerifier::new(&headers, None).err().unwrap();
+        assert_eq!(err.code(), &S3ErrorCode::InvalidRequest);
+    }
+
+    #[tokio::test]
+    async fn trailer() {
+        let trailing_headers = TrailingHeaders(Arc::new(Mutex::new(Some(header_map(&[("x-amz-checksum-crc32", CRC32)])))));
+        let headers = header_map(&[("x-amz-trailer", "x-amz-checksum-crc32")]);
+
+        let body = chunked_body(&["Hello", ", ", "world!"]);
+        let stream = ChecksumStream::new(
+            body,
+            ChecksumVerifier::new(&headers, Some(trailing_headers.clone()))
+                .unwrap()
+                .unwrap(),
+        );
+        assert_eq!(collect(stream).await.unwrap(), b"Hello, world!");
+
+        let body = chunked_body(&["Hello", ", ", "world?"]);
+        let stream = ChecksumStream::new(
+            body,
+            ChecksumVerifier::new(&headers, Some(trailing_headers.clone()))
+                .unwrap()
+                .unwrap(),
+        );
+        let err = collect(stream).await.unwrap_err();
+        assert_eq!(err.code(), &S3ErrorCode::BadDigest);
+
+        // the trailing headers are still available to the backend
+        assert!(trailing_headers.is_ready());
+    }
+}
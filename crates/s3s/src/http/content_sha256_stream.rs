@@ -0,0 +1,136 @@
+//! x-amz-content-sha256 verification
+
+use super::Body;
+
+use crate::crypto::Checksum as _;
+use crate::crypto::Sha256;
+use crate::error::*;
+use crate::stream::{ByteStream, RemainingLength};
+use crate::utils::crypto::hex;
+
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use bytes::Bytes;
+use bytestring::ByteString;
+use futures::Stream;
+use hyper::StatusCode;
+
+/// A stream which verifies the sha256 checksum of a single-chunk payload
+/// against the `x-amz-content-sha256` header.
+///
+/// The stream yields an `XAmzContentSHA256Mismatch` error at the end
+/// if the checksum does not match.
+pub struct ContentSha256Stream {
+    /// body
+    body: Body,
+    /// hasher, `None` if the stream has finished
+    hasher: Option<Sha256>,
+    /// expected checksum in lowercase hex
+    expected: Box<str>,
+}
+
+impl fmt::Debug for ContentSha256Stream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContentSha256Stream")
+            .field("expected", &self.expected)
+            .finish_non_exhaustive()
+    }
+}
+
+pub fn content_sha256_mismatch() -> S3Error {
+    let code = S3ErrorCode::Custom(ByteString::from_static("XAmzContentSHA256Mismatch"));
+    let mut err = S3Error::with_message(code, "The provided 'x-amz-content-sha256' header does not match what was computed.");
+    err.set_status_code(StatusCode::BAD_REQUEST);
+    err
+}
+
+impl ContentSha256Stream {
+    pub fn new(body: Body, expected: &str) -> Self {
+        Self {
+            body,
+            hasher: Some(Sha256::new()),
+            expected: expected.into(),
+        }
+    }
+}
+
+impl Stream for ContentSha256Stream {
+    type Item = Result<Bytes, StdError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        let Some(hasher) = this.hasher.as_mut() else { return Poll::Ready(None) };
+
+        match ready!(Pin::new(&mut this.body).poll_next(cx)) {
+            Some(Ok(bytes)) => {
+                hasher.update(&bytes);
+                Poll::Ready(Some(Ok(bytes)))
+            }
+            Some(Err(err)) => {
+                this.hasher = None;
+                Poll::Ready(Some(Err(err)))
+            }
+            None => {
+                let hasher = this.hasher.take().unwrap();
+                let checksum = hex(hasher.finalize());
+                if checksum != *this.expected {
+                    return Poll::Ready(Some(Err(Box::new(content_sha256_mismatch()))));
+                }
+                Poll::Ready(None)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        Stream::size_hint(&self.body)
+    }
+}
+
+impl ByteStream for ContentSha256Stream {
+    fn remaining_length(&self) -> RemainingLength {
+        self.body.remaining_length()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dto::StreamingBlob;
+
+    use futures::StreamExt;
+
+    async fn collect(mut stream: ContentSha256Stream) -> Result<Vec<u8>, StdError> {
+        let mut buf = Vec::new();
+        while let Some(bytes) = stream.next().await {
+            buf.extend_from_slice(&bytes?);
+        }
+        Ok(buf)
+    }
+
+    fn chunked_body(chunks: &[&'static str]) -> Body {
+        let chunks: Vec<Result<Bytes, std::io::Error>> = chunks.iter().map(|&s| Ok(Bytes::from_static(s.as_bytes()))).collect();
+        Body::from(StreamingBlob::wrap(futures::stream::iter(chunks)))
+    }
+
+    #[tokio::test]
+    async fn matched() {
+        let expected = "315f5bdb76d078c43b8ac0064e4a0164612b1fce77c869345bfc94c75894edd3"; // sha256("Hello, world!")
+        let stream = ContentSha256Stream::new(chunked_body(&["Hello", ", ", "world!"]), expected);
+        let bytes = collect(stream).await.unwrap();
+        assert_eq!(bytes, b"Hello, world!");
+    }
+
+    #[tokio::test]
+    async fn mismatched() {
+        let expected = "315f5bdb76d078c43b8ac0064e4a0164612b1fce77c869345bfc94c75894edd3";
+        let stream = ContentSha256Stream::new(chunked_body(&["Hello", ", ", "world?"]), expected);
+        let err = collect(stream).await.unwrap_err();
+        let err = err.downcast::<S3Error>().unwrap();
+        assert_eq!(err.code().as_str(), "XAmzContentSHA256Mismatch");
+        assert_eq!(err.status_code(), Some(StatusCode::BAD_REQUEST));
+    }
+}
@@ -0,0 +1,94 @@
+use super::Body;
+
+use crate::error::StdError;
+
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use http_body::Frame;
+
+type OnDrop = Box<dyn FnOnce(u64) + Send + Sync + 'static>;
+
+pin_project_lite::pin_project! {
+    /// A body which counts the bytes passing through it.
+    pub struct MeteredBody {
+        #[pin]
+        inner: Body,
+        bytes: u64,
+        counter: Option<Arc<AtomicU64>>,
+        on_drop: Option<OnDrop>,
+    }
+
+    impl PinnedDrop for MeteredBody {
+        fn drop(this: Pin<&mut Self>) {
+            let this = this.project();
+            if let Some(f) = this.on_drop.take() {
+                f(*this.bytes);
+            }
+        }
+    }
+}
+
+impl MeteredBody {
+    /// Adds the bytes to a shared counter as they pass through.
+    pub fn with_counter(inner: Body, counter: Arc<AtomicU64>) -> Self {
+        Self {
+            inner,
+            bytes: 0,
+            counter: Some(counter),
+            on_drop: None,
+        }
+    }
+
+    /// Calls `f` with the total bytes when the body is finished or dropped.
+    pub fn on_drop(inner: Body, f: impl FnOnce(u64) + Send + Sync + 'static) -> Self {
+        Self {
+            inner,
+            bytes: 0,
+            counter: None,
+            on_drop: Some(Box::new(f)),
+        }
+    }
+}
+
+impl http_body::Body for MeteredBody {
+    type Data = Bytes;
+
+    type Error = StdError;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        let poll = http_body::Body::poll_frame(this.inner, cx);
+        if let Poll::Ready(Some(Ok(frame))) = &poll {
+            if let Some(data) = frame.data_ref() {
+                let len = data.len() as u64;
+                *this.bytes += len;
+                if let Some(counter) = this.counter {
+                    counter.fetch_add(len, Ordering::Relaxed);
+                }
+            }
+        }
+        poll
+    }
+
+    fn is_end_stream(&self) -> bool {
+        http_body::Body::is_end_stream(&self.inner)
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        http_body::Body::size_hint(&self.inner)
+    }
+}
+
+impl fmt::Debug for MeteredBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MeteredBody")
+            .field("inner", &self.inner)
+            .field("bytes", &self.bytes)
+            .finish_non_exhaustive()
+    }
+}
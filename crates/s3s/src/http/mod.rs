@@ -16,12 +16,21 @@ pub use self::aws_chunked_stream::*;
 mod multipart;
 pub use self::multipart::*;
 
+mod content_sha256_stream;
+pub use self::content_sha256_stream::*;
+
+mod checksum_stream;
+pub use self::checksum_stream::*;
+
 mod body;
 pub use self::body::*;
 
 mod keep_alive_body;
 pub use self::keep_alive_body::KeepAliveBody;
 
+mod metered_body;
+pub use self::metered_body::MeteredBody;
+
 mod etag;
 
 mod request;
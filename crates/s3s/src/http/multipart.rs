@@ -36,6 +36,12 @@ pub struct Multipart {
     fields: Vec<(String, String)>,
     /// file
     pub file: File,
+    /// file size, if the whole file content has been buffered
+    file_size: Option<u64>,
+    /// number of bytes before the file content
+    file_offset: u64,
+    /// length of the close delimiter `\r\n--{boundary}--\r\n`
+    close_delimiter_len: u64,
 }
 
 impl Multipart {
@@ -47,6 +53,28 @@ impl Multipart {
         self.file.stream.take()
     }
 
+    /// Returns the file size if it is known in advance.
+    ///
+    /// The size is known if the delimiter after the file has been buffered while parsing the form,
+    /// or if it has been derived from the content length of the form by [`Multipart::set_content_length`].
+    /// Otherwise, it is `None` and the size is only known after the file stream ends.
+    #[must_use]
+    pub fn file_size(&self) -> Option<u64> {
+        self.file_size
+    }
+
+    /// Derives the file size from the content length of the whole form, if it is not known yet.
+    ///
+    /// The file must be the last field of the form, followed by the close delimiter `\r\n--{boundary}--\r\n`.
+    /// Otherwise, the file stream fails when its actual size does not match.
+    pub fn set_content_length(&mut self, content_length: u64) {
+        if self.file_size.is_none() {
+            self.file_size = content_length
+                .checked_sub(self.file_offset)
+                .and_then(|n| n.checked_sub(self.close_delimiter_len));
+        }
+    }
+
     /// Finds field value
     #[must_use]
     pub fn find_field_value<'a>(&'a self, name: &str) -> Option<&'a str> {
@@ -199,6 +227,9 @@ where
                     Some(Err(_)) => return Ok(Err(MultipartError::InvalidFormat)),
                     Some(Ok(s)) => Some(s),
                 };
+                let file_size = find_delimiter(lines.slice, boundary).and_then(|idx| u64::try_from(idx).ok());
+                let file_offset = buf.len().saturating_sub(lines.slice.len()) as u64;
+                let close_delimiter_len = boundary.len().saturating_add(8) as u64;
                 let remaining_bytes = if lines.slice.is_empty() {
                     None
                 } else {
@@ -217,12 +248,29 @@ where
                 }
                 fields.sort_by(|lhs, rhs| lhs.0.as_str().cmp(rhs.0.as_str()));
 
-                return Ok(Ok(Multipart { fields, file }));
+                return Ok(Ok(Multipart {
+                    fields,
+                    file,
+                    file_size,
+                    file_offset,
+                    close_delimiter_len,
+                }));
             }
         }
     }
 }
 
+/// Finds the delimiter `\r\n--{boundary}` which ends the file content
+fn find_delimiter(buf: &[u8], boundary: &[u8]) -> Option<usize> {
+    memchr_iter(b'\r', buf).find(|&idx| {
+        #[allow(clippy::indexing_slicing)]
+        let remaining = &buf[idx..];
+        remaining
+            .strip_prefix(b"\r\n--")
+            .is_some_and(|rest| rest.starts_with(boundary))
+    })
+}
+
 /// File stream error
 #[derive(Debug, thiserror::Error)]
 pub enum FileStreamError {
@@ -627,6 +675,9 @@ mod tests {
 
         let ans = transform_multipart(body_stream, boundary.as_bytes()).await.unwrap();
 
+        // the file is not buffered while parsing the form
+        assert_eq!(ans.file_size(), None);
+
         let fields = [
             ("x-amz-signature", "a71d6dfaaa5aa018dc8e3945f2cec30ea1939ff7ed2f2dd65a6d49320c8fa1e6"),
             ("bucket", "mc-test-bucket-32569"),
@@ -3,10 +3,14 @@ use super::Multipart;
 use super::OrderedQs;
 
 use crate::HttpRequest;
-use crate::auth::Credentials;
+use crate::S3ErrorCode;
+use crate::access_log::AccessLogRecord;
+use crate::auth::{Credentials, Identity};
+use crate::ops::PostObjectStream;
 use crate::path::S3Path;
 use crate::protocol::TrailingHeaders;
-use crate::stream::VecByteStream;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::throttle::ThrottlePermit;
 
 use hyper::HeaderMap;
 use hyper::Method;
@@ -29,12 +33,27 @@ pub(crate) struct S3Extensions {
     pub qs: Option<OrderedQs>,
 
     pub multipart: Option<Multipart>,
-    pub vec_stream: Option<VecByteStream>,
+    pub file_stream: Option<PostObjectStream>,
 
     pub credentials: Option<Credentials>,
+    pub identity: Option<Identity>,
     pub region: Option<String>,
     pub service: Option<String>,
     pub trailing_headers: Option<TrailingHeaders>,
+
+    /// The resolved operation name
+    pub op: Option<&'static str>,
+    /// Whether the request has valid credentials
+    pub authenticated: bool,
+    /// The error code of a failed request
+    pub error_code: Option<S3ErrorCode>,
+    /// The access log record, which is filled when the request is handled
+    pub access_log: Option<Box<AccessLogRecord>>,
+    /// The permit of an in-flight request, which is released after the response body
+    #[cfg(not(target_arch = "wasm32"))]
+    pub throttle_permit: Option<Box<ThrottlePermit>>,
+    /// The CORS headers of a cross-origin request, which are added to the response
+    pub cors_headers: Option<Box<HeaderMap>>,
 }
 
 impl From<HttpRequest> for Request {
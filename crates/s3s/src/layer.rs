@@ -0,0 +1,273 @@
+//! Interceptors around S3 operations
+//!
+//! Layers are called after the operation is resolved and [`S3Access::check`](crate::access::S3Access::check) has passed.
+//! The per-operation access hooks, such as [`S3Access::put_object`](crate::access::S3Access::put_object),
+//! are called inside the operation, so they run after all layers.
+//! Each layer can inspect or modify the request, call the next layer, and inspect or modify the result.
+//! A layer can also return early without calling the next layer, such as for quotas.
+//!
+//! Custom routes are not intercepted.
+
+use crate::S3Operation;
+use crate::auth::{Credentials, Identity};
+use crate::error::S3Result;
+use crate::http::{Body, Request, Response};
+use crate::ops::{CallContext, Operation};
+use crate::path::S3Path;
+use crate::protocol::S3Response;
+
+use hyper::HeaderMap;
+use hyper::Method;
+use hyper::Uri;
+use hyper::http::Extensions;
+
+/// An interceptor around S3 operations
+///
+/// Layers are registered by [`S3ServiceBuilder::layer`](crate::service::S3ServiceBuilder::layer).
+/// The first registered layer is the outermost one.
+#[async_trait::async_trait]
+pub trait S3Layer: Send + Sync + 'static {
+    /// Handles a request, usually by calling `next.run(req)`.
+    async fn call(&self, req: S3LayerRequest<'_>, next: Next<'_>) -> S3Result<S3Response<Body>>;
+}
+
+/// A request seen by layers
+///
+/// The request is consumed by [`Next::run`].
+pub struct S3LayerRequest<'a> {
+    req: &'a mut Request,
+    s3_op: S3Operation,
+    s3_path: S3Path,
+}
+
+impl S3LayerRequest<'_> {
+    /// Returns the S3 operation of current request.
+    #[must_use]
+    pub fn s3_op(&self) -> &S3Operation {
+        &self.s3_op
+    }
+
+    /// Returns the S3 path of current request.
+    #[must_use]
+    pub fn s3_path(&self) -> &S3Path {
+        &self.s3_path
+    }
+
+    /// Returns the credentials of current request.
+    ///
+    /// `None` means anonymous request.
+    #[must_use]
+    pub fn credentials(&self) -> Option<&Credentials> {
+        self.req.s3ext.credentials.as_ref()
+    }
+
+    /// Returns the identity of current request, which is returned by the auth provider.
+    #[must_use]
+    pub fn identity(&self) -> Option<&Identity> {
+        self.req.s3ext.identity.as_ref()
+    }
+
+    #[must_use]
+    pub fn method(&self) -> &Method {
+        &self.req.method
+    }
+
+    #[must_use]
+    pub fn uri(&self) -> &Uri {
+        &self.req.uri
+    }
+
+    #[must_use]
+    pub fn headers(&self) -> &HeaderMap {
+        &self.req.headers
+    }
+
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.req.headers
+    }
+
+    #[must_use]
+    pub fn extensions(&self) -> &Extensions {
+        &self.req.extensions
+    }
+
+    /// Returns the extensions of current request.
+    ///
+    /// It is used to pass custom data between layers and the S3 implementation.
+    pub fn extensions_mut(&mut self) -> &mut Extensions {
+        &mut self.req.extensions
+    }
+}
+
+/// The remaining layers and the operation
+pub struct Next<'a> {
+    layers: &'a [Box<dyn S3Layer>],
+    ccx: &'a CallContext<'a>,
+    op: &'static dyn Operation,
+}
+
+impl Next<'_> {
+    /// Calls the remaining layers and the operation.
+    ///
+    /// # Errors
+    /// Returns an error if a layer or the operation fails.
+    pub async fn run(self, req: S3LayerRequest<'_>) -> S3Result<S3Response<Body>> {
+        match self.layers.split_first() {
+            Some((layer, layers)) => layer.call(req, Next { layers, ..self }).await,
+            None => {
+                let S3LayerRequest { req, s3_path, .. } = req;
+                req.s3ext.s3_path = Some(s3_path);
+                let resp = self.op.call(self.ccx, req).await?;
+                Ok(S3Response {
+                    output: resp.body,
+                    status: Some(resp.status),
+                    headers: resp.headers,
+                    extensions: resp.extensions,
+                })
+            }
+        }
+    }
+}
+
+/// Calls the operation through all layers.
+pub(crate) async fn call(ccx: &CallContext<'_>, op: &'static dyn Operation, req: &mut Request) -> S3Result<Response> {
+    let Some(s3_path) = req.s3ext.s3_path.take() else { return op.call(ccx, req).await };
+    let s3_op = S3Operation { name: op.name() };
+    let req = S3LayerRequest { req, s3_op, s3_path };
+    let next = Next {
+        layers: ccx.layers,
+        ccx,
+        op,
+    };
+    let resp = next.run(req).await?;
+    Ok(Response {
+        status: resp.status.unwrap_or_default(),
+        headers: resp.headers,
+        body: resp.output,
+        extensions: resp.extensions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::auth::SimpleAuth;
+    use crate::service::{S3Service, S3ServiceBuilder};
+    use crate::signer::{SigV4Signer, SignPayload};
+    use crate::tests::{ACCESS_KEY, MockS3, SECRET_KEY, credentials, error_code};
+
+    use std::sync::{Arc, Mutex};
+    use std::time::SystemTime;
+
+    use hyper::StatusCode;
+
+    type Log = Arc<Mutex<Vec<String>>>;
+
+    struct Recorder {
+        name: &'static str,
+        log: Log,
+    }
+
+    #[async_trait::async_trait]
+    impl S3Layer for Recorder {
+        async fn call(&self, req: S3LayerRequest<'_>, next: Next<'_>) -> S3Result<S3Response<Body>> {
+            let access_key = req.credentials().map_or("-", |c| c.access_key.as_str());
+            let line = format!("{} before {} {:?} {access_key}", self.name, req.s3_op().name(), req.s3_path());
+            self.log.lock().unwrap().push(line);
+
+            let result = next.run(req).await;
+
+            let line = match &result {
+                Ok(resp) => format!("{} after {:?}", self.name, resp.status),
+                Err(err) => format!("{} after {}", self.name, err.code().as_str()),
+            };
+            self.log.lock().unwrap().push(line);
+
+            let mut resp = result?;
+            resp.headers.insert("x-layer", self.name.parse().unwrap());
+            Ok(resp)
+        }
+    }
+
+    /// Rejects all `DeleteObject` requests.
+    struct Quota;
+
+    #[async_trait::async_trait]
+    impl S3Layer for Quota {
+        async fn call(&self, req: S3LayerRequest<'_>, next: Next<'_>) -> S3Result<S3Response<Body>> {
+            if req.s3_op().name() == "DeleteObject" {
+                return Err(s3_error!(SlowDown));
+            }
+            next.run(req).await
+        }
+    }
+
+    fn service(log: &Log) -> S3Service {
+        let mut builder = S3ServiceBuilder::new(MockS3);
+        builder.set_auth(SimpleAuth::from_single(ACCESS_KEY, SECRET_KEY));
+        builder.layer(Recorder {
+            name: "a",
+            log: log.clone(),
+        });
+        builder.layer(Quota);
+        builder.layer(Recorder {
+            name: "b",
+            log: log.clone(),
+        });
+        builder.build()
+    }
+
+    fn request(method: &str, uri: &str) -> http::Request<Body> {
+        let mut req = http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("host", "localhost")
+            .body(Body::empty())
+            .unwrap();
+        let signer = SigV4Signer::new(credentials(), "us-east-1");
+        signer.sign(&mut req, SignPayload::Empty, SystemTime::now()).unwrap();
+        req
+    }
+
+    #[tokio::test]
+    async fn layers() {
+        let log = Log::default();
+        let service = service(&log);
+
+        let resp = service.call(request("GET", "http://localhost/")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get("x-layer").unwrap(), "a");
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                format!("a before ListBuckets Root {ACCESS_KEY}"),
+                format!("b before ListBuckets Root {ACCESS_KEY}"),
+                "b after Some(200)".to_owned(),
+                "a after Some(200)".to_owned(),
+            ]
+        );
+        log.lock().unwrap().clear();
+
+        let code = error_code(&service, request("GET", "http://localhost/bucket/key")).await;
+        assert_eq!(code, "NotImplemented");
+        assert_eq!(log.lock().unwrap().last().unwrap(), "a after NotImplemented");
+        log.lock().unwrap().clear();
+
+        let code = error_code(&service, request("DELETE", "http://localhost/bucket/key")).await;
+        assert_eq!(code, "SlowDown");
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                format!(r#"a before DeleteObject Object {{ bucket: "bucket", key: "key" }} {ACCESS_KEY}"#),
+                "a after SlowDown".to_owned(),
+            ]
+        );
+        log.lock().unwrap().clear();
+
+        let req = http::Request::builder().uri("http://localhost/").body(Body::empty()).unwrap();
+        let code = error_code(&service, req).await;
+        assert_eq!(code, "AccessDenied");
+        assert!(log.lock().unwrap().is_empty());
+    }
+}
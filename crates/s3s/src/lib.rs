@@ -18,6 +18,7 @@ mod error;
 
 mod http;
 mod ops;
+mod post_policy;
 mod protocol;
 mod s3_op;
 mod s3_trait;
@@ -26,19 +27,33 @@ mod sig_v4;
 mod time;
 
 pub mod access;
+pub mod access_log;
 pub mod auth;
 pub mod checksum;
+pub mod connection;
+pub mod cors;
 pub mod crypto;
 pub mod dto;
 pub mod header;
 pub mod host;
+pub mod layer;
+pub mod metrics;
 pub mod path;
+pub mod request_id;
 pub mod route;
 pub mod service;
+pub mod signer;
 pub mod stream;
+pub mod sts;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod throttle;
 pub mod validation;
+pub mod website;
 pub mod xml;
 
+#[cfg(test)]
+mod tests;
+
 pub use self::error::*;
 pub use self::http::Body;
 pub use self::s3_op::S3Operation;
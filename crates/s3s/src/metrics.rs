@@ -0,0 +1,183 @@
+//! Metrics of S3 operations
+//!
+//! Metrics are recorded by [`S3Metrics`], which is registered by
+//! [`S3ServiceBuilder::set_metrics`](crate::service::S3ServiceBuilder::set_metrics).
+//! It can be used to export request counts, error counts, latency histograms and body sizes
+//! to any metrics system.
+
+use crate::S3ErrorCode;
+use crate::http::{Body, MeteredBody, Request, Response};
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use hyper::StatusCode;
+
+pub trait S3Metrics: Send + Sync + 'static {
+    /// Records a handled request.
+    ///
+    /// It is called when the response is ready, before the response body is sent.
+    /// If the error response cannot be built, it is called with `500 Internal Server Error`.
+    fn record_request(&self, m: &RequestMetrics<'_>);
+
+    /// Records the size of a response body.
+    ///
+    /// It is called when the response body is finished or dropped.
+    fn record_response_body(&self, labels: &MetricsLabels, bytes: u64) {
+        let _ = (labels, bytes);
+    }
+}
+
+/// The labels of a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetricsLabels {
+    /// The operation name, such as `GetObject`.
+    ///
+    /// `None` means the operation is not resolved, such as for invalid requests and custom routes.
+    pub op: Option<&'static str>,
+
+    /// Whether the request is not authenticated
+    pub anonymous: bool,
+}
+
+/// The metrics of a handled request
+#[derive(Debug)]
+pub struct RequestMetrics<'a> {
+    pub labels: &'a MetricsLabels,
+
+    pub status: StatusCode,
+
+    /// The error code of a failed request
+    pub error_code: Option<&'a S3ErrorCode>,
+
+    /// The time from receiving the request to the response being ready, which is always zero on wasm
+    pub duration: Duration,
+
+    /// The bytes of the request body read by the service when the response is ready
+    pub request_body_bytes: u64,
+}
+
+/// Counts the bytes of the request body.
+pub(crate) fn count_request_body(req: &mut Request) -> Arc<AtomicU64> {
+    let counter = Arc::new(AtomicU64::new(0));
+    let body = std::mem::take(&mut req.body);
+    req.body = Body::http_body(MeteredBody::with_counter(body, Arc::clone(&counter)));
+    counter
+}
+
+/// Records the metrics of a request, returning the labels for the response body.
+pub(crate) fn record(
+    metrics: &dyn S3Metrics,
+    req: &Request,
+    resp: &Response,
+    duration: Duration,
+    request_body: &AtomicU64,
+) -> MetricsLabels {
+    let labels = MetricsLabels {
+        op: req.s3ext.op,
+        anonymous: !req.s3ext.authenticated,
+    };
+    metrics.record_request(&RequestMetrics {
+        labels: &labels,
+        status: resp.status,
+        error_code: req.s3ext.error_code.as_ref(),
+        duration,
+        request_body_bytes: request_body.load(Ordering::Relaxed),
+    });
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dto::{ListBucketsInput, ListBucketsOutput, PutObjectInput, PutObjectOutput};
+    use crate::service::S3ServiceBuilder;
+    use crate::{S3, S3Request, S3Response, S3Result};
+
+    use std::sync::Mutex;
+
+    use futures::StreamExt;
+    use stdx::default::default;
+
+    struct MetricsS3;
+
+    #[async_trait::async_trait]
+    impl S3 for MetricsS3 {
+        async fn list_buckets(&self, _req: S3Request<ListBucketsInput>) -> S3Result<S3Response<ListBucketsOutput>> {
+            Ok(S3Response::new(ListBucketsOutput::default()))
+        }
+
+        async fn put_object(&self, req: S3Request<PutObjectInput>) -> S3Result<S3Response<PutObjectOutput>> {
+            let mut body = req.input.body.unwrap();
+            let mut len = 0;
+            while let Some(bytes) = body.next().await {
+                len += bytes.unwrap().len();
+            }
+            assert_eq!(len, 5);
+            Err(s3_error!(NoSuchBucket))
+        }
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        log: Mutex<Vec<String>>,
+    }
+
+    impl S3Metrics for Arc<Recorder> {
+        fn record_request(&self, m: &RequestMetrics<'_>) {
+            let line = format!(
+                "{:?} {} {} {:?} {}",
+                m.labels.op,
+                m.labels.anonymous,
+                m.status.as_u16(),
+                m.error_code.map(S3ErrorCode::as_str),
+                m.request_body_bytes
+            );
+            self.log.lock().unwrap().push(line);
+        }
+
+        fn record_response_body(&self, labels: &MetricsLabels, bytes: u64) {
+            let line = format!("{:?} body {}", labels.op, bytes > 0);
+            self.log.lock().unwrap().push(line);
+        }
+    }
+
+    #[tokio::test]
+    async fn metrics() {
+        let recorder = Arc::new(Recorder::default());
+        let mut builder = S3ServiceBuilder::new(MetricsS3);
+        builder.set_metrics(Arc::clone(&recorder));
+        let service = builder.build();
+
+        let req = http::Request::builder().uri("http://localhost/").body(default()).unwrap();
+        let mut resp = service.call(req).await.unwrap();
+        resp.body_mut().store_all_unlimited().await.unwrap();
+        drop(resp);
+
+        let req = http::Request::builder()
+            .method("PUT")
+            .uri("http://localhost/bucket/key")
+            .header("content-length", "5")
+            .body(Body::from(String::from("hello")))
+            .unwrap();
+        let resp = service.call(req).await.unwrap();
+        drop(resp);
+
+        let req = http::Request::builder().uri("http://localhost/%ff").body(default()).unwrap();
+        drop(service.call(req).await.unwrap());
+
+        assert_eq!(
+            *recorder.log.lock().unwrap(),
+            [
+                r#"Some("ListBuckets") true 200 None 0"#,
+                r#"Some("ListBuckets") body true"#,
+                r#"Some("PutObject") true 404 Some("NoSuchBucket") 5"#,
+                r#"Some("PutObject") body false"#,
+                r#"None true 400 Some("InvalidURI") 0"#,
+                r"None body false",
+            ]
+        );
+    }
+}
@@ -517,11 +517,7 @@ impl super::Operation for AbortMultipartUpload {
         if let Some(access) = ccx.access {
             access.abort_multipart_upload(&mut s3_req).await?;
         }
-        let result = s3.abort_multipart_upload(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.abort_multipart_upload(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -605,11 +601,7 @@ impl super::Operation for CompleteMultipartUpload {
         if let Some(access) = ccx.access {
             access.complete_multipart_upload(&mut s3_req).await?;
         }
-        let result = s3.complete_multipart_upload(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.complete_multipart_upload(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -791,11 +783,7 @@ impl super::Operation for CopyObject {
         if let Some(access) = ccx.access {
             access.copy_object(&mut s3_req).await?;
         }
-        let result = s3.copy_object(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.copy_object(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -862,11 +850,7 @@ impl super::Operation for CreateBucket {
         if let Some(access) = ccx.access {
             access.create_bucket(&mut s3_req).await?;
         }
-        let result = s3.create_bucket(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.create_bucket(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -915,11 +899,7 @@ impl super::Operation for CreateBucketMetadataTableConfiguration {
         if let Some(access) = ccx.access {
             access.create_bucket_metadata_table_configuration(&mut s3_req).await?;
         }
-        let result = s3.create_bucket_metadata_table_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.create_bucket_metadata_table_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1064,11 +1044,7 @@ impl super::Operation for CreateMultipartUpload {
         if let Some(access) = ccx.access {
             access.create_multipart_upload(&mut s3_req).await?;
         }
-        let result = s3.create_multipart_upload(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.create_multipart_upload(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1108,11 +1084,7 @@ impl super::Operation for DeleteBucket {
         if let Some(access) = ccx.access {
             access.delete_bucket(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1155,11 +1127,7 @@ impl super::Operation for DeleteBucketAnalyticsConfiguration {
         if let Some(access) = ccx.access {
             access.delete_bucket_analytics_configuration(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_analytics_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_analytics_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1199,11 +1167,7 @@ impl super::Operation for DeleteBucketCors {
         if let Some(access) = ccx.access {
             access.delete_bucket_cors(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_cors(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_cors(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1243,11 +1207,7 @@ impl super::Operation for DeleteBucketEncryption {
         if let Some(access) = ccx.access {
             access.delete_bucket_encryption(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_encryption(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_encryption(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1284,11 +1244,7 @@ impl super::Operation for DeleteBucketIntelligentTieringConfiguration {
         if let Some(access) = ccx.access {
             access.delete_bucket_intelligent_tiering_configuration(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_intelligent_tiering_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_intelligent_tiering_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1331,11 +1287,7 @@ impl super::Operation for DeleteBucketInventoryConfiguration {
         if let Some(access) = ccx.access {
             access.delete_bucket_inventory_configuration(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_inventory_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_inventory_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1375,11 +1327,7 @@ impl super::Operation for DeleteBucketLifecycle {
         if let Some(access) = ccx.access {
             access.delete_bucket_lifecycle(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_lifecycle(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_lifecycle(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1419,11 +1367,7 @@ impl super::Operation for DeleteBucketMetadataTableConfiguration {
         if let Some(access) = ccx.access {
             access.delete_bucket_metadata_table_configuration(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_metadata_table_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_metadata_table_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1466,11 +1410,7 @@ impl super::Operation for DeleteBucketMetricsConfiguration {
         if let Some(access) = ccx.access {
             access.delete_bucket_metrics_configuration(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_metrics_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_metrics_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1510,11 +1450,7 @@ impl super::Operation for DeleteBucketOwnershipControls {
         if let Some(access) = ccx.access {
             access.delete_bucket_ownership_controls(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_ownership_controls(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_ownership_controls(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1554,11 +1490,7 @@ impl super::Operation for DeleteBucketPolicy {
         if let Some(access) = ccx.access {
             access.delete_bucket_policy(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_policy(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_policy(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1598,11 +1530,7 @@ impl super::Operation for DeleteBucketReplication {
         if let Some(access) = ccx.access {
             access.delete_bucket_replication(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_replication(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_replication(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1642,11 +1570,7 @@ impl super::Operation for DeleteBucketTagging {
         if let Some(access) = ccx.access {
             access.delete_bucket_tagging(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_tagging(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_tagging(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1686,11 +1610,7 @@ impl super::Operation for DeleteBucketWebsite {
         if let Some(access) = ccx.access {
             access.delete_bucket_website(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_website(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_website(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1758,11 +1678,7 @@ impl super::Operation for DeleteObject {
         if let Some(access) = ccx.access {
             access.delete_object(&mut s3_req).await?;
         }
-        let result = s3.delete_object(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_object(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1808,11 +1724,7 @@ impl super::Operation for DeleteObjectTagging {
         if let Some(access) = ccx.access {
             access.delete_object_tagging(&mut s3_req).await?;
         }
-        let result = s3.delete_object_tagging(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_object_tagging(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1871,11 +1783,7 @@ impl super::Operation for DeleteObjects {
         if let Some(access) = ccx.access {
             access.delete_objects(&mut s3_req).await?;
         }
-        let result = s3.delete_objects(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_objects(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1915,11 +1823,7 @@ impl super::Operation for DeletePublicAccessBlock {
         if let Some(access) = ccx.access {
             access.delete_public_access_block(&mut s3_req).await?;
         }
-        let result = s3.delete_public_access_block(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_public_access_block(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1965,11 +1869,7 @@ impl super::Operation for GetBucketAccelerateConfiguration {
         if let Some(access) = ccx.access {
             access.get_bucket_accelerate_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_accelerate_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_accelerate_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2011,11 +1911,7 @@ impl super::Operation for GetBucketAcl {
         if let Some(access) = ccx.access {
             access.get_bucket_acl(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_acl(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_acl(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2062,11 +1958,7 @@ impl super::Operation for GetBucketAnalyticsConfiguration {
         if let Some(access) = ccx.access {
             access.get_bucket_analytics_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_analytics_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_analytics_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2108,11 +2000,7 @@ impl super::Operation for GetBucketCors {
         if let Some(access) = ccx.access {
             access.get_bucket_cors(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_cors(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_cors(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2156,11 +2044,7 @@ impl super::Operation for GetBucketEncryption {
         if let Some(access) = ccx.access {
             access.get_bucket_encryption(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_encryption(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_encryption(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2201,11 +2085,7 @@ impl super::Operation for GetBucketIntelligentTieringConfiguration {
         if let Some(access) = ccx.access {
             access.get_bucket_intelligent_tiering_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_intelligent_tiering_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_intelligent_tiering_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2252,11 +2132,7 @@ impl super::Operation for GetBucketInventoryConfiguration {
         if let Some(access) = ccx.access {
             access.get_bucket_inventory_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_inventory_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_inventory_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2303,11 +2179,7 @@ impl super::Operation for GetBucketLifecycleConfiguration {
         if let Some(access) = ccx.access {
             access.get_bucket_lifecycle_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_lifecycle_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_lifecycle_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2349,11 +2221,7 @@ impl super::Operation for GetBucketLocation {
         if let Some(access) = ccx.access {
             access.get_bucket_location(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_location(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_location(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2395,11 +2263,7 @@ impl super::Operation for GetBucketLogging {
         if let Some(access) = ccx.access {
             access.get_bucket_logging(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_logging(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_logging(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2443,11 +2307,7 @@ impl super::Operation for GetBucketMetadataTableConfiguration {
         if let Some(access) = ccx.access {
             access.get_bucket_metadata_table_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_metadata_table_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_metadata_table_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2494,11 +2354,7 @@ impl super::Operation for GetBucketMetricsConfiguration {
         if let Some(access) = ccx.access {
             access.get_bucket_metrics_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_metrics_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_metrics_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2540,11 +2396,7 @@ impl super::Operation for GetBucketNotificationConfiguration {
         if let Some(access) = ccx.access {
             access.get_bucket_notification_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_notification_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_notification_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2588,11 +2440,7 @@ impl super::Operation for GetBucketOwnershipControls {
         if let Some(access) = ccx.access {
             access.get_bucket_ownership_controls(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_ownership_controls(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_ownership_controls(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2636,11 +2484,7 @@ impl super::Operation for GetBucketPolicy {
         if let Some(access) = ccx.access {
             access.get_bucket_policy(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_policy(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_policy(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2684,11 +2528,7 @@ impl super::Operation for GetBucketPolicyStatus {
         if let Some(access) = ccx.access {
             access.get_bucket_policy_status(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_policy_status(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_policy_status(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2732,11 +2572,7 @@ impl super::Operation for GetBucketReplication {
         if let Some(access) = ccx.access {
             access.get_bucket_replication(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_replication(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_replication(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2778,11 +2614,7 @@ impl super::Operation for GetBucketRequestPayment {
         if let Some(access) = ccx.access {
             access.get_bucket_request_payment(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_request_payment(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_request_payment(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2824,11 +2656,7 @@ impl super::Operation for GetBucketTagging {
         if let Some(access) = ccx.access {
             access.get_bucket_tagging(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_tagging(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_tagging(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2870,11 +2698,7 @@ impl super::Operation for GetBucketVersioning {
         if let Some(access) = ccx.access {
             access.get_bucket_versioning(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_versioning(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_versioning(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2916,11 +2740,7 @@ impl super::Operation for GetBucketWebsite {
         if let Some(access) = ccx.access {
             access.get_bucket_website(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_website(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_website(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3071,11 +2891,7 @@ impl super::Operation for GetObject {
             access.get_object(&mut s3_req).await?;
         }
         let overridden_headers = super::get_object::extract_overridden_response_headers(&s3_req)?;
-        let result = s3.get_object(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_object(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(overridden_headers);
         super::get_object::merge_custom_headers(&mut resp, s3_resp.headers);
@@ -3126,11 +2942,7 @@ impl super::Operation for GetObjectAcl {
         if let Some(access) = ccx.access {
             access.get_object_acl(&mut s3_req).await?;
         }
-        let result = s3.get_object_acl(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_object_acl(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3203,11 +3015,7 @@ impl super::Operation for GetObjectAttributes {
         if let Some(access) = ccx.access {
             access.get_object_attributes(&mut s3_req).await?;
         }
-        let result = s3.get_object_attributes(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_object_attributes(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3258,11 +3066,7 @@ impl super::Operation for GetObjectLegalHold {
         if let Some(access) = ccx.access {
             access.get_object_legal_hold(&mut s3_req).await?;
         }
-        let result = s3.get_object_legal_hold(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_object_legal_hold(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3306,11 +3110,7 @@ impl super::Operation for GetObjectLockConfiguration {
         if let Some(access) = ccx.access {
             access.get_object_lock_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_object_lock_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_object_lock_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3361,11 +3161,7 @@ impl super::Operation for GetObjectRetention {
         if let Some(access) = ccx.access {
             access.get_object_retention(&mut s3_req).await?;
         }
-        let result = s3.get_object_retention(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_object_retention(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3415,11 +3211,7 @@ impl super::Operation for GetObjectTagging {
         if let Some(access) = ccx.access {
             access.get_object_tagging(&mut s3_req).await?;
         }
-        let result = s3.get_object_tagging(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_object_tagging(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3468,11 +3260,7 @@ impl super::Operation for GetObjectTorrent {
         if let Some(access) = ccx.access {
             access.get_object_torrent(&mut s3_req).await?;
         }
-        let result = s3.get_object_torrent(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_object_torrent(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3516,11 +3304,7 @@ impl super::Operation for GetPublicAccessBlock {
         if let Some(access) = ccx.access {
             access.get_public_access_block(&mut s3_req).await?;
         }
-        let result = s3.get_public_access_block(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_public_access_block(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3565,11 +3349,7 @@ impl super::Operation for HeadBucket {
         if let Some(access) = ccx.access {
             access.head_bucket(&mut s3_req).await?;
         }
-        let result = s3.head_bucket(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.head_bucket(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3713,11 +3493,7 @@ impl super::Operation for HeadObject {
         if let Some(access) = ccx.access {
             access.head_object(&mut s3_req).await?;
         }
-        let result = s3.head_object(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.head_object(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3762,11 +3538,7 @@ impl super::Operation for ListBucketAnalyticsConfigurations {
         if let Some(access) = ccx.access {
             access.list_bucket_analytics_configurations(&mut s3_req).await?;
         }
-        let result = s3.list_bucket_analytics_configurations(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_bucket_analytics_configurations(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3808,11 +3580,7 @@ impl super::Operation for ListBucketIntelligentTieringConfigurations {
         if let Some(access) = ccx.access {
             access.list_bucket_intelligent_tiering_configurations(&mut s3_req).await?;
         }
-        let result = s3.list_bucket_intelligent_tiering_configurations(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_bucket_intelligent_tiering_configurations(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3857,11 +3625,7 @@ impl super::Operation for ListBucketInventoryConfigurations {
         if let Some(access) = ccx.access {
             access.list_bucket_inventory_configurations(&mut s3_req).await?;
         }
-        let result = s3.list_bucket_inventory_configurations(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_bucket_inventory_configurations(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3906,11 +3670,7 @@ impl super::Operation for ListBucketMetricsConfigurations {
         if let Some(access) = ccx.access {
             access.list_bucket_metrics_configurations(&mut s3_req).await?;
         }
-        let result = s3.list_bucket_metrics_configurations(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_bucket_metrics_configurations(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3958,11 +3718,7 @@ impl super::Operation for ListBuckets {
         if let Some(access) = ccx.access {
             access.list_buckets(&mut s3_req).await?;
         }
-        let result = s3.list_buckets(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_buckets(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4026,11 +3782,7 @@ impl super::Operation for ListMultipartUploads {
         if let Some(access) = ccx.access {
             access.list_multipart_uploads(&mut s3_req).await?;
         }
-        let result = s3.list_multipart_uploads(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_multipart_uploads(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4098,11 +3850,7 @@ impl super::Operation for ListObjectVersions {
         if let Some(access) = ccx.access {
             access.list_object_versions(&mut s3_req).await?;
         }
-        let result = s3.list_object_versions(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_object_versions(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4167,11 +3915,7 @@ impl super::Operation for ListObjects {
         if let Some(access) = ccx.access {
             access.list_objects(&mut s3_req).await?;
         }
-        let result = s3.list_objects(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_objects(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4242,11 +3986,7 @@ impl super::Operation for ListObjectsV2 {
         if let Some(access) = ccx.access {
             access.list_objects_v2(&mut s3_req).await?;
         }
-        let result = s3.list_objects_v2(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_objects_v2(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4315,11 +4055,7 @@ impl super::Operation for ListParts {
         if let Some(access) = ccx.access {
             access.list_parts(&mut s3_req).await?;
         }
-        let result = s3.list_parts(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_parts(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4365,11 +4101,7 @@ impl super::Operation for PutBucketAccelerateConfiguration {
         if let Some(access) = ccx.access {
             access.put_bucket_accelerate_configuration(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_accelerate_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_accelerate_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4436,11 +4168,7 @@ impl super::Operation for PutBucketAcl {
         if let Some(access) = ccx.access {
             access.put_bucket_acl(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_acl(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_acl(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4486,11 +4214,7 @@ impl super::Operation for PutBucketAnalyticsConfiguration {
         if let Some(access) = ccx.access {
             access.put_bucket_analytics_configuration(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_analytics_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_analytics_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4539,11 +4263,7 @@ impl super::Operation for PutBucketCors {
         if let Some(access) = ccx.access {
             access.put_bucket_cors(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_cors(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_cors(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4592,11 +4312,7 @@ impl super::Operation for PutBucketEncryption {
         if let Some(access) = ccx.access {
             access.put_bucket_encryption(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_encryption(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_encryption(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4639,11 +4355,7 @@ impl super::Operation for PutBucketIntelligentTieringConfiguration {
         if let Some(access) = ccx.access {
             access.put_bucket_intelligent_tiering_configuration(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_intelligent_tiering_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_intelligent_tiering_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4689,11 +4401,7 @@ impl super::Operation for PutBucketInventoryConfiguration {
         if let Some(access) = ccx.access {
             access.put_bucket_inventory_configuration(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_inventory_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_inventory_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4749,11 +4457,7 @@ impl super::Operation for PutBucketLifecycleConfiguration {
         if let Some(access) = ccx.access {
             access.put_bucket_lifecycle_configuration(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_lifecycle_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_lifecycle_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4802,11 +4506,7 @@ impl super::Operation for PutBucketLogging {
         if let Some(access) = ccx.access {
             access.put_bucket_logging(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_logging(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_logging(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4852,11 +4552,7 @@ impl super::Operation for PutBucketMetricsConfiguration {
         if let Some(access) = ccx.access {
             access.put_bucket_metrics_configuration(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_metrics_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_metrics_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4903,11 +4599,7 @@ impl super::Operation for PutBucketNotificationConfiguration {
         if let Some(access) = ccx.access {
             access.put_bucket_notification_configuration(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_notification_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_notification_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4953,11 +4645,7 @@ impl super::Operation for PutBucketOwnershipControls {
         if let Some(access) = ccx.access {
             access.put_bucket_ownership_controls(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_ownership_controls(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_ownership_controls(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5010,11 +4698,7 @@ impl super::Operation for PutBucketPolicy {
         if let Some(access) = ccx.access {
             access.put_bucket_policy(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_policy(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_policy(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5066,11 +4750,7 @@ impl super::Operation for PutBucketReplication {
         if let Some(access) = ccx.access {
             access.put_bucket_replication(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_replication(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_replication(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5119,11 +4799,7 @@ impl super::Operation for PutBucketRequestPayment {
         if let Some(access) = ccx.access {
             access.put_bucket_request_payment(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_request_payment(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_request_payment(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5172,11 +4848,7 @@ impl super::Operation for PutBucketTagging {
         if let Some(access) = ccx.access {
             access.put_bucket_tagging(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_tagging(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_tagging(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5228,11 +4900,7 @@ impl super::Operation for PutBucketVersioning {
         if let Some(access) = ccx.access {
             access.put_bucket_versioning(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_versioning(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_versioning(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5281,11 +4949,7 @@ impl super::Operation for PutBucketWebsite {
         if let Some(access) = ccx.access {
             access.put_bucket_website(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_website(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_website(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5437,13 +5101,15 @@ impl PutObject {
         let bucket = http::unwrap_bucket(req);
         let key = http::parse_field_value(&m, "key")?.ok_or_else(|| invalid_request!("missing key"))?;
 
-        let vec_stream = req.s3ext.vec_stream.take().expect("missing vec stream");
+        let file_stream = req.s3ext.file_stream.take().expect("missing file stream");
 
-        let content_length = i64::try_from(vec_stream.exact_remaining_length())
+        let content_length = file_stream
+            .file_size()
+            .map(i64::try_from)
+            .transpose()
             .map_err(|e| s3_error!(e, InvalidArgument, "content-length overflow"))?;
-        let content_length = (content_length != 0).then_some(content_length);
 
-        let body: Option<StreamingBlob> = Some(StreamingBlob::new(vec_stream));
+        let body: Option<StreamingBlob> = Some(StreamingBlob::new(file_stream));
 
         let acl: Option<ObjectCannedACL> = http::parse_field_value(&m, "x-amz-acl")?;
 
@@ -5618,11 +5284,7 @@ impl super::Operation for PutObject {
         if let Some(access) = ccx.access {
             access.put_object(&mut s3_req).await?;
         }
-        let result = s3.put_object(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_object(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5698,11 +5360,7 @@ impl super::Operation for PutObjectAcl {
         if let Some(access) = ccx.access {
             access.put_object_acl(&mut s3_req).await?;
         }
-        let result = s3.put_object_acl(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_object_acl(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5760,11 +5418,7 @@ impl super::Operation for PutObjectLegalHold {
         if let Some(access) = ccx.access {
             access.put_object_legal_hold(&mut s3_req).await?;
         }
-        let result = s3.put_object_legal_hold(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_object_legal_hold(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5821,11 +5475,7 @@ impl super::Operation for PutObjectLockConfiguration {
         if let Some(access) = ccx.access {
             access.put_object_lock_configuration(&mut s3_req).await?;
         }
-        let result = s3.put_object_lock_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_object_lock_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5887,11 +5537,7 @@ impl super::Operation for PutObjectRetention {
         if let Some(access) = ccx.access {
             access.put_object_retention(&mut s3_req).await?;
         }
-        let result = s3.put_object_retention(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_object_retention(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5949,11 +5595,7 @@ impl super::Operation for PutObjectTagging {
         if let Some(access) = ccx.access {
             access.put_object_tagging(&mut s3_req).await?;
         }
-        let result = s3.put_object_tagging(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_object_tagging(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -6002,11 +5644,7 @@ impl super::Operation for PutPublicAccessBlock {
         if let Some(access) = ccx.access {
             access.put_public_access_block(&mut s3_req).await?;
         }
-        let result = s3.put_public_access_block(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_public_access_block(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -6062,11 +5700,7 @@ impl super::Operation for RestoreObject {
         if let Some(access) = ccx.access {
             access.restore_object(&mut s3_req).await?;
         }
-        let result = s3.restore_object(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.restore_object(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -6125,11 +5759,7 @@ impl super::Operation for SelectObjectContent {
         if let Some(access) = ccx.access {
             access.select_object_content(&mut s3_req).await?;
         }
-        let result = s3.select_object_content(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.select_object_content(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -6230,11 +5860,7 @@ impl super::Operation for UploadPart {
         if let Some(access) = ccx.access {
             access.upload_part(&mut s3_req).await?;
         }
-        let result = s3.upload_part(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.upload_part(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -6342,11 +5968,7 @@ impl super::Operation for UploadPartCopy {
         if let Some(access) = ccx.access {
             access.upload_part_copy(&mut s3_req).await?;
         }
-        let result = s3.upload_part_copy(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.upload_part_copy(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -6517,11 +6139,7 @@ impl super::Operation for WriteGetObjectResponse {
         if let Some(access) = ccx.access {
             access.write_get_object_response(&mut s3_req).await?;
         }
-        let result = s3.write_get_object_response(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.write_get_object_response(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -517,11 +517,7 @@ impl super::Operation for AbortMultipartUpload {
         if let Some(access) = ccx.access {
             access.abort_multipart_upload(&mut s3_req).await?;
         }
-        let result = s3.abort_multipart_upload(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.abort_multipart_upload(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -605,11 +601,7 @@ impl super::Operation for CompleteMultipartUpload {
         if let Some(access) = ccx.access {
             access.complete_multipart_upload(&mut s3_req).await?;
         }
-        let result = s3.complete_multipart_upload(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.complete_multipart_upload(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -794,11 +786,7 @@ impl super::Operation for CopyObject {
         if let Some(access) = ccx.access {
             access.copy_object(&mut s3_req).await?;
         }
-        let result = s3.copy_object(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.copy_object(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -865,11 +853,7 @@ impl super::Operation for CreateBucket {
         if let Some(access) = ccx.access {
             access.create_bucket(&mut s3_req).await?;
         }
-        let result = s3.create_bucket(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.create_bucket(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -918,11 +902,7 @@ impl super::Operation for CreateBucketMetadataTableConfiguration {
         if let Some(access) = ccx.access {
             access.create_bucket_metadata_table_configuration(&mut s3_req).await?;
         }
-        let result = s3.create_bucket_metadata_table_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.create_bucket_metadata_table_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1070,11 +1050,7 @@ impl super::Operation for CreateMultipartUpload {
         if let Some(access) = ccx.access {
             access.create_multipart_upload(&mut s3_req).await?;
         }
-        let result = s3.create_multipart_upload(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.create_multipart_upload(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1117,11 +1093,7 @@ impl super::Operation for DeleteBucket {
         if let Some(access) = ccx.access {
             access.delete_bucket(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1164,11 +1136,7 @@ impl super::Operation for DeleteBucketAnalyticsConfiguration {
         if let Some(access) = ccx.access {
             access.delete_bucket_analytics_configuration(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_analytics_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_analytics_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1208,11 +1176,7 @@ impl super::Operation for DeleteBucketCors {
         if let Some(access) = ccx.access {
             access.delete_bucket_cors(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_cors(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_cors(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1252,11 +1216,7 @@ impl super::Operation for DeleteBucketEncryption {
         if let Some(access) = ccx.access {
             access.delete_bucket_encryption(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_encryption(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_encryption(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1293,11 +1253,7 @@ impl super::Operation for DeleteBucketIntelligentTieringConfiguration {
         if let Some(access) = ccx.access {
             access.delete_bucket_intelligent_tiering_configuration(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_intelligent_tiering_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_intelligent_tiering_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1340,11 +1296,7 @@ impl super::Operation for DeleteBucketInventoryConfiguration {
         if let Some(access) = ccx.access {
             access.delete_bucket_inventory_configuration(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_inventory_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_inventory_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1384,11 +1336,7 @@ impl super::Operation for DeleteBucketLifecycle {
         if let Some(access) = ccx.access {
             access.delete_bucket_lifecycle(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_lifecycle(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_lifecycle(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1428,11 +1376,7 @@ impl super::Operation for DeleteBucketMetadataTableConfiguration {
         if let Some(access) = ccx.access {
             access.delete_bucket_metadata_table_configuration(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_metadata_table_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_metadata_table_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1475,11 +1419,7 @@ impl super::Operation for DeleteBucketMetricsConfiguration {
         if let Some(access) = ccx.access {
             access.delete_bucket_metrics_configuration(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_metrics_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_metrics_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1519,11 +1459,7 @@ impl super::Operation for DeleteBucketOwnershipControls {
         if let Some(access) = ccx.access {
             access.delete_bucket_ownership_controls(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_ownership_controls(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_ownership_controls(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1563,11 +1499,7 @@ impl super::Operation for DeleteBucketPolicy {
         if let Some(access) = ccx.access {
             access.delete_bucket_policy(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_policy(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_policy(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1607,11 +1539,7 @@ impl super::Operation for DeleteBucketReplication {
         if let Some(access) = ccx.access {
             access.delete_bucket_replication(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_replication(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_replication(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1651,11 +1579,7 @@ impl super::Operation for DeleteBucketTagging {
         if let Some(access) = ccx.access {
             access.delete_bucket_tagging(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_tagging(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_tagging(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1695,11 +1619,7 @@ impl super::Operation for DeleteBucketWebsite {
         if let Some(access) = ccx.access {
             access.delete_bucket_website(&mut s3_req).await?;
         }
-        let result = s3.delete_bucket_website(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_bucket_website(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1767,11 +1687,7 @@ impl super::Operation for DeleteObject {
         if let Some(access) = ccx.access {
             access.delete_object(&mut s3_req).await?;
         }
-        let result = s3.delete_object(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_object(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1817,11 +1733,7 @@ impl super::Operation for DeleteObjectTagging {
         if let Some(access) = ccx.access {
             access.delete_object_tagging(&mut s3_req).await?;
         }
-        let result = s3.delete_object_tagging(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_object_tagging(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1880,11 +1792,7 @@ impl super::Operation for DeleteObjects {
         if let Some(access) = ccx.access {
             access.delete_objects(&mut s3_req).await?;
         }
-        let result = s3.delete_objects(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_objects(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1924,11 +1832,7 @@ impl super::Operation for DeletePublicAccessBlock {
         if let Some(access) = ccx.access {
             access.delete_public_access_block(&mut s3_req).await?;
         }
-        let result = s3.delete_public_access_block(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.delete_public_access_block(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -1974,11 +1878,7 @@ impl super::Operation for GetBucketAccelerateConfiguration {
         if let Some(access) = ccx.access {
             access.get_bucket_accelerate_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_accelerate_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_accelerate_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2020,11 +1920,7 @@ impl super::Operation for GetBucketAcl {
         if let Some(access) = ccx.access {
             access.get_bucket_acl(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_acl(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_acl(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2071,11 +1967,7 @@ impl super::Operation for GetBucketAnalyticsConfiguration {
         if let Some(access) = ccx.access {
             access.get_bucket_analytics_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_analytics_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_analytics_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2117,11 +2009,7 @@ impl super::Operation for GetBucketCors {
         if let Some(access) = ccx.access {
             access.get_bucket_cors(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_cors(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_cors(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2165,11 +2053,7 @@ impl super::Operation for GetBucketEncryption {
         if let Some(access) = ccx.access {
             access.get_bucket_encryption(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_encryption(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_encryption(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2210,11 +2094,7 @@ impl super::Operation for GetBucketIntelligentTieringConfiguration {
         if let Some(access) = ccx.access {
             access.get_bucket_intelligent_tiering_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_intelligent_tiering_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_intelligent_tiering_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2261,11 +2141,7 @@ impl super::Operation for GetBucketInventoryConfiguration {
         if let Some(access) = ccx.access {
             access.get_bucket_inventory_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_inventory_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_inventory_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2312,11 +2188,7 @@ impl super::Operation for GetBucketLifecycleConfiguration {
         if let Some(access) = ccx.access {
             access.get_bucket_lifecycle_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_lifecycle_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_lifecycle_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2358,11 +2230,7 @@ impl super::Operation for GetBucketLocation {
         if let Some(access) = ccx.access {
             access.get_bucket_location(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_location(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_location(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2404,11 +2272,7 @@ impl super::Operation for GetBucketLogging {
         if let Some(access) = ccx.access {
             access.get_bucket_logging(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_logging(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_logging(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2452,11 +2316,7 @@ impl super::Operation for GetBucketMetadataTableConfiguration {
         if let Some(access) = ccx.access {
             access.get_bucket_metadata_table_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_metadata_table_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_metadata_table_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2503,11 +2363,7 @@ impl super::Operation for GetBucketMetricsConfiguration {
         if let Some(access) = ccx.access {
             access.get_bucket_metrics_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_metrics_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_metrics_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2549,11 +2405,7 @@ impl super::Operation for GetBucketNotificationConfiguration {
         if let Some(access) = ccx.access {
             access.get_bucket_notification_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_notification_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_notification_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2597,11 +2449,7 @@ impl super::Operation for GetBucketOwnershipControls {
         if let Some(access) = ccx.access {
             access.get_bucket_ownership_controls(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_ownership_controls(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_ownership_controls(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2645,11 +2493,7 @@ impl super::Operation for GetBucketPolicy {
         if let Some(access) = ccx.access {
             access.get_bucket_policy(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_policy(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_policy(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2693,11 +2537,7 @@ impl super::Operation for GetBucketPolicyStatus {
         if let Some(access) = ccx.access {
             access.get_bucket_policy_status(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_policy_status(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_policy_status(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2741,11 +2581,7 @@ impl super::Operation for GetBucketReplication {
         if let Some(access) = ccx.access {
             access.get_bucket_replication(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_replication(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_replication(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2787,11 +2623,7 @@ impl super::Operation for GetBucketRequestPayment {
         if let Some(access) = ccx.access {
             access.get_bucket_request_payment(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_request_payment(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_request_payment(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2833,11 +2665,7 @@ impl super::Operation for GetBucketTagging {
         if let Some(access) = ccx.access {
             access.get_bucket_tagging(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_tagging(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_tagging(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2879,11 +2707,7 @@ impl super::Operation for GetBucketVersioning {
         if let Some(access) = ccx.access {
             access.get_bucket_versioning(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_versioning(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_versioning(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -2925,11 +2749,7 @@ impl super::Operation for GetBucketWebsite {
         if let Some(access) = ccx.access {
             access.get_bucket_website(&mut s3_req).await?;
         }
-        let result = s3.get_bucket_website(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_bucket_website(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3080,11 +2900,7 @@ impl super::Operation for GetObject {
             access.get_object(&mut s3_req).await?;
         }
         let overridden_headers = super::get_object::extract_overridden_response_headers(&s3_req)?;
-        let result = s3.get_object(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_object(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(overridden_headers);
         super::get_object::merge_custom_headers(&mut resp, s3_resp.headers);
@@ -3135,11 +2951,7 @@ impl super::Operation for GetObjectAcl {
         if let Some(access) = ccx.access {
             access.get_object_acl(&mut s3_req).await?;
         }
-        let result = s3.get_object_acl(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_object_acl(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3212,11 +3024,7 @@ impl super::Operation for GetObjectAttributes {
         if let Some(access) = ccx.access {
             access.get_object_attributes(&mut s3_req).await?;
         }
-        let result = s3.get_object_attributes(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_object_attributes(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3267,11 +3075,7 @@ impl super::Operation for GetObjectLegalHold {
         if let Some(access) = ccx.access {
             access.get_object_legal_hold(&mut s3_req).await?;
         }
-        let result = s3.get_object_legal_hold(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_object_legal_hold(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3315,11 +3119,7 @@ impl super::Operation for GetObjectLockConfiguration {
         if let Some(access) = ccx.access {
             access.get_object_lock_configuration(&mut s3_req).await?;
         }
-        let result = s3.get_object_lock_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_object_lock_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3370,11 +3170,7 @@ impl super::Operation for GetObjectRetention {
         if let Some(access) = ccx.access {
             access.get_object_retention(&mut s3_req).await?;
         }
-        let result = s3.get_object_retention(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_object_retention(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3424,11 +3220,7 @@ impl super::Operation for GetObjectTagging {
         if let Some(access) = ccx.access {
             access.get_object_tagging(&mut s3_req).await?;
         }
-        let result = s3.get_object_tagging(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_object_tagging(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3477,11 +3269,7 @@ impl super::Operation for GetObjectTorrent {
         if let Some(access) = ccx.access {
             access.get_object_torrent(&mut s3_req).await?;
         }
-        let result = s3.get_object_torrent(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_object_torrent(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3525,11 +3313,7 @@ impl super::Operation for GetPublicAccessBlock {
         if let Some(access) = ccx.access {
             access.get_public_access_block(&mut s3_req).await?;
         }
-        let result = s3.get_public_access_block(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.get_public_access_block(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3574,11 +3358,7 @@ impl super::Operation for HeadBucket {
         if let Some(access) = ccx.access {
             access.head_bucket(&mut s3_req).await?;
         }
-        let result = s3.head_bucket(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.head_bucket(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3722,11 +3502,7 @@ impl super::Operation for HeadObject {
         if let Some(access) = ccx.access {
             access.head_object(&mut s3_req).await?;
         }
-        let result = s3.head_object(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.head_object(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3771,11 +3547,7 @@ impl super::Operation for ListBucketAnalyticsConfigurations {
         if let Some(access) = ccx.access {
             access.list_bucket_analytics_configurations(&mut s3_req).await?;
         }
-        let result = s3.list_bucket_analytics_configurations(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_bucket_analytics_configurations(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3817,11 +3589,7 @@ impl super::Operation for ListBucketIntelligentTieringConfigurations {
         if let Some(access) = ccx.access {
             access.list_bucket_intelligent_tiering_configurations(&mut s3_req).await?;
         }
-        let result = s3.list_bucket_intelligent_tiering_configurations(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_bucket_intelligent_tiering_configurations(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3866,11 +3634,7 @@ impl super::Operation for ListBucketInventoryConfigurations {
         if let Some(access) = ccx.access {
             access.list_bucket_inventory_configurations(&mut s3_req).await?;
         }
-        let result = s3.list_bucket_inventory_configurations(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_bucket_inventory_configurations(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3915,11 +3679,7 @@ impl super::Operation for ListBucketMetricsConfigurations {
         if let Some(access) = ccx.access {
             access.list_bucket_metrics_configurations(&mut s3_req).await?;
         }
-        let result = s3.list_bucket_metrics_configurations(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_bucket_metrics_configurations(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -3967,11 +3727,7 @@ impl super::Operation for ListBuckets {
         if let Some(access) = ccx.access {
             access.list_buckets(&mut s3_req).await?;
         }
-        let result = s3.list_buckets(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_buckets(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4035,11 +3791,7 @@ impl super::Operation for ListMultipartUploads {
         if let Some(access) = ccx.access {
             access.list_multipart_uploads(&mut s3_req).await?;
         }
-        let result = s3.list_multipart_uploads(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_multipart_uploads(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4107,11 +3859,7 @@ impl super::Operation for ListObjectVersions {
         if let Some(access) = ccx.access {
             access.list_object_versions(&mut s3_req).await?;
         }
-        let result = s3.list_object_versions(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_object_versions(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4176,11 +3924,7 @@ impl super::Operation for ListObjects {
         if let Some(access) = ccx.access {
             access.list_objects(&mut s3_req).await?;
         }
-        let result = s3.list_objects(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_objects(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4251,11 +3995,7 @@ impl super::Operation for ListObjectsV2 {
         if let Some(access) = ccx.access {
             access.list_objects_v2(&mut s3_req).await?;
         }
-        let result = s3.list_objects_v2(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_objects_v2(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4324,11 +4064,7 @@ impl super::Operation for ListParts {
         if let Some(access) = ccx.access {
             access.list_parts(&mut s3_req).await?;
         }
-        let result = s3.list_parts(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.list_parts(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4374,11 +4110,7 @@ impl super::Operation for PutBucketAccelerateConfiguration {
         if let Some(access) = ccx.access {
             access.put_bucket_accelerate_configuration(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_accelerate_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_accelerate_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4445,11 +4177,7 @@ impl super::Operation for PutBucketAcl {
         if let Some(access) = ccx.access {
             access.put_bucket_acl(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_acl(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_acl(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4495,11 +4223,7 @@ impl super::Operation for PutBucketAnalyticsConfiguration {
         if let Some(access) = ccx.access {
             access.put_bucket_analytics_configuration(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_analytics_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_analytics_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4548,11 +4272,7 @@ impl super::Operation for PutBucketCors {
         if let Some(access) = ccx.access {
             access.put_bucket_cors(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_cors(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_cors(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4601,11 +4321,7 @@ impl super::Operation for PutBucketEncryption {
         if let Some(access) = ccx.access {
             access.put_bucket_encryption(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_encryption(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_encryption(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4648,11 +4364,7 @@ impl super::Operation for PutBucketIntelligentTieringConfiguration {
         if let Some(access) = ccx.access {
             access.put_bucket_intelligent_tiering_configuration(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_intelligent_tiering_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_intelligent_tiering_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4698,11 +4410,7 @@ impl super::Operation for PutBucketInventoryConfiguration {
         if let Some(access) = ccx.access {
             access.put_bucket_inventory_configuration(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_inventory_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_inventory_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4758,11 +4466,7 @@ impl super::Operation for PutBucketLifecycleConfiguration {
         if let Some(access) = ccx.access {
             access.put_bucket_lifecycle_configuration(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_lifecycle_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_lifecycle_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4811,11 +4515,7 @@ impl super::Operation for PutBucketLogging {
         if let Some(access) = ccx.access {
             access.put_bucket_logging(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_logging(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_logging(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4861,11 +4561,7 @@ impl super::Operation for PutBucketMetricsConfiguration {
         if let Some(access) = ccx.access {
             access.put_bucket_metrics_configuration(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_metrics_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_metrics_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4912,11 +4608,7 @@ impl super::Operation for PutBucketNotificationConfiguration {
         if let Some(access) = ccx.access {
             access.put_bucket_notification_configuration(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_notification_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_notification_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -4962,11 +4654,7 @@ impl super::Operation for PutBucketOwnershipControls {
         if let Some(access) = ccx.access {
             access.put_bucket_ownership_controls(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_ownership_controls(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_ownership_controls(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5019,11 +4707,7 @@ impl super::Operation for PutBucketPolicy {
         if let Some(access) = ccx.access {
             access.put_bucket_policy(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_policy(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_policy(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5075,11 +4759,7 @@ impl super::Operation for PutBucketReplication {
         if let Some(access) = ccx.access {
             access.put_bucket_replication(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_replication(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_replication(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5128,11 +4808,7 @@ impl super::Operation for PutBucketRequestPayment {
         if let Some(access) = ccx.access {
             access.put_bucket_request_payment(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_request_payment(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_request_payment(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5181,11 +4857,7 @@ impl super::Operation for PutBucketTagging {
         if let Some(access) = ccx.access {
             access.put_bucket_tagging(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_tagging(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_tagging(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5237,11 +4909,7 @@ impl super::Operation for PutBucketVersioning {
         if let Some(access) = ccx.access {
             access.put_bucket_versioning(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_versioning(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_versioning(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5290,11 +4958,7 @@ impl super::Operation for PutBucketWebsite {
         if let Some(access) = ccx.access {
             access.put_bucket_website(&mut s3_req).await?;
         }
-        let result = s3.put_bucket_website(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_bucket_website(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5449,13 +5113,15 @@ impl PutObject {
         let bucket = http::unwrap_bucket(req);
         let key = http::parse_field_value(&m, "key")?.ok_or_else(|| invalid_request!("missing key"))?;
 
-        let vec_stream = req.s3ext.vec_stream.take().expect("missing vec stream");
+        let file_stream = req.s3ext.file_stream.take().expect("missing file stream");
 
-        let content_length = i64::try_from(vec_stream.exact_remaining_length())
+        let content_length = file_stream
+            .file_size()
+            .map(i64::try_from)
+            .transpose()
             .map_err(|e| s3_error!(e, InvalidArgument, "content-length overflow"))?;
-        let content_length = (content_length != 0).then_some(content_length);
 
-        let body: Option<StreamingBlob> = Some(StreamingBlob::new(vec_stream));
+        let body: Option<StreamingBlob> = Some(StreamingBlob::new(file_stream));
 
         let acl: Option<ObjectCannedACL> = http::parse_field_value(&m, "x-amz-acl")?;
 
@@ -5633,11 +5299,7 @@ impl super::Operation for PutObject {
         if let Some(access) = ccx.access {
             access.put_object(&mut s3_req).await?;
         }
-        let result = s3.put_object(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_object(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5713,11 +5375,7 @@ impl super::Operation for PutObjectAcl {
         if let Some(access) = ccx.access {
             access.put_object_acl(&mut s3_req).await?;
         }
-        let result = s3.put_object_acl(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_object_acl(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5775,11 +5433,7 @@ impl super::Operation for PutObjectLegalHold {
         if let Some(access) = ccx.access {
             access.put_object_legal_hold(&mut s3_req).await?;
         }
-        let result = s3.put_object_legal_hold(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_object_legal_hold(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5836,11 +5490,7 @@ impl super::Operation for PutObjectLockConfiguration {
         if let Some(access) = ccx.access {
             access.put_object_lock_configuration(&mut s3_req).await?;
         }
-        let result = s3.put_object_lock_configuration(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_object_lock_configuration(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5902,11 +5552,7 @@ impl super::Operation for PutObjectRetention {
         if let Some(access) = ccx.access {
             access.put_object_retention(&mut s3_req).await?;
         }
-        let result = s3.put_object_retention(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_object_retention(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -5964,11 +5610,7 @@ impl super::Operation for PutObjectTagging {
         if let Some(access) = ccx.access {
             access.put_object_tagging(&mut s3_req).await?;
         }
-        let result = s3.put_object_tagging(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_object_tagging(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -6017,11 +5659,7 @@ impl super::Operation for PutPublicAccessBlock {
         if let Some(access) = ccx.access {
             access.put_public_access_block(&mut s3_req).await?;
         }
-        let result = s3.put_public_access_block(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.put_public_access_block(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -6077,11 +5715,7 @@ impl super::Operation for RestoreObject {
         if let Some(access) = ccx.access {
             access.restore_object(&mut s3_req).await?;
         }
-        let result = s3.restore_object(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.restore_object(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -6140,11 +5774,7 @@ impl super::Operation for SelectObjectContent {
         if let Some(access) = ccx.access {
             access.select_object_content(&mut s3_req).await?;
         }
-        let result = s3.select_object_content(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.select_object_content(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -6245,11 +5875,7 @@ impl super::Operation for UploadPart {
         if let Some(access) = ccx.access {
             access.upload_part(&mut s3_req).await?;
         }
-        let result = s3.upload_part(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.upload_part(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -6357,11 +5983,7 @@ impl super::Operation for UploadPartCopy {
         if let Some(access) = ccx.access {
             access.upload_part_copy(&mut s3_req).await?;
         }
-        let result = s3.upload_part_copy(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.upload_part_copy(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -6532,11 +6154,7 @@ impl super::Operation for WriteGetObjectResponse {
         if let Some(access) = ccx.access {
             access.write_get_object_response(&mut s3_req).await?;
         }
-        let result = s3.write_get_object_response(s3_req).await;
-        let s3_resp = match result {
-            Ok(val) => val,
-            Err(err) => return super::serialize_error(err, false),
-        };
+        let s3_resp = s3.write_get_object_response(s3_req).await?;
         let mut resp = Self::serialize_http(s3_resp.output)?;
         resp.headers.extend(s3_resp.headers);
         resp.extensions.extend(s3_resp.extensions);
@@ -15,26 +15,36 @@ use self::signature::SignatureContext;
 mod get_object;
 mod multipart;
 
+mod post_object;
+pub use self::post_object::{DEFAULT_POST_OBJECT_MAX_FILE_SIZE, PostObjectStream};
+
 #[cfg(test)]
 mod tests;
 
 use crate::access::{S3Access, S3AccessContext};
-use crate::auth::{Credentials, S3Auth};
+use crate::auth::{Credentials, S3Auth, SignaturePolicy};
+use crate::cors::S3Cors;
 use crate::error::*;
 use crate::header;
 use crate::host::S3Host;
 use crate::http;
 use crate::http::Body;
+use crate::http::{ChecksumStream, ChecksumVerifier};
 use crate::http::{OrderedHeaders, OrderedQs};
 use crate::http::{Request, Response};
+use crate::layer::S3Layer;
 use crate::path::{ParseS3PathError, S3Path};
 use crate::protocol::S3Request;
+use crate::request_id::RequestId;
 use crate::route::S3Route;
 use crate::s3_trait::S3;
-use crate::stream::VecByteStream;
-use crate::stream::aggregate_unlimited;
+use crate::stream::DynByteStream;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::throttle::Throttle;
 use crate::validation::{AwsNameValidation, NameValidation};
+use crate::website::S3Website;
 
+use std::borrow::Cow;
 use std::mem;
 use std::net::{IpAddr, SocketAddr};
 use std::ops::Not;
@@ -62,6 +72,18 @@ pub struct CallContext<'a> {
     pub access: Option<&'a dyn S3Access>,
     pub route: Option<&'a dyn S3Route>,
     pub validation: Option<&'a dyn NameValidation>,
+    pub post_object_max_file_size: u64,
+    pub checksum_verification: bool,
+    pub signature_policy: &'a SignaturePolicy,
+    pub layers: &'a [Box<dyn S3Layer>],
+    pub request_id: &'a RequestId,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub throttle: Option<&'a Throttle>,
+    pub cors: Option<&'a dyn S3Cors>,
+    pub website: Option<&'a dyn S3Website>,
+    pub base_path: Option<&'a str>,
+    pub base_path_stripped: bool,
+    pub region: Option<&'a str>,
 }
 
 fn build_s3_request<T>(input: T, req: &mut Request) -> S3Request<T> {
@@ -70,6 +92,7 @@ fn build_s3_request<T>(input: T, req: &mut Request) -> S3Request<T> {
     let headers = mem::take(&mut req.headers);
     let extensions = mem::take(&mut req.extensions);
     let credentials = req.s3ext.credentials.take();
+    let identity = req.s3ext.identity.take();
     let region = req.s3ext.region.take();
     let service = req.s3ext.service.take();
     let trailing_headers = req.s3ext.trailing_headers.take();
@@ -81,6 +104,7 @@ fn build_s3_request<T>(input: T, req: &mut Request) -> S3Request<T> {
         headers,
         extensions,
         credentials,
+        identity,
         region,
         service,
         trailing_headers,
@@ -98,10 +122,26 @@ pub(crate) fn serialize_error(mut e: S3Error, no_decl: bool) -> S3Result<Respons
     if let Some(headers) = e.take_headers() {
         res.headers = headers;
     }
+    if let Some(request_id) = e.request_id() {
+        if let Ok(val) = http::HeaderValue::try_from(request_id) {
+            res.headers.entry(header::X_AMZ_REQUEST_ID).or_insert(val);
+        }
+    }
     drop(e);
     Ok(res)
 }
 
+/// Serializes an error of the request.
+///
+/// The request id is set unless the S3 implementation has set one.
+fn serialize_op_error(mut err: S3Error, req: &mut Request, ccx: &CallContext<'_>) -> S3Result<Response> {
+    if err.request_id().is_none() {
+        err.set_request_id(ccx.request_id.request_id.as_str());
+    }
+    req.s3ext.error_code = Some(err.code().clone());
+    serialize_error(err, false)
+}
+
 fn unknown_operation() -> S3Error {
     S3Error::with_message(S3ErrorCode::NotImplemented, "Unknown operation")
 }
@@ -117,6 +157,18 @@ fn is_socket_addr_or_ip_addr(host: &str) -> bool {
     host.parse::<SocketAddr>().is_ok() || host.parse::<IpAddr>().is_ok()
 }
 
+/// Checks the region of the endpoint against the region of the service.
+fn check_host_region<'a>(expected: Option<&'a str>, region: Option<&'a str>) -> S3Result<Option<&'a str>> {
+    match (expected, region) {
+        (Some(expected), Some(region)) if region != expected => {
+            let mut err = invalid_request!("The endpoint of region '{region}' is wrong; expecting '{expected}'");
+            err.set_region(expected);
+            Err(err)
+        }
+        _ => Ok(expected.or(region)),
+    }
+}
+
 fn convert_parse_s3_path_error(err: &ParseS3PathError) -> S3Error {
     match err {
         ParseS3PathError::InvalidPath => s3_error!(InvalidURI),
@@ -177,10 +229,10 @@ async fn extract_full_body(content_length: Option<u64>, body: &mut Body) -> S3Re
         return Ok(bytes);
     }
 
-    let bytes = body
-        .store_all_unlimited()
-        .await
-        .map_err(|e| S3Error::with_source(S3ErrorCode::InternalError, e))?;
+    let bytes = body.store_all_unlimited().await.map_err(|e| match e.downcast::<S3Error>() {
+        Ok(err) => *err,
+        Err(e) => S3Error::with_source(S3ErrorCode::InternalError, e),
+    })?;
 
     if bytes.is_empty().not() {
         let content_length = content_length.ok_or(S3ErrorCode::MissingContentLength)?;
@@ -207,22 +259,41 @@ pub async fn call(req: &mut Request, ccx: &CallContext<'_>) -> S3Result<Response
         Ok(op) => op,
         Err(err) => {
             error!(?err, "failed to prepare");
-            return serialize_error(err, false);
+            return serialize_op_error(err, req, ccx);
         }
     };
 
     match prep {
         Prepare::S3(op) => {
-            match op.call(ccx, req).await {
+            let result = if ccx.layers.is_empty() {
+                op.call(ccx, req).await
+            } else {
+                crate::layer::call(ccx, op, req).await
+            };
+            match result {
                 Ok(resp) => {
                     Ok(resp) //
                 }
                 Err(err) => {
-                    error!(op = %op.name(), ?err, "op returns error");
-                    serialize_error(err, false)
+                    // Errors from the S3 implementation are usually client errors like `NoSuchKey`.
+                    if err.status_code().is_none_or(|s| s.is_server_error()) {
+                        error!(op = %op.name(), ?err, "op returns error");
+                    } else {
+                        debug!(op = %op.name(), ?err, "op returns error");
+                    }
+                    serialize_op_error(err, req, ccx)
                 }
             }
         }
+        Prepare::CorsPreflight(cors) => match crate::cors::preflight(cors, ccx.s3, req).await {
+            Ok(resp) => Ok(resp),
+            Err(err) => {
+                debug!(?err, "cors preflight is rejected");
+                serialize_op_error(err, req, ccx)
+            }
+        },
+        // the website call is boxed to keep the request future small
+        Prepare::Website(website) => Ok(Box::pin(crate::website::call(website, ccx, req)).await),
         Prepare::CustomRoute => {
             let body = mem::take(&mut req.body);
             let mut s3_req = build_s3_request(body, req);
@@ -243,31 +314,73 @@ pub async fn call(req: &mut Request, ccx: &CallContext<'_>) -> S3Result<Response
                 }),
                 Err(err) => {
                     error!(?err, "custom route returns error");
-                    serialize_error(err, false)
+                    serialize_op_error(err, req, ccx)
                 }
             }
         }
     }
 }
 
-enum Prepare {
+/// Returns the path for parsing [`S3Path`] and the full path for checking signatures.
+fn split_base_path<'p>(ccx: &CallContext<'_>, uri_path: &'p str) -> S3Result<(&'p str, Cow<'p, str>)> {
+    let Some(base_path) = ccx.base_path else { return Ok((uri_path, Cow::Borrowed(uri_path))) };
+    if ccx.base_path_stripped {
+        return Ok((uri_path, Cow::Owned(format!("{base_path}{uri_path}"))));
+    }
+    match uri_path.strip_prefix(base_path) {
+        Some("") => Ok(("/", Cow::Borrowed(uri_path))),
+        Some(rest) if rest.starts_with('/') => Ok((rest, Cow::Borrowed(uri_path))),
+        _ => Err(s3_error!(InvalidURI, "The request path is not under the base path")),
+    }
+}
+
+/// Returns the URI with the base path stripped, which is matched by custom routes.
+fn strip_base_path<'u>(ccx: &CallContext<'_>, uri: &'u Uri) -> Cow<'u, Uri> {
+    let path = match split_base_path(ccx, uri.path()) {
+        Ok((path, _)) if path.len() != uri.path().len() => path,
+        _ => return Cow::Borrowed(uri),
+    };
+    let path_and_query = match uri.query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path.to_owned(),
+    };
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = path_and_query.parse().ok();
+    Uri::from_parts(parts).map_or(Cow::Borrowed(uri), Cow::Owned)
+}
+
+enum Prepare<'a> {
     S3(&'static dyn Operation),
+    CorsPreflight(&'a dyn S3Cors),
+    Website(&'a dyn S3Website),
     CustomRoute,
 }
 
 #[allow(clippy::too_many_lines)]
 #[tracing::instrument(level = "debug", skip_all, err)]
-async fn prepare(req: &mut Request, ccx: &CallContext<'_>) -> S3Result<Prepare> {
+async fn prepare<'a>(req: &mut Request, ccx: &CallContext<'a>) -> S3Result<Prepare<'a>> {
     let s3_path;
     let mut content_length;
+    let content_length_range;
+
+    // the global limit is checked before the signature, so that requests with bad signatures are also limited
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(throttle) = ccx.throttle {
+        req.s3ext.throttle_permit = Some(Box::new(throttle.acquire_global()?));
+    }
+
     {
-        let decoded_uri_path = urlencoding::decode(req.uri.path())
+        let (uri_path, signed_uri_path) = split_base_path(ccx, req.uri.path())?;
+        let decoded_uri_path = urlencoding::decode(uri_path)
             .map_err(|_| S3ErrorCode::InvalidURI)?
             .into_owned();
 
         let host_header = extract_host(req)?;
         let vh;
         let vh_bucket;
+        // the region of the endpoint is used when the service has no region
+        let mut region = ccx.region;
+        let mut is_website = false;
         {
             let default_validation = &const { AwsNameValidation::new() };
             let validation = ccx.validation.unwrap_or(default_validation);
@@ -281,6 +394,8 @@ async fn prepare(req: &mut Request, ccx: &CallContext<'_>) -> S3Result<Prepare>
                         debug!(?vh);
 
                         vh_bucket = vh.bucket();
+                        region = check_host_region(ccx.region, vh.region())?;
+                        is_website = vh.is_website();
                         break 'parse crate::path::parse_virtual_hosted_style_with_validation(
                             vh_bucket,
                             &decoded_uri_path,
@@ -296,6 +411,30 @@ async fn prepare(req: &mut Request, ccx: &CallContext<'_>) -> S3Result<Prepare>
 
             req.s3ext.s3_path = Some(result.map_err(|err| convert_parse_s3_path_error(&err))?);
             s3_path = req.s3ext.s3_path.as_ref().unwrap();
+            if let Some(log) = &mut req.s3ext.access_log {
+                log.set_path(s3_path);
+            }
+        }
+
+        if is_website {
+            if let Some(website) = ccx.website {
+                #[cfg(not(target_arch = "wasm32"))]
+                if let (Some(throttle), Some(permit)) = (ccx.throttle, &mut req.s3ext.throttle_permit) {
+                    throttle.acquire(permit, None, s3_path)?;
+                }
+                return Ok(Prepare::Website(website));
+            }
+        }
+
+        // browsers send preflight requests without credentials, even for presigned URLs
+        if let Some(cors) = ccx.cors {
+            if req.method == Method::OPTIONS {
+                #[cfg(not(target_arch = "wasm32"))]
+                if let (Some(throttle), Some(permit)) = (ccx.throttle, &mut req.s3ext.throttle_permit) {
+                    throttle.acquire(permit, None, s3_path)?;
+                }
+                return Ok(Prepare::CorsPreflight(cors));
+            }
         }
 
         req.s3ext.qs = extract_qs(&req.uri)?;
@@ -310,33 +449,50 @@ async fn prepare(req: &mut Request, ccx: &CallContext<'_>) -> S3Result<Prepare>
         {
             let mut scx = SignatureContext {
                 auth: ccx.auth,
+                policy: ccx.signature_policy,
 
                 req_version: req.version,
                 req_method: &req.method,
                 req_uri: &req.uri,
+                req_uri_path: &signed_uri_path,
                 req_body: &mut req.body,
 
                 qs: req.s3ext.qs.as_ref(),
                 hs,
 
-                decoded_uri_path,
+                decoded_uri_path: urlencoding::decode(&signed_uri_path)
+                    .map_err(|_| S3ErrorCode::InvalidURI)?
+                    .into_owned(),
                 vh_bucket,
+                region,
+                s3_path,
 
                 content_length,
                 decoded_content_length,
                 mime,
 
                 multipart: None,
+                post_policy: None,
                 transformed_body: None,
                 trailing_headers: None,
+
+                signature_version: None,
+                auth_type: None,
             };
 
-            let credentials = scx.check().await?;
+            // the signature check is boxed to keep the request future small
+            let credentials = Box::pin(scx.check()).await;
+            if let Some(log) = &mut req.s3ext.access_log {
+                log.signature_version = scx.signature_version;
+                log.auth_type = scx.auth_type;
+            }
+            let credentials = credentials?;
 
             body_changed = scx.transformed_body.is_some() || scx.multipart.is_some();
             transformed_body = scx.transformed_body;
 
             req.s3ext.multipart = scx.multipart;
+            content_length_range = scx.post_policy.and_then(|p| p.content_length_range());
             req.s3ext.trailing_headers = scx.trailing_headers;
 
             match credentials {
@@ -344,14 +500,30 @@ async fn prepare(req: &mut Request, ccx: &CallContext<'_>) -> S3Result<Prepare>
                     req.s3ext.credentials = Some(Credentials {
                         access_key: cred.access_key,
                         secret_key: cred.secret_key,
+                        session_token: cred.session_token,
                     });
-                    req.s3ext.region = cred.region;
+                    req.s3ext.identity = cred.identity;
+                    req.s3ext.region = cred.region.or_else(|| region.map(ToOwned::to_owned));
                     req.s3ext.service = cred.service;
                 }
                 None => {
                     req.s3ext.credentials = None;
+                    req.s3ext.identity = None;
+                    req.s3ext.region = region.map(ToOwned::to_owned);
                 }
             }
+            req.s3ext.authenticated = req.s3ext.credentials.is_some();
+            if let Some(log) = &mut req.s3ext.access_log {
+                let principal = req.s3ext.identity.as_ref().and_then(|i| i.principal.as_ref());
+                let access_key = req.s3ext.credentials.as_ref().map(|c| &c.access_key);
+                log.requester = principal.or(access_key).cloned();
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            if let (Some(throttle), Some(permit)) = (ccx.throttle, &mut req.s3ext.throttle_permit) {
+                let access_key = req.s3ext.credentials.as_ref().map(|c| c.access_key.as_str());
+                throttle.acquire(permit, access_key, s3_path)?;
+            }
         }
 
         if body_changed {
@@ -372,7 +544,8 @@ async fn prepare(req: &mut Request, ccx: &CallContext<'_>) -> S3Result<Prepare>
     }
 
     if let Some(route) = ccx.route {
-        if route.is_match(&req.method, &req.uri, &req.headers, &mut req.extensions) {
+        let route_uri = strip_base_path(ccx, &req.uri);
+        if route.is_match(&req.method, &route_uri, &req.headers, &mut req.extensions) {
             return Ok(Prepare::CustomRoute);
         }
     }
@@ -385,10 +558,17 @@ async fn prepare(req: &mut Request, ccx: &CallContext<'_>) -> S3Result<Prepare>
                     S3Path::Bucket { .. } => {
                         // POST object
                         debug!(?multipart);
+                        let (min_file_size, max_file_size) = match content_length_range {
+                            Some((min, max)) => (min, max.min(ccx.post_object_max_file_size)),
+                            None => (0, ccx.post_object_max_file_size),
+                        };
+                        let file_size = multipart.file_size();
+                        if file_size.is_some_and(|size| size > max_file_size) {
+                            return Err(s3_error!(EntityTooLarge, "Your proposed upload exceeds the maximum allowed size."));
+                        }
                         let file_stream = multipart.take_file_stream().expect("missing file stream");
-                        let vec_bytes = aggregate_unlimited(file_stream).await.map_err(S3Error::internal_error)?;
-                        let vec_stream = VecByteStream::new(vec_bytes);
-                        req.s3ext.vec_stream = Some(vec_stream);
+                        let file_stream = PostObjectStream::new(file_stream, file_size, min_file_size, max_file_size);
+                        req.s3ext.file_stream = Some(file_stream);
                         break 'resolve (&PutObject as &'static dyn Operation, false);
                     }
                     // FIXME: POST /bucket/key hits this branch
@@ -409,10 +589,12 @@ async fn prepare(req: &mut Request, ccx: &CallContext<'_>) -> S3Result<Prepare>
     }
 
     debug!(op = %op.name(), ?s3_path, "resolved route");
+    req.s3ext.op = Some(op.name());
 
     if ccx.auth.is_some() {
         let mut acx = S3AccessContext {
             credentials: req.s3ext.credentials.as_ref(),
+            identity: req.s3ext.identity.as_ref(),
             s3_path,
             s3_op: &crate::S3Operation { name: op.name() },
             method: &req.method,
@@ -426,8 +608,22 @@ async fn prepare(req: &mut Request, ccx: &CallContext<'_>) -> S3Result<Prepare>
         }
     }
 
+    if let Some(cors) = ccx.cors {
+        let cors_headers = crate::cors::actual_headers(cors, ccx.s3, &req.method, &req.headers, s3_path).await;
+        req.s3ext.cors_headers = Some(Box::new(cors_headers));
+    }
+
     debug!(op = %op.name(), ?s3_path, "checked access");
 
+    if ccx.checksum_verification && matches!(op.name(), "PutObject" | "UploadPart") && req.s3ext.file_stream.is_none() {
+        let trailing_headers = req.s3ext.trailing_headers.clone();
+        if let Some(verifier) = ChecksumVerifier::new(&req.headers, trailing_headers)? {
+            let body = mem::take(&mut req.body);
+            let stream: DynByteStream = Box::pin(ChecksumStream::new(body, verifier));
+            req.body = Body::from(stream);
+        }
+    }
+
     if needs_full_body {
         extract_full_body(content_length, &mut req.body).await?;
     }
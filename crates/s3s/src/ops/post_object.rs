@@ -0,0 +1,265 @@
+//! POST Object
+//!
+//! See <https://docs.aws.amazon.com/AmazonS3/latest/API/RESTObjectPOST.html>
+
+use crate::error::StdError;
+use crate::http::FileStream;
+use crate::stream::{ByteStream, RemainingLength};
+
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use bytes::Bytes;
+use futures::Stream;
+
+/// The default maximum file size of POST Object (5 GiB)
+pub const DEFAULT_POST_OBJECT_MAX_FILE_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+
+/// File content of POST Object, passed to `PutObject` as a streaming body.
+pub struct PostObjectStream {
+    inner: FileStream,
+    file_size: Option<u64>,
+    min_file_size: u64,
+    max_file_size: u64,
+    nread: u64,
+    done: bool,
+}
+
+impl PostObjectStream {
+    pub fn new(inner: FileStream, file_size: Option<u64>, min_file_size: u64, max_file_size: u64) -> Self {
+        Self {
+            inner,
+            file_size,
+            min_file_size,
+            max_file_size,
+            nread: 0,
+            done: false,
+        }
+    }
+
+    /// Returns the file size if it is known in advance.
+    pub fn file_size(&self) -> Option<u64> {
+        self.file_size
+    }
+
+    fn fail(&mut self, err: StdError) -> Poll<Option<Result<Bytes, StdError>>> {
+        self.done = true;
+        Poll::Ready(Some(Err(err)))
+    }
+}
+
+fn malformed_post_request() -> StdError {
+    let err = s3_error!(
+        MalformedPOSTRequest,
+        "The body of your POST request is not well-formed multipart/form-data."
+    );
+    Box::new(err)
+}
+
+fn entity_too_small() -> StdError {
+    let err = s3_error!(EntityTooSmall, "Your proposed upload is smaller than the minimum allowed size.");
+    Box::new(err)
+}
+
+fn entity_too_large() -> StdError {
+    let err = s3_error!(EntityTooLarge, "Your proposed upload exceeds the maximum allowed size.");
+    Box::new(err)
+}
+
+impl Stream for PostObjectStream {
+    type Item = Result<Bytes, StdError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        match ready!(Pin::new(&mut this.inner).poll_next(cx)) {
+            Some(Ok(bytes)) => {
+                this.nread = this.nread.saturating_add(bytes.len() as u64);
+                if this.nread > this.max_file_size {
+                    return this.fail(entity_too_large());
+                }
+                if this.file_size.is_some_and(|size| this.nread > size) {
+                    return this.fail(malformed_post_request());
+                }
+                Poll::Ready(Some(Ok(bytes)))
+            }
+            Some(Err(err)) => this.fail(Box::new(err)),
+            None => {
+                this.done = true;
+                if this.file_size.is_some_and(|size| this.nread != size) {
+                    return Poll::Ready(Some(Err(malformed_post_request())));
+                }
+                if this.nread < this.min_file_size {
+                    return Poll::Ready(Some(Err(entity_too_small())));
+                }
+                Poll::Ready(None)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
+impl ByteStream for PostObjectStream {
+    fn remaining_length(&self) -> RemainingLength {
+        let remaining = self.file_size.map(|size| size.saturating_sub(self.nread));
+        match remaining.map(usize::try_from) {
+            Some(Ok(n)) => RemainingLength::new_exact(n),
+            _ => RemainingLength::unknown(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::http::transform_multipart;
+    use crate::{S3Error, S3ErrorCode};
+
+    use futures::StreamExt;
+
+    const BOUNDARY: &str = "boundary";
+
+    fn form(file_content: &str, epilogue: &str) -> String {
+        format!(
+            concat!(
+                "--{0}\r\n",
+                "Content-Disposition: form-data; name=\"key\"\r\n\r\n",
+                "file.txt\r\n",
+                "--{0}\r\n",
+                "Content-Disposition: form-data; name=\"file\"; filename=\"file.txt\"\r\n",
+                "Content-Type: text/plain\r\n\r\n",
+                "{1}\r\n",
+                "--{0}{2}",
+            ),
+            BOUNDARY, file_content, epilogue
+        )
+    }
+
+    async fn from_chunks(chunks: Vec<String>, min_file_size: u64, max_file_size: u64) -> PostObjectStream {
+        from_form(chunks, None, min_file_size, max_file_size).await
+    }
+
+    async fn from_form(
+        chunks: Vec<String>,
+        content_length: Option<u64>,
+        min_file_size: u64,
+        max_file_size: u64,
+    ) -> PostObjectStream {
+        let body_stream = futures::stream::iter(chunks.into_iter().map(|s| Ok::<_, StdError>(Bytes::from(s))));
+        let mut multipart = transform_multipart(body_stream, BOUNDARY.as_bytes()).await.unwrap();
+        if let Some(content_length) = content_length {
+            multipart.set_content_length(content_length);
+        }
+        let file_size = multipart.file_size();
+        let file_stream = multipart.take_file_stream().unwrap();
+        PostObjectStream::new(file_stream, file_size, min_file_size, max_file_size)
+    }
+
+    async fn post_object_stream(file_content: &str, min_file_size: u64, max_file_size: u64) -> PostObjectStream {
+        from_chunks(vec![form(file_content, "--\r\n")], min_file_size, max_file_size).await
+    }
+
+    async fn collect(mut stream: PostObjectStream) -> Result<Vec<u8>, S3Error> {
+        let mut buf = Vec::new();
+        while let Some(result) = stream.next().await {
+            let bytes = result.map_err(|e| *e.downcast::<S3Error>().unwrap())?;
+            buf.extend_from_slice(&bytes);
+        }
+        Ok(buf)
+    }
+
+    #[tokio::test]
+    async fn streaming() {
+        let file_content = "hello, world";
+        let stream = post_object_stream(file_content, 0, DEFAULT_POST_OBJECT_MAX_FILE_SIZE).await;
+        assert_eq!(stream.file_size(), Some(file_content.len() as u64));
+        assert_eq!(stream.remaining_length().exact(), Some(file_content.len()));
+
+        let bytes = collect(stream).await.unwrap();
+        assert_eq!(bytes, file_content.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn too_large() {
+        let stream = post_object_stream("hello, world", 0, 5).await;
+        let err = collect(stream).await.unwrap_err();
+        assert_eq!(err.code(), &S3ErrorCode::EntityTooLarge);
+    }
+
+    #[tokio::test]
+    async fn too_small() {
+        let stream = post_object_stream("hello, world", 100, DEFAULT_POST_OBJECT_MAX_FILE_SIZE).await;
+        let err = collect(stream).await.unwrap_err();
+        assert_eq!(err.code(), &S3ErrorCode::EntityTooSmall);
+    }
+
+    #[tokio::test]
+    async fn form_layouts() {
+        let file_content = "hello, world";
+        let epilogues = [
+            // missing the final CRLF
+            "--",
+            // with an epilogue
+            "--\r\nepilogue\r\n",
+            // with fields after the file
+            "\r\nContent-Disposition: form-data; name=\"acl\"\r\n\r\nprivate\r\n--boundary--\r\n",
+        ];
+        for epilogue in epilogues {
+            let stream = from_chunks(vec![form(file_content, epilogue)], 0, DEFAULT_POST_OBJECT_MAX_FILE_SIZE).await;
+            assert_eq!(stream.file_size(), Some(file_content.len() as u64));
+
+            let bytes = collect(stream).await.unwrap();
+            assert_eq!(bytes, file_content.as_bytes());
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_size() {
+        let file_content = "hello, world";
+        let body = form(file_content, "--\r\n");
+        let (head, tail) = body.split_at(body.find(file_content).unwrap() + 5);
+        let stream = from_chunks(vec![head.to_owned(), tail.to_owned()], 0, DEFAULT_POST_OBJECT_MAX_FILE_SIZE).await;
+        assert_eq!(stream.file_size(), None);
+        assert_eq!(stream.remaining_length().exact(), None);
+
+        let bytes = collect(stream).await.unwrap();
+        assert_eq!(bytes, file_content.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn size_from_content_length() {
+        let file_content = "hello, world";
+        let body = form(file_content, "--\r\n");
+        let content_length = body.len() as u64;
+        let (head, tail) = body.split_at(body.find(file_content).unwrap() + 5);
+        let chunks = vec![head.to_owned(), tail.to_owned()];
+        let stream = from_form(chunks, Some(content_length), 0, DEFAULT_POST_OBJECT_MAX_FILE_SIZE).await;
+        assert_eq!(stream.file_size(), Some(file_content.len() as u64));
+        assert_eq!(stream.remaining_length().exact(), Some(file_content.len()));
+
+        let bytes = collect(stream).await.unwrap();
+        assert_eq!(bytes, file_content.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn size_from_content_length_mismatch() {
+        let file_content = "hello, world";
+        let body = form(file_content, "--\r\nepilogue\r\n");
+        let content_length = body.len() as u64;
+        let (head, tail) = body.split_at(body.find(file_content).unwrap() + 5);
+        let chunks = vec![head.to_owned(), tail.to_owned()];
+        let stream = from_form(chunks, Some(content_length), 0, DEFAULT_POST_OBJECT_MAX_FILE_SIZE).await;
+        assert_eq!(stream.file_size(), Some(file_content.len() as u64 + 10));
+
+        let err = collect(stream).await.unwrap_err();
+        assert_eq!(err.code(), &S3ErrorCode::MalformedPOSTRequest);
+    }
+}
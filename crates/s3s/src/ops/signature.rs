@@ -1,9 +1,15 @@
+use crate::access_log::{AuthType, SignatureVersion};
+use crate::auth::Identity;
 use crate::auth::S3Auth;
 use crate::auth::SecretKey;
+use crate::auth::SignaturePolicy;
+use crate::dto::{Timestamp, TimestampFormat};
 use crate::error::*;
 use crate::http;
-use crate::http::{AwsChunkedStream, Body, Multipart};
+use crate::http::{AwsChunkedStream, Body, ContentSha256Stream, Multipart};
 use crate::http::{OrderedHeaders, OrderedQs};
+use crate::path::S3Path;
+use crate::post_policy::PostPolicy;
 use crate::protocol::TrailingHeaders;
 use crate::sig_v2;
 use crate::sig_v2::{AuthorizationV2, PostSignatureV2, PresignedUrlV2};
@@ -12,12 +18,13 @@ use crate::sig_v4::PostSignatureV4;
 use crate::sig_v4::PresignedUrlV4;
 use crate::sig_v4::{AmzContentSha256, AmzDate};
 use crate::sig_v4::{AuthorizationV4, CredentialV4};
+use crate::stream::DynByteStream;
+use crate::utils::crypto::hex_sha256;
 use crate::utils::is_base64_encoded;
 
 use std::mem;
 use std::ops::Not;
 
-use bytestring::ByteString;
 use hyper::Method;
 use hyper::Uri;
 use mime::Mime;
@@ -28,7 +35,7 @@ fn extract_amz_content_sha256<'a>(hs: &'_ OrderedHeaders<'a>) -> S3Result<Option
     match AmzContentSha256::parse(val) {
         Ok(x) => Ok(Some(x)),
         Err(e) => {
-            let mut err: S3Error = S3ErrorCode::Custom(ByteString::from_static("XAmzContentSHA256Mismatch")).into();
+            let mut err = http::content_sha256_mismatch();
             err.set_message("invalid header: x-amz-content-sha256");
             err.set_source(Box::new(e));
             Err(err)
@@ -54,10 +61,12 @@ fn extract_amz_date(hs: &'_ OrderedHeaders<'_>) -> S3Result<Option<AmzDate>> {
 
 pub struct SignatureContext<'a> {
     pub auth: Option<&'a dyn S3Auth>,
+    pub policy: &'a SignaturePolicy,
 
     pub req_version: ::http::Version,
     pub req_method: &'a Method,
     pub req_uri: &'a Uri,
+    pub req_uri_path: &'a str,
     pub req_body: &'a mut Body,
 
     pub qs: Option<&'a OrderedQs>,
@@ -65,6 +74,8 @@ pub struct SignatureContext<'a> {
 
     pub decoded_uri_path: String,
     pub vh_bucket: Option<&'a str>,
+    pub region: Option<&'a str>,
+    pub s3_path: &'a S3Path,
 
     pub content_length: Option<u64>,
     pub mime: Option<Mime>,
@@ -72,13 +83,19 @@ pub struct SignatureContext<'a> {
 
     pub transformed_body: Option<Body>,
     pub multipart: Option<Multipart>,
+    pub post_policy: Option<PostPolicy>,
 
     pub trailing_headers: Option<TrailingHeaders>,
+
+    pub signature_version: Option<SignatureVersion>,
+    pub auth_type: Option<AuthType>,
 }
 
 pub struct CredentialsExt {
     pub access_key: String,
     pub secret_key: SecretKey,
+    pub session_token: Option<String>,
+    pub identity: Option<Identity>,
     pub region: Option<String>,
     pub service: Option<String>,
 }
@@ -87,6 +104,21 @@ fn require_auth(auth: Option<&dyn S3Auth>) -> S3Result<&dyn S3Auth> {
     auth.ok_or_else(|| s3_error!(NotImplemented, "This service has no authentication provider"))
 }
 
+/// Checks the region of a credential scope against the region of the service.
+fn check_region(expected: Option<&str>, region: &str, code: S3ErrorCode, msg: &str) -> S3Result<()> {
+    let Some(expected) = expected else { return Ok(()) };
+    if region == expected {
+        return Ok(());
+    }
+    let mut err = S3Error::with_message(code, format!("{msg}; the region '{region}' is wrong; expecting '{expected}'"));
+    err.set_region(expected);
+    Err(err)
+}
+
+fn unsupported_auth_mechanism() -> S3Error {
+    invalid_request!("The authorization mechanism you have provided is not supported. Please use AWS4-HMAC-SHA256.")
+}
+
 impl SignatureContext<'_> {
     pub async fn check(&mut self) -> S3Result<Option<CredentialsExt>> {
         if self.req_method == Method::POST {
@@ -120,32 +152,58 @@ impl SignatureContext<'_> {
                 .ok_or_else(|| invalid_request!("missing boundary"))?;
 
             let body = mem::take(self.req_body);
-            http::transform_multipart(body, boundary.as_str().as_bytes())
+            let mut multipart = http::transform_multipart(body, boundary.as_str().as_bytes())
                 .await
-                .map_err(|e| s3_error!(e, MalformedPOSTRequest))?
+                .map_err(|e| s3_error!(e, MalformedPOSTRequest))?;
+
+            if let Some(content_length) = self.content_length {
+                multipart.set_content_length(content_length);
+            }
+
+            multipart
         };
 
         debug!(?multipart);
 
         if multipart.find_field_value("x-amz-signature").is_some() {
             debug!("checking post signature v4");
+            self.signature_version = Some(SignatureVersion::V4);
             return self.v4_check_post_signature(multipart).await;
         }
 
         if multipart.find_field_value("signature").is_some() {
+            if self.policy.allow_sigv2.not() {
+                return Err(unsupported_auth_mechanism());
+            }
             debug!("checking post signature v2");
+            self.signature_version = Some(SignatureVersion::V2);
             return self.v2_check_post_signature(multipart).await;
         }
 
         Err(invalid_request!("unsupported post signature"))
     }
 
+    fn check_post_policy(&self, multipart: &Multipart, policy: &str) -> S3Result<PostPolicy> {
+        let policy =
+            PostPolicy::from_base64(policy).map_err(|e| s3_error!(e, InvalidPolicyDocument, "Invalid Policy: Invalid JSON."))?;
+
+        policy.check_expiration(time::OffsetDateTime::now_utc())?;
+
+        let bucket = self.s3_path.get_bucket_name().unwrap_or_default();
+        let file_size = multipart.file_size();
+        policy.check_conditions(multipart, bucket, file_size)?;
+
+        Ok(policy)
+    }
+
     #[tracing::instrument(skip(self))]
     pub async fn v4_check(&mut self) -> Option<S3Result<CredentialsExt>> {
         // query auth
         if let Some(qs) = self.qs {
             if qs.has("X-Amz-Signature") {
                 debug!("checking presigned url");
+                self.signature_version = Some(SignatureVersion::V4);
+                self.auth_type = Some(AuthType::QueryString);
                 return Some(self.v4_check_presigned_url().await);
             }
         }
@@ -153,6 +211,8 @@ impl SignatureContext<'_> {
         // header auth
         if self.hs.get_unique(crate::header::AUTHORIZATION).is_some() {
             debug!("checking header auth");
+            self.signature_version = Some(SignatureVersion::V4);
+            self.auth_type = Some(AuthType::AuthHeader);
             return Some(self.v4_check_header_auth().await);
         }
 
@@ -180,8 +240,16 @@ impl SignatureContext<'_> {
 
         let amz_date = AmzDate::parse(info.x_amz_date).map_err(|_| invalid_request!("invalid field: x-amz-date"))?;
 
+        check_region(
+            self.region,
+            credential.aws_region,
+            S3ErrorCode::AuthorizationHeaderMalformed,
+            "Error parsing the X-Amz-Credential field",
+        )?;
+
         let access_key = credential.access_key_id.to_owned();
-        let secret_key = auth.get_secret_key(&access_key).await?;
+        let session_token = multipart.find_field_value("x-amz-security-token").map(ToOwned::to_owned);
+        let (secret_key, identity) = auth.get_identity(&access_key, session_token.as_deref()).await?;
 
         let region = credential.aws_region;
         let service = credential.aws_service;
@@ -195,13 +263,18 @@ impl SignatureContext<'_> {
             return Err(s3_error!(SignatureDoesNotMatch));
         }
 
+        let post_policy = self.check_post_policy(&multipart, info.policy)?;
+
         let region = region.to_owned();
         let service = service.to_owned();
 
         self.multipart = Some(multipart);
+        self.post_policy = Some(post_policy);
         Ok(CredentialsExt {
             access_key,
             secret_key,
+            session_token,
+            identity,
             region: Some(region),
             service: Some(service),
         })
@@ -212,6 +285,14 @@ impl SignatureContext<'_> {
 
         let presigned_url = PresignedUrlV4::parse(qs).map_err(|err| invalid_request!(err, "missing presigned url v4 fields"))?;
 
+        if presigned_url.expires > self.policy.max_presigned_url_expires() {
+            return Err(s3_error!(
+                AuthorizationQueryParametersError,
+                "X-Amz-Expires must be less than {} seconds.",
+                self.policy.max_presigned_url_expires.as_secs()
+            ));
+        }
+
         if presigned_url.algorithm != "AWS4-HMAC-SHA256" {
             return Err(s3_error!(
                 NotImplemented,
@@ -233,13 +314,11 @@ impl SignatureContext<'_> {
 
             let duration = now - date;
 
-            // Allow requests that are up to 15 minutes in the future.
+            // Allow requests that are slightly in the future.
             // This is to account for clock skew between the client and server.
             // See also https://github.com/minio/minio/blob/b5177993b371817699d3fa25685f54f88d8bfcce/cmd/signature-v4.go#L238-L242
 
-            // TODO: configurable max_skew_time
-
-            let max_skew_time = time::Duration::seconds(15 * 60);
+            let max_skew_time = self.policy.max_skew_time();
             if duration.is_negative() && duration.abs() > max_skew_time {
                 return Err(s3_error!(RequestTimeTooSkewed, "request date is later than server time too much"));
             }
@@ -249,9 +328,17 @@ impl SignatureContext<'_> {
             }
         }
 
+        check_region(
+            self.region,
+            presigned_url.credential.aws_region,
+            S3ErrorCode::AuthorizationQueryParametersError,
+            "Error parsing the X-Amz-Credential parameter",
+        )?;
+
         let auth = require_auth(self.auth)?;
         let access_key = presigned_url.credential.access_key_id;
-        let secret_key = auth.get_secret_key(access_key).await?;
+        let session_token = qs.get_unique("X-Amz-Security-Token").map(ToOwned::to_owned);
+        let (secret_key, identity) = auth.get_identity(access_key, session_token.as_deref()).await?;
 
         let region = presigned_url.credential.aws_region;
         let service = presigned_url.credential.aws_service;
@@ -289,6 +376,8 @@ impl SignatureContext<'_> {
         Ok(CredentialsExt {
             access_key: access_key.into(),
             secret_key,
+            session_token,
+            identity,
             region: Some(region.into()),
             service: Some(service.into()),
         })
@@ -303,6 +392,11 @@ impl SignatureContext<'_> {
             a.signed_headers.sort_unstable();
             a
         };
+
+        if authorization.algorithm != "AWS4-HMAC-SHA256" {
+            return Err(unsupported_auth_mechanism());
+        }
+
         let region = authorization.credential.aws_region;
         let service = authorization.credential.aws_service;
 
@@ -310,6 +404,13 @@ impl SignatureContext<'_> {
             return Err(s3_error!(NotImplemented, "unknown service"));
         }
 
+        check_region(
+            self.region,
+            region,
+            S3ErrorCode::AuthorizationHeaderMalformed,
+            "The authorization header is malformed",
+        )?;
+
         let auth = require_auth(self.auth)?;
 
         let amz_content_sha256 = extract_amz_content_sha256(&self.hs)?;
@@ -319,9 +420,11 @@ impl SignatureContext<'_> {
         }
 
         let access_key = authorization.credential.access_key_id;
-        let secret_key = auth.get_secret_key(access_key).await?;
+        let session_token = self.hs.get_unique("x-amz-security-token").map(ToOwned::to_owned);
+        let (secret_key, identity) = auth.get_identity(access_key, session_token.as_deref()).await?;
 
         let amz_date = extract_amz_date(&self.hs)?.ok_or_else(|| invalid_request!("missing header: x-amz-date"))?;
+        self.check_request_time(&amz_date)?;
 
         let is_stream = matches!(
             amz_content_sha256,
@@ -374,20 +477,13 @@ impl SignatureContext<'_> {
                     &headers,
                     sig_v4::Payload::UnsignedMultipleChunksWithTrailer,
                 ),
-                Some(AmzContentSha256::SingleChunk { .. }) => {
-                    let bytes = super::extract_full_body(self.content_length, self.req_body).await?;
-                    if bytes.is_empty() {
-                        sig_v4::create_canonical_request(method, uri_path, query_strings, &headers, sig_v4::Payload::Empty)
-                    } else {
-                        sig_v4::create_canonical_request(
-                            method,
-                            uri_path,
-                            query_strings,
-                            &headers,
-                            sig_v4::Payload::SingleChunk(&bytes),
-                        )
-                    }
-                }
+                Some(AmzContentSha256::SingleChunk { payload_checksum }) => sig_v4::create_canonical_request(
+                    method,
+                    uri_path,
+                    query_strings,
+                    &headers,
+                    sig_v4::Payload::SingleChunkHash(payload_checksum),
+                ),
                 None => {
                     if matches!(*self.req_method, Method::GET | Method::HEAD) {
                         sig_v4::create_canonical_request(method, uri_path, query_strings, &headers, sig_v4::Payload::Empty)
@@ -417,6 +513,10 @@ impl SignatureContext<'_> {
             return Err(s3_error!(SignatureDoesNotMatch));
         }
 
+        if let Some(AmzContentSha256::SingleChunk { payload_checksum }) = amz_content_sha256 {
+            self.check_payload_checksum(payload_checksum)?;
+        }
+
         if is_stream {
             // For streaming with trailers, AWS requires x-amz-trailer header present.
             let has_trailer = matches!(
@@ -454,17 +554,59 @@ impl SignatureContext<'_> {
         Ok(CredentialsExt {
             access_key: access_key.into(),
             secret_key,
+            session_token,
+            identity,
             region: Some(region.into()),
             service: Some(service.into()),
         })
     }
 
+    /// Rejects header auth requests whose date is too far from the server time.
+    fn check_request_time(&self, amz_date: &AmzDate) -> S3Result<()> {
+        let date = amz_date.to_time().ok_or_else(|| invalid_request!("invalid amz date"))?;
+        self.check_request_skew(date)
+    }
+
+    fn check_request_skew(&self, date: time::OffsetDateTime) -> S3Result<()> {
+        let skew = (time::OffsetDateTime::now_utc() - date).abs();
+        if skew > self.policy.max_skew_time() {
+            return Err(s3_error!(
+                RequestTimeTooSkewed,
+                "The difference between the request time and the current time is too large."
+            ));
+        }
+        Ok(())
+    }
+
+    /// Verifies the body against the signed payload checksum.
+    ///
+    /// A body in memory is checked immediately.
+    /// A streaming body is hashed while it is read by the operation.
+    fn check_payload_checksum(&mut self, payload_checksum: &str) -> S3Result<()> {
+        if let Some(bytes) = self.req_body.bytes() {
+            if hex_sha256(&bytes, |s| s != payload_checksum) {
+                return Err(http::content_sha256_mismatch());
+            }
+            return Ok(());
+        }
+
+        let body = mem::take(self.req_body);
+        let stream = ContentSha256Stream::new(body, payload_checksum);
+        *self.req_body = Body::from(Box::pin(stream) as DynByteStream);
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self))]
     pub async fn v2_check(&mut self) -> Option<S3Result<CredentialsExt>> {
         // query auth
         if let Some(qs) = self.qs {
             if qs.has("Signature") {
+                if self.policy.allow_sigv2.not() {
+                    return Some(Err(unsupported_auth_mechanism()));
+                }
                 debug!("checking presigned url");
+                self.signature_version = Some(SignatureVersion::V2);
+                self.auth_type = Some(AuthType::QueryString);
                 return Some(self.v2_check_presigned_url().await);
             }
         }
@@ -472,7 +614,12 @@ impl SignatureContext<'_> {
         // header auth
         if let Some(auth) = self.hs.get_unique(crate::header::AUTHORIZATION) {
             if let Ok(auth) = AuthorizationV2::parse(auth) {
+                if self.policy.allow_sigv2.not() {
+                    return Some(Err(unsupported_auth_mechanism()));
+                }
                 debug!("checking header auth");
+                self.signature_version = Some(SignatureVersion::V2);
+                self.auth_type = Some(AuthType::AuthHeader);
                 return Some(self.v2_check_header_auth(auth).await);
             }
         }
@@ -483,23 +630,19 @@ impl SignatureContext<'_> {
     pub async fn v2_check_header_auth(&mut self, auth_v2: AuthorizationV2<'_>) -> S3Result<CredentialsExt> {
         let method = &self.req_method;
 
-        let date = self.hs.get_unique("date").or_else(|| self.hs.get_unique("x-amz-date"));
-        if date.is_none() {
+        let Some(date) = self.hs.get_unique("date").or_else(|| self.hs.get_unique("x-amz-date")) else {
             return Err(invalid_request!("missing date"));
-        }
+        };
+        let date = Timestamp::parse(TimestampFormat::HttpDate, date).map_err(|e| invalid_request!(e, "invalid header: date"))?;
+        self.check_request_skew(date.into())?;
 
         let auth = require_auth(self.auth)?;
         let access_key = auth_v2.access_key;
-        let secret_key = auth.get_secret_key(access_key).await?;
+        let session_token = self.hs.get_unique("x-amz-security-token").map(ToOwned::to_owned);
+        let (secret_key, identity) = auth.get_identity(access_key, session_token.as_deref()).await?;
 
-        let string_to_sign = sig_v2::create_string_to_sign(
-            sig_v2::Mode::HeaderAuth,
-            method,
-            self.req_uri.path(),
-            self.qs,
-            &self.hs,
-            self.vh_bucket,
-        );
+        let string_to_sign =
+            sig_v2::create_string_to_sign(sig_v2::Mode::HeaderAuth, method, self.req_uri_path, self.qs, &self.hs, self.vh_bucket);
         let signature = sig_v2::calculate_signature(&secret_key, &string_to_sign);
 
         debug!(?string_to_sign, "sig_v2 header_auth");
@@ -513,6 +656,8 @@ impl SignatureContext<'_> {
         Ok(CredentialsExt {
             access_key: access_key.into(),
             secret_key,
+            session_token,
+            identity,
             region: None,
             service: Some("s3".into()),
         })
@@ -528,7 +673,8 @@ impl SignatureContext<'_> {
         }
 
         let access_key = info.access_key_id.to_owned();
-        let secret_key = auth.get_secret_key(&access_key).await?;
+        let session_token = multipart.find_field_value("x-amz-security-token").map(ToOwned::to_owned);
+        let (secret_key, identity) = auth.get_identity(&access_key, session_token.as_deref()).await?;
 
         // For v2 POST signature, the string to sign is the base64-encoded policy
         let string_to_sign = info.policy;
@@ -540,10 +686,15 @@ impl SignatureContext<'_> {
             return Err(s3_error!(SignatureDoesNotMatch));
         }
 
+        let post_policy = self.check_post_policy(&multipart, info.policy)?;
+
         self.multipart = Some(multipart);
+        self.post_policy = Some(post_policy);
         Ok(CredentialsExt {
             access_key,
             secret_key,
+            session_token,
+            identity,
             region: None,
             service: Some("s3".into()),
         })
@@ -553,18 +704,27 @@ impl SignatureContext<'_> {
         let qs = self.qs.unwrap(); // assume: qs has "Signature"
         let presigned_url = PresignedUrlV2::parse(qs).map_err(|err| invalid_request!(err, "missing presigned url v2 fields"))?;
 
-        if time::OffsetDateTime::now_utc() > presigned_url.expires_time {
+        let now = time::OffsetDateTime::now_utc();
+        if now > presigned_url.expires_time {
             return Err(s3_error!(AccessDenied, "Request has expired"));
         }
+        if presigned_url.expires_time - now > self.policy.max_presigned_url_expires() {
+            return Err(s3_error!(
+                AuthorizationQueryParametersError,
+                "Expires must be less than {} seconds from now.",
+                self.policy.max_presigned_url_expires.as_secs()
+            ));
+        }
 
         let auth = require_auth(self.auth)?;
         let access_key = presigned_url.access_key;
-        let secret_key = auth.get_secret_key(access_key).await?;
+        let session_token = qs.get_unique("x-amz-security-token").map(ToOwned::to_owned);
+        let (secret_key, identity) = auth.get_identity(access_key, session_token.as_deref()).await?;
 
         let string_to_sign = sig_v2::create_string_to_sign(
             sig_v2::Mode::PresignedUrl,
             self.req_method,
-            self.req_uri.path(),
+            self.req_uri_path,
             self.qs,
             &self.hs,
             self.vh_bucket,
@@ -580,6 +740,8 @@ impl SignatureContext<'_> {
         Ok(CredentialsExt {
             access_key: access_key.into(),
             secret_key,
+            session_token,
+            identity,
             region: None,
             service: Some("s3".into()),
         })
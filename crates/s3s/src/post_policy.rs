@@ -0,0 +1,349 @@
+//! POST policy
+//!
+//! See <https://docs.aws.amazon.com/AmazonS3/latest/API/sigv4-HTTPPOSTConstructPolicy.html>
+//!
+
+use crate::error::*;
+use crate::http::Multipart;
+
+use std::fmt;
+
+use serde::Deserialize;
+use serde_json::Value;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+/// POST policy
+#[derive(Debug)]
+pub struct PostPolicy {
+    /// expiration
+    expiration: OffsetDateTime,
+    /// conditions
+    conditions: Vec<Condition>,
+}
+
+/// POST policy condition
+#[derive(Debug)]
+enum Condition {
+    /// `{"field": "value"}` or `["eq", "$field", "value"]`
+    Eq { name: String, value: String },
+    /// `["starts-with", "$field", "prefix"]`
+    StartsWith { name: String, prefix: String },
+    /// `["content-length-range", min, max]`
+    ContentLengthRange { min: u64, max: u64 },
+}
+
+/// [`PostPolicy`]
+#[derive(Debug, thiserror::Error)]
+pub enum ParsePostPolicyError {
+    #[error("ParsePostPolicyError: InvalidBase64")]
+    InvalidBase64,
+    #[error("ParsePostPolicyError: InvalidJson: {0}")]
+    InvalidJson(serde_json::Error),
+    #[error("ParsePostPolicyError: InvalidExpiration")]
+    InvalidExpiration,
+    #[error("ParsePostPolicyError: InvalidCondition")]
+    InvalidCondition,
+}
+
+#[derive(Deserialize)]
+struct PolicyDocument {
+    expiration: String,
+    conditions: Vec<Value>,
+}
+
+/// Form fields which are not required to appear in the conditions
+fn is_exempt_field(name: &str) -> bool {
+    matches!(name, "policy" | "x-amz-signature" | "signature" | "awsaccesskeyid" | "file") || name.starts_with("x-ignore-")
+}
+
+fn parse_field_name(s: &str) -> Option<String> {
+    let name = s.strip_prefix('$')?;
+    Some(name.to_ascii_lowercase())
+}
+
+fn parse_length(v: &Value) -> Option<u64> {
+    match v {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+impl Condition {
+    fn parse_array(arr: &[Value]) -> Option<Self> {
+        let [op, a, b] = arr else { return None };
+        let op = op.as_str()?;
+        if op.eq_ignore_ascii_case("eq") {
+            let name = parse_field_name(a.as_str()?)?;
+            let value = b.as_str()?.to_owned();
+            return Some(Self::Eq { name, value });
+        }
+        if op.eq_ignore_ascii_case("starts-with") {
+            let name = parse_field_name(a.as_str()?)?;
+            let prefix = b.as_str()?.to_owned();
+            return Some(Self::StartsWith { name, prefix });
+        }
+        if op.eq_ignore_ascii_case("content-length-range") {
+            let min = parse_length(a)?;
+            let max = parse_length(b)?;
+            return (min <= max).then_some(Self::ContentLengthRange { min, max });
+        }
+        None
+    }
+
+    fn field_name(&self) -> Option<&str> {
+        match self {
+            Self::Eq { name, .. } | Self::StartsWith { name, .. } => Some(name),
+            Self::ContentLengthRange { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Eq { name, value } => write!(f, "[\"eq\", \"${name}\", \"{value}\"]"),
+            Self::StartsWith { name, prefix } => write!(f, "[\"starts-with\", \"${name}\", \"{prefix}\"]"),
+            Self::ContentLengthRange { min, max } => write!(f, "[\"content-length-range\", {min}, {max}]"),
+        }
+    }
+}
+
+impl PostPolicy {
+    /// Parses the base64-encoded policy field
+    ///
+    /// # Errors
+    /// Returns an error if the policy is not a valid POST policy document
+    pub fn from_base64(policy: &str) -> Result<Self, ParsePostPolicyError> {
+        let json = base64_simd::STANDARD
+            .decode_to_vec(policy)
+            .map_err(|_| ParsePostPolicyError::InvalidBase64)?;
+        Self::from_json(&json)
+    }
+
+    fn from_json(json: &[u8]) -> Result<Self, ParsePostPolicyError> {
+        let doc: PolicyDocument = serde_json::from_slice(json).map_err(ParsePostPolicyError::InvalidJson)?;
+
+        let expiration = OffsetDateTime::parse(&doc.expiration, &Rfc3339).map_err(|_| ParsePostPolicyError::InvalidExpiration)?;
+
+        let mut conditions = Vec::with_capacity(doc.conditions.len());
+        for cond in &doc.conditions {
+            match cond {
+                Value::Object(map) => {
+                    for (name, value) in map {
+                        let value = value.as_str().ok_or(ParsePostPolicyError::InvalidCondition)?;
+                        conditions.push(Condition::Eq {
+                            name: name.to_ascii_lowercase(),
+                            value: value.to_owned(),
+                        });
+                    }
+                }
+                Value::Array(arr) => {
+                    let cond = Condition::parse_array(arr).ok_or(ParsePostPolicyError::InvalidCondition)?;
+                    conditions.push(cond);
+                }
+                _ => return Err(ParsePostPolicyError::InvalidCondition),
+            }
+        }
+
+        Ok(Self { expiration, conditions })
+    }
+
+    /// Returns the allowed range of the file size, if specified
+    pub fn content_length_range(&self) -> Option<(u64, u64)> {
+        let mut ans: Option<(u64, u64)> = None;
+        for cond in &self.conditions {
+            if let Condition::ContentLengthRange { min, max } = *cond {
+                ans = Some(match ans {
+                    Some((lo, hi)) => (lo.max(min), hi.min(max)),
+                    None => (min, max),
+                });
+            }
+        }
+        ans
+    }
+
+    /// Checks the expiration of the policy
+    ///
+    /// # Errors
+    /// Returns `AccessDenied` if the policy has expired
+    pub fn check_expiration(&self, now: OffsetDateTime) -> S3Result<()> {
+        if now > self.expiration {
+            return Err(s3_error!(AccessDenied, "Invalid according to Policy: Policy expired."));
+        }
+        Ok(())
+    }
+
+    /// Checks the form fields and the file size against the conditions
+    ///
+    /// + `bucket` is the bucket in the request path.
+    /// + `file_size` is the file size if it is known in advance.
+    ///
+    /// # Errors
+    /// + Returns `AccessDenied` if any condition fails or any form field is not allowed.
+    /// + Returns `EntityTooSmall` or `EntityTooLarge` if the file size is out of range.
+    pub fn check_conditions(&self, multipart: &Multipart, bucket: &str, file_size: Option<u64>) -> S3Result<()> {
+        let field_value = |name: &str| -> &str {
+            if name == "bucket" {
+                return bucket;
+            }
+            multipart.find_field_value(name).unwrap_or_default()
+        };
+
+        for cond in &self.conditions {
+            let ok = match cond {
+                Condition::Eq { name, value } => field_value(name) == value,
+                Condition::StartsWith { name, prefix } => field_value(name).starts_with(prefix.as_str()),
+                Condition::ContentLengthRange { .. } => continue,
+            };
+            if !ok {
+                return Err(s3_error!(AccessDenied, "Invalid according to Policy: Policy Condition failed: {cond}"));
+            }
+        }
+
+        for (name, _) in multipart.fields() {
+            if is_exempt_field(name) {
+                continue;
+            }
+            if self.conditions.iter().all(|cond| cond.field_name() != Some(name)) {
+                return Err(s3_error!(AccessDenied, "Invalid according to Policy: Extra input fields: {name}"));
+            }
+        }
+
+        if let (Some(size), Some((min, max))) = (file_size, self.content_length_range()) {
+            if size < min {
+                return Err(s3_error!(
+                    EntityTooSmall,
+                    "Your proposed upload is smaller than the minimum allowed size."
+                ));
+            }
+            if size > max {
+                return Err(s3_error!(EntityTooLarge, "Your proposed upload exceeds the maximum allowed size."));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::http::transform_multipart;
+
+    use std::fmt::Write as _;
+
+    use bytes::Bytes;
+
+    async fn multipart(fields: &[(&str, &str)]) -> Multipart {
+        let boundary = "boundary";
+        let mut body = String::new();
+        for (name, value) in fields {
+            write!(body, "--{boundary}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n").unwrap();
+        }
+        write!(
+            body,
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\n\r\nhello\r\n--{boundary}--\r\n"
+        )
+        .unwrap();
+        let body_stream = futures::stream::iter([Ok::<_, StdError>(Bytes::from(body))]);
+        transform_multipart(body_stream, boundary.as_bytes()).await.unwrap()
+    }
+
+    fn policy(json: &str) -> PostPolicy {
+        PostPolicy::from_base64(&base64_simd::STANDARD.encode_to_string(json)).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let p = policy(
+            r#"{
+                "expiration": "2007-12-01T12:00:00.000Z",
+                "conditions": [
+                    {"acl": "public-read"},
+                    {"bucket": "johnsmith"},
+                    ["starts-with", "$key", "user/eric/"],
+                    ["content-length-range", 1048579, "10485760"]
+                ]
+            }"#,
+        );
+        assert_eq!(p.expiration.unix_timestamp(), 1_196_510_400);
+        assert_eq!(p.conditions.len(), 4);
+        assert_eq!(p.content_length_range(), Some((1_048_579, 10_485_760)));
+        assert_eq!(p.conditions[2].to_string(), r#"["starts-with", "$key", "user/eric/"]"#);
+
+        let invalid = [
+            r#"{"conditions": []}"#,
+            r#"{"expiration": "tomorrow", "conditions": []}"#,
+            r#"{"expiration": "2007-12-01T12:00:00.000Z", "conditions": [["eq", "key", "a"]]}"#,
+            r#"{"expiration": "2007-12-01T12:00:00.000Z", "conditions": [["content-length-range", 2, 1]]}"#,
+            r#"{"expiration": "2007-12-01T12:00:00.000Z", "conditions": [["unknown", "$key", "a"]]}"#,
+        ];
+        for json in invalid {
+            let b64 = base64_simd::STANDARD.encode_to_string(json);
+            assert!(PostPolicy::from_base64(&b64).is_err(), "{json}");
+        }
+        assert!(PostPolicy::from_base64("not base64").is_err());
+    }
+
+    #[test]
+    fn expiration() {
+        let p = policy(r#"{"expiration": "2007-12-01T12:00:00.000Z", "conditions": []}"#);
+        assert!(p.check_expiration(p.expiration - time::Duration::SECOND).is_ok());
+        let err = p.check_expiration(p.expiration + time::Duration::SECOND).unwrap_err();
+        assert_eq!(err.code(), &S3ErrorCode::AccessDenied);
+    }
+
+    #[tokio::test]
+    async fn conditions() {
+        let p = policy(
+            r#"{
+                "expiration": "2007-12-01T12:00:00.000Z",
+                "conditions": [
+                    {"bucket": "johnsmith"},
+                    ["starts-with", "$key", "user/eric/"],
+                    ["eq", "$Content-Type", "text/plain"],
+                    ["starts-with", "$x-amz-meta-tag", ""],
+                    ["content-length-range", 1, 10]
+                ]
+            }"#,
+        );
+
+        let fields = [
+            ("key", "user/eric/a.txt"),
+            ("Content-Type", "text/plain"),
+            ("x-amz-meta-tag", "anything"),
+            ("policy", "..."),
+            ("x-amz-signature", "..."),
+            ("x-ignore-foo", "bar"),
+        ];
+        let m = multipart(&fields).await;
+        assert!(p.check_conditions(&m, "johnsmith", Some(5)).is_ok());
+        assert!(p.check_conditions(&m, "johnsmith", None).is_ok());
+
+        let err = p.check_conditions(&m, "other", Some(5)).unwrap_err();
+        assert_eq!(err.code(), &S3ErrorCode::AccessDenied);
+
+        let err = p.check_conditions(&m, "johnsmith", Some(0)).unwrap_err();
+        assert_eq!(err.code(), &S3ErrorCode::EntityTooSmall);
+
+        let err = p.check_conditions(&m, "johnsmith", Some(11)).unwrap_err();
+        assert_eq!(err.code(), &S3ErrorCode::EntityTooLarge);
+
+        let m = multipart(&[("key", "user/bob/a.txt"), ("content-type", "text/plain")]).await;
+        let err = p.check_conditions(&m, "johnsmith", Some(5)).unwrap_err();
+        assert_eq!(err.code(), &S3ErrorCode::AccessDenied);
+
+        let m = multipart(&[
+            ("key", "user/eric/a.txt"),
+            ("content-type", "text/plain"),
+            ("acl", "public-read"),
+        ])
+        .await;
+        let err = p.check_conditions(&m, "johnsmith", Some(5)).unwrap_err();
+        assert_eq!(err.code(), &S3ErrorCode::AccessDenied);
+        assert_eq!(err.message(), Some("Invalid according to Policy: Extra input fields: acl"));
+    }
+}
@@ -1,6 +1,6 @@
 use crate::Body;
 use crate::StdError;
-use crate::auth::Credentials;
+use crate::auth::{Credentials, Identity};
 
 use http::Extensions;
 use http::HeaderMap;
@@ -51,7 +51,7 @@ impl TrailingHeaders {
     /// Returns true if trailers have been produced by the body stream.
     #[must_use]
     pub fn is_ready(&self) -> bool {
-        self.0.lock().map(|g| g.is_some()).unwrap_or(false)
+        self.0.lock().is_ok_and(|g| g.is_some())
     }
 
     /// Take the trailing headers if available.
@@ -77,6 +77,10 @@ impl TrailingHeaders {
 #[derive(Debug, Clone)]
 pub struct S3Request<T> {
     /// S3 operation input
+    ///
+    /// For POST Object uploads, the `content_length` of [`PutObjectInput`](crate::dto::PutObjectInput)
+    /// is derived from the form. It is `None` if the request has no `Content-Length` header
+    /// and the file is not buffered while parsing the form.
     pub input: T,
 
     /// HTTP method
@@ -96,7 +100,12 @@ pub struct S3Request<T> {
     /// `None` means anonymous request.
     pub credentials: Option<Credentials>,
 
+    /// The identity of the caller returned by the auth provider.
+    /// `None` means anonymous request or no identity.
+    pub identity: Option<Identity>,
+
     /// S3 requested region.
+    /// It is the region of the credential scope, the host, or the service.
     pub region: Option<String>,
 
     /// S3 requested service.
@@ -119,6 +128,7 @@ impl<T> S3Request<T> {
             headers: self.headers,
             extensions: self.extensions,
             credentials: self.credentials,
+            identity: self.identity,
             region: self.region,
             service: self.service,
             trailing_headers: self.trailing_headers,
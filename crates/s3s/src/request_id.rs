@@ -0,0 +1,135 @@
+//! Request ids
+//!
+//! Every request handled by [`S3Service`](crate::service::S3Service) is assigned a [`RequestId`],
+//! which is available in the extensions of [`S3Request`](crate::S3Request) and [`S3LayerRequest`](crate::layer::S3LayerRequest).
+//!
+//! The ids are returned in the `x-amz-request-id` and `x-amz-id-2` headers,
+//! and in the `RequestId` element of error responses.
+//! Headers and request ids set by the S3 implementation take precedence.
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::utils::crypto::random_bytes;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The ids of a request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId {
+    /// The value of `x-amz-request-id`
+    pub request_id: String,
+
+    /// The value of `x-amz-id-2`
+    pub host_id: String,
+}
+
+pub trait RequestIdGenerator: Send + Sync + 'static {
+    /// Generates the ids of a new request.
+    fn generate(&self) -> RequestId;
+}
+
+/// The default request id generator
+///
+/// It generates random ids in the same format as AWS S3,
+/// such as `4442587FB7D0A2F9` for request ids and base64 strings for host ids.
+/// On `wasm32` targets, which have no system random source, request ids are generated from a counter.
+#[derive(Debug, Default)]
+pub struct DefaultRequestIdGenerator {
+    counter: AtomicU64,
+}
+
+impl DefaultRequestIdGenerator {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RequestIdGenerator for DefaultRequestIdGenerator {
+    fn generate(&self) -> RequestId {
+        let counter = || self.counter.fetch_add(1, Ordering::Relaxed).to_be_bytes();
+
+        // Falls back to a counter if the system random source is unavailable
+        #[cfg(not(target_arch = "wasm32"))]
+        let (id_bytes, host_bytes): ([u8; 8], [u8; 48]) =
+            (random_bytes().unwrap_or_else(|_| counter()), random_bytes().unwrap_or([0; 48]));
+        #[cfg(target_arch = "wasm32")]
+        let (id_bytes, host_bytes): ([u8; 8], [u8; 48]) = (counter(), [0; 48]);
+
+        RequestId {
+            request_id: hex_simd::encode_to_string(id_bytes, hex_simd::AsciiCase::Upper),
+            host_id: base64_simd::STANDARD.encode_to_string(host_bytes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::header::{X_AMZ_ID_2, X_AMZ_REQUEST_ID};
+    use crate::http::Body;
+    use crate::service::S3ServiceBuilder;
+    use crate::tests::FixedGenerator;
+    use crate::{S3, S3Request, S3Response, S3Result};
+
+    use crate::dto::{GetObjectInput, GetObjectOutput, ListBucketsInput, ListBucketsOutput};
+
+    struct RequestIdS3;
+
+    #[async_trait::async_trait]
+    impl S3 for RequestIdS3 {
+        async fn list_buckets(&self, req: S3Request<ListBucketsInput>) -> S3Result<S3Response<ListBucketsOutput>> {
+            let id = req.extensions.get::<RequestId>().unwrap();
+            assert_eq!(id.request_id, "REQUEST");
+            Ok(S3Response::new(ListBucketsOutput::default()))
+        }
+
+        async fn get_object(&self, req: S3Request<GetObjectInput>) -> S3Result<S3Response<GetObjectOutput>> {
+            if req.input.key == "custom" {
+                let mut err = s3_error!(NoSuchKey);
+                err.set_request_id("CUSTOM");
+                return Err(err);
+            }
+            Err(s3_error!(NoSuchKey))
+        }
+    }
+
+    fn request(uri: &str) -> http::Request<Body> {
+        http::Request::builder().uri(uri).body(Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn default_generator() {
+        let generator = DefaultRequestIdGenerator::new();
+        let a = generator.generate();
+        let b = generator.generate();
+        assert_eq!(a.request_id.len(), 16);
+        assert!(a.request_id.bytes().all(|b| b.is_ascii_digit() || b.is_ascii_uppercase()));
+        assert_eq!(a.host_id.len(), 64);
+        assert_ne!(a, b);
+    }
+
+    #[tokio::test]
+    async fn propagate() {
+        let mut builder = S3ServiceBuilder::new(RequestIdS3);
+        builder.set_request_id_generator(FixedGenerator);
+        let service = builder.build();
+
+        let resp = service.call(request("http://localhost/")).await.unwrap();
+        assert!(resp.status().is_success());
+        assert_eq!(resp.headers().get(X_AMZ_REQUEST_ID).unwrap(), "REQUEST");
+        assert_eq!(resp.headers().get(X_AMZ_ID_2).unwrap(), "HOST");
+
+        for (key, expected) in [("key", "REQUEST"), ("custom", "CUSTOM")] {
+            let mut resp = service
+                .call(request(&format!("http://localhost/bucket/{key}")))
+                .await
+                .unwrap();
+            assert_eq!(resp.headers().get(X_AMZ_REQUEST_ID).unwrap(), expected);
+            assert_eq!(resp.headers().get(X_AMZ_ID_2).unwrap(), "HOST");
+            let body = resp.body_mut().store_all_unlimited().await.unwrap();
+            let body = std::str::from_utf8(&body).unwrap();
+            assert!(body.contains(&format!("<RequestId>{expected}</RequestId>")), "{body}");
+        }
+    }
+}
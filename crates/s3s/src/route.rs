@@ -10,6 +10,9 @@ use hyper::http::Extensions;
 
 #[async_trait::async_trait]
 pub trait S3Route: Send + Sync + 'static {
+    /// Returns whether the request is handled by this route.
+    ///
+    /// The base path of the service, if any, is stripped from `uri`.
     fn is_match(&self, method: &Method, uri: &Uri, headers: &HeaderMap, extensions: &mut Extensions) -> bool;
 
     async fn check_access(&self, req: &mut S3Request<Body>) -> S3Result<()> {
@@ -16,7 +16,6 @@ pub enum AmzContentSha256<'a> {
     /// single chunk
     SingleChunk {
         /// the checksum of single chunk payload
-        #[allow(dead_code)] // TODO: check this field when calculating the payload checksum
         payload_checksum: &'a str,
     },
     /// `UNSIGNED-PAYLOAD`
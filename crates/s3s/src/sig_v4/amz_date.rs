@@ -51,6 +51,20 @@ impl AmzDate {
         buf
     }
 
+    /// Converts a timestamp to `AmzDate` in UTC
+    #[must_use]
+    pub fn from_time(t: time::OffsetDateTime) -> Self {
+        let t = t.to_offset(time::UtcOffset::UTC);
+        Self {
+            year: u16::try_from(t.year()).unwrap_or_default(),
+            month: u8::from(t.month()),
+            day: t.day(),
+            hour: t.hour(),
+            minute: t.minute(),
+            second: t.second(),
+        }
+    }
+
     pub fn to_time(&self) -> Option<time::OffsetDateTime> {
         let y = i32::from(self.year);
         let m: time::Month = self.month.try_into().ok()?;
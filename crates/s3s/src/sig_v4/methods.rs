@@ -49,7 +49,7 @@ fn uri_encode(output: &mut String, input: &str, encode_slash: bool) {
 }
 
 /// custom uri encode
-fn uri_encode_string(input: &str, encode_slash: bool) -> String {
+pub fn uri_encode_string(input: &str, encode_slash: bool) -> String {
     let mut output = String::with_capacity(input.len());
     uri_encode(&mut output, input, encode_slash);
     output
@@ -77,6 +77,8 @@ pub enum Payload<'a> {
     Empty,
     /// single chunk
     SingleChunk(&'a [u8]),
+    /// single chunk with the sha256 checksum given by `x-amz-content-sha256`
+    SingleChunkHash(&'a str),
     /// multiple chunks
     MultipleChunks,
     /// multiple chunks with trailing headers
@@ -180,6 +182,7 @@ pub fn create_canonical_request(
             Payload::Unsigned => ans.push_str("UNSIGNED-PAYLOAD"),
             Payload::Empty => ans.push_str(EMPTY_STRING_SHA256_HASH),
             Payload::SingleChunk(data) => hex_sha256(data, |s| ans.push_str(s)),
+            Payload::SingleChunkHash(hash) => ans.push_str(hash),
             Payload::MultipleChunks => ans.push_str("STREAMING-AWS4-HMAC-SHA256-PAYLOAD"),
             Payload::MultipleChunksWithTrailer => ans.push_str("STREAMING-AWS4-HMAC-SHA256-PAYLOAD-TRAILER"),
             Payload::UnsignedMultipleChunksWithTrailer => ans.push_str("STREAMING-UNSIGNED-PAYLOAD-TRAILER"),
@@ -0,0 +1,214 @@
+use crate::auth::SecretKey;
+use crate::sig_v4;
+use crate::sig_v4::AmzDate;
+
+use std::fmt::Write as _;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use bytes::{Bytes, BytesMut};
+use futures::Stream;
+
+/// The size of chunks encoded by [`ChunkSigner::into_stream`] (64 KiB)
+pub const AWS_CHUNKED_CHUNK_SIZE: usize = 64 * 1024;
+
+/// `;chunk-signature=` + 64 hex digits + `\r\n`
+const CHUNK_META_LEN: u64 = 17 + 64 + 2;
+
+fn encoded_chunk_len(size: u64) -> u64 {
+    let hex_len = u64::from(size.max(1).ilog(16) + 1);
+    hex_len + CHUNK_META_LEN + size + 2
+}
+
+/// Returns the content length of an aws-chunked body which is encoded by [`ChunkSigner::into_stream`].
+#[must_use]
+pub fn aws_chunked_content_length(decoded_content_length: u64) -> u64 {
+    let chunk_size = AWS_CHUNKED_CHUNK_SIZE as u64;
+    let full_chunks = decoded_content_length / chunk_size;
+    let remaining = decoded_content_length % chunk_size;
+
+    let mut len = full_chunks * encoded_chunk_len(chunk_size);
+    if remaining > 0 {
+        len += encoded_chunk_len(remaining);
+    }
+    len + encoded_chunk_len(0)
+}
+
+/// Signs the chunks of an aws-chunked streaming payload
+///
+/// Returned by [`SigV4Signer::sign_streaming`](super::SigV4Signer::sign_streaming).
+#[derive(Debug)]
+pub struct ChunkSigner {
+    secret_key: SecretKey,
+    amz_date: AmzDate,
+    region: String,
+    service: String,
+    prev_signature: String,
+}
+
+impl ChunkSigner {
+    pub(super) fn new(secret_key: SecretKey, amz_date: AmzDate, region: String, service: String, seed_signature: String) -> Self {
+        Self {
+            secret_key,
+            amz_date,
+            region,
+            service,
+            prev_signature: seed_signature,
+        }
+    }
+
+    /// Returns the signature of the previous chunk, or the seed signature of the request.
+    #[must_use]
+    pub fn prev_signature(&self) -> &str {
+        &self.prev_signature
+    }
+
+    /// Signs a chunk and encodes it as `{hex-size};chunk-signature={signature}\r\n{data}\r\n`.
+    ///
+    /// The final chunk must be empty.
+    pub fn sign_chunk(&mut self, data: &Bytes) -> Bytes {
+        let (amz_date, region, service) = (&self.amz_date, self.region.as_str(), self.service.as_str());
+        let chunk_data: &[Bytes] = if data.is_empty() { &[] } else { std::slice::from_ref(data) };
+        let string_to_sign = sig_v4::create_chunk_string_to_sign(amz_date, region, service, &self.prev_signature, chunk_data);
+        let signature = sig_v4::calculate_signature(&string_to_sign, &self.secret_key, amz_date, region, service);
+
+        let mut buf = String::with_capacity(96);
+        write!(&mut buf, "{:x};chunk-signature={signature}\r\n", data.len()).unwrap();
+
+        let mut ans = BytesMut::with_capacity(buf.len() + data.len() + 2);
+        ans.extend_from_slice(buf.as_bytes());
+        ans.extend_from_slice(data);
+        ans.extend_from_slice(b"\r\n");
+
+        self.prev_signature = signature;
+        ans.freeze()
+    }
+
+    /// Encodes a body in signed chunks of [`AWS_CHUNKED_CHUNK_SIZE`], followed by the final empty chunk.
+    pub fn into_stream<S, E>(self, body: S) -> SignedChunkStream<S>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Unpin,
+    {
+        SignedChunkStream {
+            body,
+            signer: self,
+            buf: BytesMut::new(),
+            state: State::Reading,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Reading,
+    Flushing,
+    Done,
+}
+
+/// An aws-chunked body encoded by [`ChunkSigner::into_stream`]
+#[derive(Debug)]
+pub struct SignedChunkStream<S> {
+    body: S,
+    signer: ChunkSigner,
+    buf: BytesMut,
+    state: State,
+}
+
+impl<S, E> Stream for SignedChunkStream<S>
+where
+    S: Stream<Item = Result<Bytes, E>> + Unpin,
+{
+    type Item = Result<Bytes, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            match this.state {
+                State::Reading => {
+                    if this.buf.len() >= AWS_CHUNKED_CHUNK_SIZE {
+                        let data = this.buf.split_to(AWS_CHUNKED_CHUNK_SIZE).freeze();
+                        return Poll::Ready(Some(Ok(this.signer.sign_chunk(&data))));
+                    }
+                    match ready!(Pin::new(&mut this.body).poll_next(cx)) {
+                        Some(Ok(bytes)) => this.buf.extend_from_slice(&bytes),
+                        Some(Err(err)) => {
+                            this.state = State::Done;
+                            return Poll::Ready(Some(Err(err)));
+                        }
+                        None => this.state = State::Flushing,
+                    }
+                }
+                State::Flushing => {
+                    if this.buf.is_empty() {
+                        this.state = State::Done;
+                        return Poll::Ready(Some(Ok(this.signer.sign_chunk(&Bytes::new()))));
+                    }
+                    let len = this.buf.len().min(AWS_CHUNKED_CHUNK_SIZE);
+                    let data = this.buf.split_to(len).freeze();
+                    return Poll::Ready(Some(Ok(this.signer.sign_chunk(&data))));
+                }
+                State::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::error::StdError;
+    use crate::http::AwsChunkedStream;
+    use crate::signer::SigV4Signer;
+    use crate::tests::{SECRET_KEY, credentials};
+
+    use std::time::SystemTime;
+
+    use futures::StreamExt;
+
+    #[tokio::test]
+    async fn roundtrip() {
+        let signer = SigV4Signer::new(credentials(), "us-east-1");
+
+        let content: Vec<u8> = (0..AWS_CHUNKED_CHUNK_SIZE * 2 + 100)
+            .map(|i| (i % 251).to_le_bytes()[0])
+            .collect();
+        let decoded_content_length = content.len() as u64;
+
+        let mut req = http::Request::builder()
+            .method("PUT")
+            .uri("http://localhost/bucket/key")
+            .body(())
+            .unwrap();
+        let chunk_signer = signer
+            .sign_streaming(&mut req, decoded_content_length, SystemTime::now())
+            .unwrap();
+        let seed_signature = chunk_signer.prev_signature().to_owned();
+
+        let parts: Vec<Result<Bytes, StdError>> = content.chunks(1000).map(|c| Ok(Bytes::copy_from_slice(c))).collect();
+        let encoded: Vec<Bytes> = chunk_signer
+            .into_stream(futures::stream::iter(parts))
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        let encoded_len: usize = encoded.iter().map(Bytes::len).sum();
+
+        let content_length = req.headers()["content-length"].to_str().unwrap();
+        assert_eq!(content_length, encoded_len.to_string());
+        assert_eq!(encoded_len as u64, aws_chunked_content_length(decoded_content_length));
+
+        let amz_date = AmzDate::parse(req.headers()["x-amz-date"].to_str().unwrap()).unwrap();
+        let stream = AwsChunkedStream::new(
+            futures::stream::iter(encoded.into_iter().map(Ok::<_, StdError>)),
+            seed_signature.into(),
+            amz_date,
+            "us-east-1".into(),
+            "s3".into(),
+            SECRET_KEY.into(),
+            content.len(),
+            false,
+        );
+        let decoded: Vec<Bytes> = stream.map(Result::unwrap).collect().await;
+        assert_eq!(decoded.concat(), content);
+    }
+}
@@ -0,0 +1,106 @@
+//! Request signing
+//!
+//! Signs S3 requests and generates presigned URLs with the given [`Credentials`].
+//!
+//! + [`SigV4Signer`]: AWS Signature Version 4, including aws-chunked streaming payloads
+//! + [`SigV2Signer`]: AWS Signature Version 2
+//!
+
+mod v4;
+pub use self::v4::*;
+
+mod chunked;
+pub use self::chunked::*;
+
+mod v2;
+pub use self::v2::*;
+
+use crate::auth::Credentials;
+
+use hyper::HeaderMap;
+use hyper::Uri;
+use hyper::header::{HeaderName, HeaderValue};
+
+/// An error that occurs when signing a request
+#[derive(Debug, thiserror::Error)]
+pub enum SignError {
+    /// The request has no host header and its uri has no authority
+    #[error("SignError: missing host")]
+    MissingHost,
+
+    /// A header value is not visible ASCII
+    #[error("SignError: invalid header value")]
+    InvalidHeaderValue,
+
+    /// The query string cannot be decoded
+    #[error("SignError: invalid query string")]
+    InvalidQuery,
+
+    /// The uri path cannot be decoded
+    #[error("SignError: invalid uri path")]
+    InvalidPath,
+
+    /// The presigned uri cannot be constructed
+    #[error("SignError: invalid uri")]
+    InvalidUri,
+}
+
+fn insert_header(headers: &mut HeaderMap, name: HeaderName, value: &str) -> Result<(), SignError> {
+    let value = HeaderValue::from_str(value).map_err(|_| SignError::InvalidHeaderValue)?;
+    headers.insert(name, value);
+    Ok(())
+}
+
+/// Inserts the session token header of temporary credentials.
+fn insert_session_token(headers: &mut HeaderMap, credentials: &Credentials) -> Result<(), SignError> {
+    match &credentials.session_token {
+        Some(token) => insert_header(headers, HeaderName::from_static("x-amz-security-token"), token),
+        None => Ok(()),
+    }
+}
+
+/// Inserts the host header from the uri authority if it is missing.
+fn ensure_host(headers: &mut HeaderMap, uri: &Uri) -> Result<(), SignError> {
+    if headers.contains_key(hyper::header::HOST) {
+        return Ok(());
+    }
+    let authority = uri.authority().ok_or(SignError::MissingHost)?;
+    insert_header(headers, hyper::header::HOST, authority.as_str())
+}
+
+fn decode_path(uri: &Uri) -> Result<String, SignError> {
+    let path = urlencoding::decode(uri.path()).map_err(|_| SignError::InvalidPath)?;
+    Ok(path.into_owned())
+}
+
+fn decode_query(uri: &Uri) -> Result<Vec<(String, String)>, SignError> {
+    let Some(query) = uri.query() else { return Ok(Vec::new()) };
+    serde_urlencoded::from_str(query).map_err(|_| SignError::InvalidQuery)
+}
+
+/// Replaces the query of `uri` with the encoded query strings.
+fn with_query(uri: &Uri, query: &str) -> Result<Uri, SignError> {
+    let mut parts = uri.clone().into_parts();
+    let path_and_query = format!("{}?{}", uri.path(), query);
+    parts.path_and_query = Some(path_and_query.parse().map_err(|_| SignError::InvalidUri)?);
+    Uri::from_parts(parts).map_err(|_| SignError::InvalidUri)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::auth::SimpleAuth;
+    use crate::service::{S3Service, S3ServiceBuilder};
+    use crate::tests::{ACCESS_KEY, MockS3, SECRET_KEY, SESSION_TOKEN, temporary_credentials};
+
+    use std::time::{Duration, SystemTime};
+
+    pub fn service() -> S3Service {
+        let mut builder = S3ServiceBuilder::new(MockS3);
+        let auth = SimpleAuth::from_single(ACCESS_KEY, SECRET_KEY);
+        let cred = temporary_credentials();
+        let expiration = SystemTime::now() + Duration::from_secs(3600);
+        auth.register_temporary(cred.access_key, cred.secret_key, SESSION_TOKEN.into(), expiration);
+        builder.set_auth(auth);
+        builder.build()
+    }
+}
@@ -1,12 +1,11 @@
 use crate::error::StdError;
 
-use std::collections::VecDeque;
 use std::fmt;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use bytes::Bytes;
-use futures::{Stream, StreamExt, pin_mut};
+use futures::Stream;
 
 pub trait ByteStream: Stream {
     fn remaining_length(&self) -> RemainingLength {
@@ -131,66 +130,3 @@ where
         self.0.remaining_length()
     }
 }
-
-// FIXME: unbounded memory allocation
-pub(crate) async fn aggregate_unlimited<S, E>(stream: S) -> Result<Vec<Bytes>, E>
-where
-    S: ByteStream<Item = Result<Bytes, E>>,
-{
-    let mut vec = Vec::new();
-    pin_mut!(stream);
-    while let Some(result) = stream.next().await {
-        vec.push(result?);
-    }
-    Ok(vec)
-}
-
-pub(crate) struct VecByteStream {
-    queue: VecDeque<Bytes>,
-    remaining_bytes: usize,
-}
-
-impl VecByteStream {
-    pub fn new(v: Vec<Bytes>) -> Self {
-        let total = v
-            .iter()
-            .map(Bytes::len)
-            .try_fold(0, usize::checked_add)
-            .expect("length overflow");
-
-        Self {
-            queue: v.into(),
-            remaining_bytes: total,
-        }
-    }
-
-    pub fn exact_remaining_length(&self) -> usize {
-        self.remaining_bytes
-    }
-}
-
-impl Stream for VecByteStream {
-    type Item = Result<Bytes, StdError>;
-
-    fn poll_next(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let this = Pin::into_inner(self);
-        match this.queue.pop_front() {
-            Some(b) => {
-                this.remaining_bytes -= b.len();
-                Poll::Ready(Some(Ok(b)))
-            }
-            None => Poll::Ready(None),
-        }
-    }
-
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let cnt = self.queue.len();
-        (cnt, Some(cnt))
-    }
-}
-
-impl ByteStream for VecByteStream {
-    fn remaining_length(&self) -> RemainingLength {
-        RemainingLength::new_exact(self.remaining_bytes)
-    }
-}
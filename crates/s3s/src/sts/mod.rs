@@ -0,0 +1,570 @@
+//! Security Token Service
+//!
+//! [`AssumeRoleRoute`] is a ready-made [`S3Route`] which handles the STS `AssumeRole` action.
+//! The temporary credentials are minted by a [`CredentialIssuer`].
+//!
+//! [`SimpleIssuer`] implements [`CredentialIssuer`] and registers the minted credentials
+//! in a [`SimpleAuth`](crate::auth::SimpleAuth), which accepts them on later requests.
+//! The roles which a caller may assume are decided by a [`RoleAuthorizer`].
+//! [`SimpleIssuer`] is not available on `wasm32` targets, which have no system random source.
+//!
+//! See <https://docs.aws.amazon.com/STS/latest/APIReference/API_AssumeRole.html>
+
+#[cfg(not(target_arch = "wasm32"))]
+mod simple_issuer;
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::simple_issuer::SimpleIssuer;
+
+use crate::auth::{Credentials, Identity, SecretKey};
+use crate::dto;
+use crate::error::{S3Error, S3ErrorCode, S3Result};
+use crate::header;
+use crate::route::S3Route;
+use crate::xml;
+use crate::{Body, S3Request, S3Response};
+
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+use bytestring::ByteString;
+use http_body_util::BodyExt;
+use hyper::HeaderMap;
+use hyper::Method;
+use hyper::StatusCode;
+use hyper::Uri;
+use hyper::header::HeaderValue;
+use hyper::http::Extensions;
+
+/// The default lifetime of temporary credentials (1 hour)
+pub const DEFAULT_DURATION: Duration = Duration::from_secs(3600);
+
+/// The minimum lifetime of temporary credentials (15 minutes)
+pub const MIN_DURATION: Duration = Duration::from_secs(900);
+
+/// The maximum lifetime of temporary credentials (12 hours)
+pub const MAX_DURATION: Duration = Duration::from_secs(43200);
+
+/// The maximum size of the form body
+const MAX_FORM_SIZE: usize = 64 * 1024;
+
+/// An `AssumeRole` request
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct AssumeRoleRequest {
+    /// The credentials of the caller
+    pub credentials: Credentials,
+
+    /// `RoleArn`
+    pub role_arn: String,
+
+    /// `RoleSessionName`
+    pub role_session_name: String,
+
+    /// `Policy`, an inline session policy in JSON
+    pub policy: Option<String>,
+
+    /// `DurationSeconds`
+    pub duration: Duration,
+}
+
+/// Temporary credentials minted by a [`CredentialIssuer`]
+#[derive(Debug, Clone)]
+pub struct TemporaryCredentials {
+    pub access_key: String,
+    pub secret_key: SecretKey,
+    pub session_token: String,
+    pub expiration: SystemTime,
+    pub assumed_role_user: Option<dto::AssumedRoleUser>,
+}
+
+/// A provider which mints temporary credentials
+#[async_trait::async_trait]
+pub trait CredentialIssuer: Send + Sync + 'static {
+    /// Mints temporary credentials for the role.
+    ///
+    /// # Errors
+    /// Returns an error if the caller is not allowed to assume the role.
+    async fn assume_role(&self, req: AssumeRoleRequest) -> S3Result<TemporaryCredentials>;
+}
+
+/// A hook which decides whether the caller may assume the role, such as checking the trust policy of the role
+///
+/// It is called by [`SimpleIssuer`] before minting temporary credentials.
+/// `caller` is the identity of the caller, if any.
+pub trait RoleAuthorizer: Send + Sync + 'static {
+    /// Authorizes the request.
+    ///
+    /// # Errors
+    /// Returns an error, such as `AccessDenied`, if the caller is not allowed to assume the role.
+    fn authorize(&self, req: &AssumeRoleRequest, caller: Option<&Identity>) -> S3Result<()>;
+}
+
+impl<F> RoleAuthorizer for F
+where
+    F: Fn(&AssumeRoleRequest, Option<&Identity>) -> S3Result<()> + Send + Sync + 'static,
+{
+    fn authorize(&self, req: &AssumeRoleRequest, caller: Option<&Identity>) -> S3Result<()> {
+        self(req, caller)
+    }
+}
+
+impl fmt::Debug for dyn RoleAuthorizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RoleAuthorizer")
+    }
+}
+
+/// A route which handles the STS `AssumeRole` action
+///
+/// It matches form POST requests to `/`, which is relative to the base path of the service, and requires signed requests.
+pub struct AssumeRoleRoute {
+    issuer: Box<dyn CredentialIssuer>,
+}
+
+impl AssumeRoleRoute {
+    #[must_use]
+    pub fn new(issuer: impl CredentialIssuer) -> Self {
+        Self {
+            issuer: Box::new(issuer),
+        }
+    }
+}
+
+fn validation_error(msg: String) -> S3Error {
+    let mut err = S3Error::with_message(S3ErrorCode::Custom(ByteString::from_static("ValidationError")), msg);
+    err.set_status_code(StatusCode::BAD_REQUEST);
+    err
+}
+
+fn missing_parameter(name: &str) -> S3Error {
+    let mut err = S3Error::with_message(
+        S3ErrorCode::Custom(ByteString::from_static("MissingParameter")),
+        format!("The request must contain the parameter {name}"),
+    );
+    err.set_status_code(StatusCode::BAD_REQUEST);
+    err
+}
+
+async fn read_form(body: Body) -> S3Result<Vec<(String, String)>> {
+    let bytes = match body.bytes() {
+        Some(bytes) => bytes,
+        None => {
+            let body = http_body_util::Limited::new(body, MAX_FORM_SIZE);
+            let collected = body.collect().await.map_err(|e| match e.downcast::<S3Error>() {
+                Ok(err) => *err,
+                Err(e) => S3Error::with_source(S3ErrorCode::IncompleteBody, e),
+            })?;
+            collected.to_bytes()
+        }
+    };
+    serde_urlencoded::from_bytes(&bytes).map_err(|e| invalid_request!(e, "invalid form body"))
+}
+
+fn parse_duration(val: Option<&str>) -> S3Result<Duration> {
+    let Some(val) = val else { return Ok(DEFAULT_DURATION) };
+    let secs = val
+        .parse::<u64>()
+        .map_err(|_| validation_error(format!("Value '{val}' at 'durationSeconds' is not a valid integer")))?;
+    let duration = Duration::from_secs(secs);
+    if !(MIN_DURATION..=MAX_DURATION).contains(&duration) {
+        return Err(validation_error(format!(
+            "Value '{secs}' at 'durationSeconds' failed to satisfy constraint: \
+            Member must have value between {} and {}",
+            MIN_DURATION.as_secs(),
+            MAX_DURATION.as_secs()
+        )));
+    }
+    Ok(duration)
+}
+
+fn parse_request(form: Vec<(String, String)>, credentials: Credentials) -> S3Result<AssumeRoleRequest> {
+    let mut action = None;
+    let mut role_arn = None;
+    let mut role_session_name = None;
+    let mut policy = None;
+    let mut duration = None;
+
+    for (name, value) in form {
+        match name.as_str() {
+            "Action" => action = Some(value),
+            "RoleArn" => role_arn = Some(value),
+            "RoleSessionName" => role_session_name = Some(value),
+            "Policy" => policy = Some(value),
+            "DurationSeconds" => duration = Some(value),
+            _ => {}
+        }
+    }
+
+    match action.as_deref() {
+        Some("AssumeRole") => {}
+        Some(action) => {
+            let mut err = S3Error::with_message(
+                S3ErrorCode::Custom(ByteString::from_static("InvalidAction")),
+                format!("Could not find operation {action} for version 2011-06-15"),
+            );
+            err.set_status_code(StatusCode::BAD_REQUEST);
+            return Err(err);
+        }
+        None => return Err(missing_parameter("Action")),
+    }
+
+    let role_arn = role_arn.ok_or_else(|| missing_parameter("RoleArn"))?;
+    let role_session_name = role_session_name.ok_or_else(|| missing_parameter("RoleSessionName"))?;
+    let duration = parse_duration(duration.as_deref())?;
+
+    Ok(AssumeRoleRequest {
+        credentials,
+        role_arn,
+        role_session_name,
+        policy,
+        duration,
+    })
+}
+
+fn serialize_output(output: &dto::AssumeRoleOutput) -> S3Result<S3Response<Body>> {
+    let mut buf = Vec::with_capacity(256);
+    {
+        let mut ser = xml::Serializer::new(&mut buf);
+        ser.decl()
+            .and_then(|()| xml::Serialize::serialize(output, &mut ser))
+            .map_err(S3Error::internal_error)?;
+    }
+    let mut resp = S3Response::new(Body::from(buf));
+    resp.headers
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static("application/xml"));
+    Ok(resp)
+}
+
+#[async_trait::async_trait]
+impl S3Route for AssumeRoleRoute {
+    fn is_match(&self, method: &Method, uri: &Uri, headers: &HeaderMap, _: &mut Extensions) -> bool {
+        if method == Method::POST && uri.path() == "/" {
+            if let Some(val) = headers.get(header::CONTENT_TYPE) {
+                return val.as_bytes().starts_with(b"application/x-www-form-urlencoded");
+            }
+        }
+        false
+    }
+
+    async fn check_access(&self, req: &mut S3Request<Body>) -> S3Result<()> {
+        if req.credentials.is_none() {
+            return Err(s3_error!(AccessDenied, "Signature is required"));
+        }
+        if req.service.as_deref() != Some("sts") {
+            return Err(s3_error!(AccessDenied, "The request must be signed for the sts service"));
+        }
+        Ok(())
+    }
+
+    async fn call(&self, req: S3Request<Body>) -> S3Result<S3Response<Body>> {
+        let Some(credentials) = req.credentials else {
+            return Err(s3_error!(AccessDenied, "Signature is required"));
+        };
+        let form = read_form(req.input).await?;
+        let req = parse_request(form, credentials)?;
+
+        let temp = self.issuer.assume_role(req).await?;
+
+        let output = dto::AssumeRoleOutput {
+            assumed_role_user: temp.assumed_role_user,
+            credentials: Some(dto::Credentials {
+                access_key_id: temp.access_key,
+                expiration: temp.expiration.into(),
+                secret_access_key: temp.secret_key.expose().to_owned(),
+                session_token: temp.session_token,
+            }),
+            ..Default::default()
+        };
+        serialize_output(&output)
+    }
+}
+
+/// Returns the assumed role user of a role ARN like `arn:aws:iam::123456789012:role/demo`.
+///
+/// The ARN of the assumed role user is `arn:aws:sts::123456789012:assumed-role/demo/{role_session_name}`.
+#[must_use]
+pub fn assumed_role_user(role_arn: &str, role_session_name: &str, role_id: &str) -> Option<dto::AssumedRoleUser> {
+    let mut parts = role_arn.splitn(6, ':');
+    let (Some("arn"), Some(partition), Some("iam"), Some(""), Some(account), Some(resource)) =
+        (parts.next(), parts.next(), parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return None;
+    };
+    let role_name = resource.strip_prefix("role/")?.rsplit('/').next()?;
+    Some(dto::AssumedRoleUser {
+        arn: format!("arn:{partition}:sts::{account}:assumed-role/{role_name}/{role_session_name}"),
+        assumed_role_id: format!("{role_id}:{role_session_name}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::auth::{S3Auth, SimpleAuth};
+    use crate::service::{S3Service, S3ServiceBuilder};
+    use crate::signer::{SigV4Signer, SignPayload};
+    use crate::tests::{ACCESS_KEY, MockS3, SECRET_KEY, SESSION_TOKEN, credentials, error_code, temporary_credentials};
+
+    const DEMO_ROLE: &str = "arn%3Aaws%3Aiam%3A%3A123456789012%3Arole%2Fdemo";
+
+    fn auth() -> SimpleAuth {
+        SimpleAuth::from_single(ACCESS_KEY, SECRET_KEY)
+    }
+
+    fn role(name: &str) -> (String, Identity) {
+        let identity = Identity {
+            account_id: Some("123456789012".into()),
+            policies: vec![name.to_owned()],
+            ..Default::default()
+        };
+        (format!("arn:aws:iam::123456789012:role/{name}"), identity)
+    }
+
+    fn issuer(auth: &SimpleAuth) -> SimpleIssuer {
+        let mut issuer = SimpleIssuer::new(auth.clone());
+        for name in ["demo", "admin"] {
+            let (role_arn, identity) = role(name);
+            issuer.set_role(role_arn, identity);
+        }
+        issuer.set_role_authorizer(|req: &AssumeRoleRequest, _: Option<&Identity>| {
+            if req.role_arn == "arn:aws:iam::123456789012:role/demo" {
+                Ok(())
+            } else {
+                Err(s3_error!(AccessDenied))
+            }
+        });
+        issuer
+    }
+
+    fn service_with(auth: SimpleAuth, issuer: SimpleIssuer) -> S3Service {
+        let mut builder = S3ServiceBuilder::new(MockS3);
+        builder.set_route(AssumeRoleRoute::new(issuer));
+        builder.set_auth(auth);
+        builder.build()
+    }
+
+    fn service() -> S3Service {
+        let auth = auth();
+        let issuer = issuer(&auth);
+        service_with(auth, issuer)
+    }
+
+    fn assume_role_request(form: &str, service: &str) -> http::Request<Body> {
+        sts_request(credentials(), "http://localhost/", form, service)
+    }
+
+    fn sts_request(credentials: Credentials, uri: &str, form: &str, service: &str) -> http::Request<Body> {
+        let mut signer = SigV4Signer::new(credentials, "us-east-1");
+        signer.set_service(service);
+        let mut req = http::Request::builder()
+            .method(Method::POST)
+            .uri(uri)
+            .header("content-type", "application/x-www-form-urlencoded")
+            .header("content-length", form.len())
+            .body(Body::from(form.to_owned()))
+            .unwrap();
+        signer
+            .sign(&mut req, SignPayload::Bytes(form.as_bytes()), SystemTime::now())
+            .unwrap();
+        req
+    }
+
+    fn extract<'a>(body: &'a str, tag: &str) -> &'a str {
+        let start = body.find(&format!("<{tag}>")).unwrap() + tag.len() + 2;
+        let end = body.find(&format!("</{tag}>")).unwrap();
+        &body[start..end]
+    }
+
+    #[test]
+    fn role_user() {
+        let user = assumed_role_user("arn:aws:iam::123456789012:role/path/demo", "session", "ASIA1").unwrap();
+        assert_eq!(user.arn, "arn:aws:sts::123456789012:assumed-role/demo/session");
+        assert_eq!(user.assumed_role_id, "ASIA1:session");
+
+        assert!(assumed_role_user("arn:aws:iam::123456789012:user/demo", "session", "ASIA1").is_none());
+        assert!(assumed_role_user("demo", "session", "ASIA1").is_none());
+    }
+
+    #[tokio::test]
+    async fn assume_role() {
+        let service = service();
+
+        let form = "Action=AssumeRole&Version=2011-06-15\
+            &RoleArn=arn%3Aaws%3Aiam%3A%3A123456789012%3Arole%2Fdemo\
+            &RoleSessionName=session&DurationSeconds=900";
+        let mut resp = service.call(assume_role_request(form, "sts")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = resp.body_mut().store_all_unlimited().await.unwrap();
+        let body = std::str::from_utf8(&body).unwrap();
+
+        assert!(body.contains("<AssumeRoleResponse xmlns=\"https://sts.amazonaws.com/doc/2011-06-15/\">"));
+        assert_eq!(extract(body, "Arn"), "arn:aws:sts::123456789012:assumed-role/demo/session");
+
+        let temp = minted_credentials(body);
+        assert!(temp.access_key.starts_with("ASIA"));
+
+        let signer = SigV4Signer::new(temp.clone(), "us-east-1");
+        let mut req = http::Request::builder()
+            .method(Method::GET)
+            .uri("http://localhost/bucket/key")
+            .body(Body::empty())
+            .unwrap();
+        signer.sign(&mut req, SignPayload::Empty, SystemTime::now()).unwrap();
+        assert_eq!(error_code(&service, req).await, "NotImplemented");
+
+        let signer = SigV4Signer::new(
+            Credentials {
+                session_token: None,
+                ..temp
+            },
+            "us-east-1",
+        );
+        let mut req = http::Request::builder()
+            .method(Method::GET)
+            .uri("http://localhost/bucket/key")
+            .body(Body::empty())
+            .unwrap();
+        signer.sign(&mut req, SignPayload::Empty, SystemTime::now()).unwrap();
+        assert_eq!(error_code(&service, req).await, "NotSignedUp");
+    }
+
+    #[tokio::test]
+    async fn invalid_requests() {
+        let service = service();
+
+        let form = "Action=AssumeRole&RoleArn=arn%3Aaws%3Aiam%3A%3A123456789012%3Arole%2Fdemo";
+        assert_eq!(error_code(&service, assume_role_request(form, "sts")).await, "MissingParameter");
+
+        let form = "Action=AssumeRole&RoleArn=arn%3Aaws%3Aiam%3A%3A123456789012%3Arole%2Fdemo\
+            &RoleSessionName=session&DurationSeconds=60";
+        assert_eq!(error_code(&service, assume_role_request(form, "sts")).await, "ValidationError");
+
+        let form = "Action=GetCallerIdentity";
+        assert_eq!(error_code(&service, assume_role_request(form, "sts")).await, "InvalidAction");
+
+        let form = "Action=AssumeRole&RoleArn=arn%3Aaws%3Aiam%3A%3A123456789012%3Arole%2Fdemo&RoleSessionName=session";
+        assert_eq!(error_code(&service, assume_role_request(form, "s3")).await, "AccessDenied");
+    }
+
+    async fn assume_role_body(service: &S3Service, req: http::Request<Body>) -> String {
+        let mut resp = service.call(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = resp.body_mut().store_all_unlimited().await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    /// Returns the temporary credentials in an `AssumeRole` response.
+    fn minted_credentials(body: &str) -> Credentials {
+        Credentials::with_session_token(
+            extract(body, "AccessKeyId"),
+            extract(body, "SecretAccessKey"),
+            extract(body, "SessionToken"),
+        )
+    }
+
+    #[tokio::test]
+    async fn role_authorizer() {
+        let form = format!("Action=AssumeRole&RoleArn={DEMO_ROLE}&RoleSessionName=session");
+        let auth = auth();
+        let mut issuer = SimpleIssuer::new(auth.clone());
+        let (role_arn, identity) = role("demo");
+        issuer.set_role(role_arn, identity);
+        let unauthorized = service_with(auth, issuer);
+        assert_eq!(error_code(&unauthorized, assume_role_request(&form, "sts")).await, "AccessDenied");
+
+        let service = service();
+        let form = "Action=AssumeRole&RoleArn=arn%3Aaws%3Aiam%3A%3A123456789012%3Arole%2Fadmin&RoleSessionName=session";
+        assert_eq!(error_code(&service, assume_role_request(form, "sts")).await, "AccessDenied");
+    }
+
+    #[tokio::test]
+    async fn unknown_role() {
+        let auth = auth();
+        let mut issuer = SimpleIssuer::new(auth.clone());
+        issuer.set_role_authorizer(|_: &AssumeRoleRequest, _: Option<&Identity>| Ok(()));
+        let service = service_with(auth, issuer);
+
+        let form = format!("Action=AssumeRole&RoleArn={DEMO_ROLE}&RoleSessionName=session");
+        assert_eq!(error_code(&service, assume_role_request(&form, "sts")).await, "AccessDenied");
+    }
+
+    #[tokio::test]
+    async fn role_identity() {
+        let mut auth = auth();
+        let caller = Identity {
+            principal: Some("alice".into()),
+            groups: vec!["staff".into()],
+            policies: vec!["caller".into()],
+            ..Default::default()
+        };
+        auth.set_identity(ACCESS_KEY.into(), caller);
+        let issuer = issuer(&auth);
+
+        let form = format!("Action=AssumeRole&RoleArn={DEMO_ROLE}&RoleSessionName=session");
+        let service = service_with(auth.clone(), issuer);
+        let body = assume_role_body(&service, assume_role_request(&form, "sts")).await;
+
+        let temp = minted_credentials(&body);
+        let (_, identity) = auth
+            .get_identity(&temp.access_key, temp.session_token.as_deref())
+            .await
+            .unwrap();
+        let identity = identity.unwrap();
+        assert_eq!(identity.principal.as_deref(), Some("arn:aws:sts::123456789012:assumed-role/demo/session"));
+        assert_eq!(identity.account_id.as_deref(), Some("123456789012"));
+        assert!(identity.groups.is_empty());
+        assert_eq!(identity.policies, ["demo"]);
+    }
+
+    #[tokio::test]
+    async fn session_policy() {
+        let service = service();
+        let form = format!("Action=AssumeRole&RoleArn={DEMO_ROLE}&RoleSessionName=session&Policy=%7B%7D");
+        assert_eq!(error_code(&service, assume_role_request(&form, "sts")).await, "NotImplemented");
+    }
+
+    #[tokio::test]
+    async fn chained_session() {
+        let service = service();
+
+        let form = format!("Action=AssumeRole&RoleArn={DEMO_ROLE}&RoleSessionName=session&DurationSeconds=900");
+        let body = assume_role_body(&service, assume_role_request(&form, "sts")).await;
+        let parent_expiration = extract(&body, "Expiration").to_owned();
+
+        let form = format!("Action=AssumeRole&RoleArn={DEMO_ROLE}&RoleSessionName=chained&DurationSeconds=3600");
+        let req = sts_request(minted_credentials(&body), "http://localhost/", &form, "sts");
+        let body = assume_role_body(&service, req).await;
+        assert!(extract(&body, "Expiration") <= parent_expiration.as_str());
+    }
+
+    #[tokio::test]
+    async fn registered_session() {
+        let auth = auth();
+        let temp = temporary_credentials();
+        let expiration = SystemTime::now() + DEFAULT_DURATION;
+        auth.register_temporary(temp.access_key.clone(), temp.secret_key.clone(), SESSION_TOKEN.to_owned(), expiration);
+        let issuer = issuer(&auth);
+        let service = service_with(auth, issuer);
+
+        let form = format!("Action=AssumeRole&RoleArn={DEMO_ROLE}&RoleSessionName=session");
+        let req = sts_request(temp, "http://localhost/", &form, "sts");
+        let body = assume_role_body(&service, req).await;
+        assert!(minted_credentials(&body).access_key.starts_with("ASIA"));
+    }
+
+    #[tokio::test]
+    async fn base_path() {
+        let auth = auth();
+        let mut builder = S3ServiceBuilder::new(MockS3);
+        builder.set_route(AssumeRoleRoute::new(issuer(&auth)));
+        builder.set_auth(auth);
+        builder.set_base_path("/storage");
+        let service = builder.build();
+
+        let form = "Action=AssumeRole&RoleArn=arn%3Aaws%3Aiam%3A%3A123456789012%3Arole%2Fdemo&RoleSessionName=session";
+        let req = sts_request(credentials(), "http://localhost/storage/", form, "sts");
+        let resp = service.call(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+}
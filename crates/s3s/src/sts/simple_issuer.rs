@@ -0,0 +1,111 @@
+use super::{AssumeRoleRequest, CredentialIssuer, RoleAuthorizer, TemporaryCredentials, assumed_role_user};
+
+use crate::auth::{Identity, SimpleAuth};
+use crate::error::{S3Error, S3Result};
+use crate::utils::crypto::random_bytes;
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+/// A [`CredentialIssuer`] which registers the minted credentials in a [`SimpleAuth`]
+///
+/// Roles are registered by [`SimpleIssuer::set_role`].
+/// A session gets the identity of its role, so it has the permissions of the role instead of the caller.
+/// Roles can be assumed only if they are allowed by [`SimpleIssuer::set_role_authorizer`].
+/// Session policies are not supported.
+#[derive(Debug)]
+pub struct SimpleIssuer {
+    /// the auth provider which accepts the minted credentials
+    auth: SimpleAuth,
+    /// role map
+    roles: HashMap<String, Identity>,
+    /// role authorizer
+    role_authorizer: Option<Box<dyn RoleAuthorizer>>,
+}
+
+/// Characters of temporary access keys
+const ACCESS_KEY_CHARS: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+impl SimpleIssuer {
+    /// Constructs a new `SimpleIssuer`
+    ///
+    /// The minted credentials are accepted by `auth` and its clones.
+    #[must_use]
+    pub fn new(auth: SimpleAuth) -> Self {
+        Self {
+            auth,
+            roles: HashMap::new(),
+            role_authorizer: None,
+        }
+    }
+
+    /// register a role with the identity which carries its permissions
+    pub fn set_role(&mut self, role_arn: String, identity: Identity) -> Option<Identity> {
+        self.roles.insert(role_arn, identity)
+    }
+
+    /// set the hook which authorizes `AssumeRole` requests
+    ///
+    /// All `AssumeRole` requests are denied if it is not set.
+    pub fn set_role_authorizer(&mut self, authorizer: impl RoleAuthorizer) {
+        self.role_authorizer = Some(Box::new(authorizer));
+    }
+}
+
+#[async_trait::async_trait]
+impl CredentialIssuer for SimpleIssuer {
+    async fn assume_role(&self, req: AssumeRoleRequest) -> S3Result<TemporaryCredentials> {
+        if req.policy.is_some() {
+            return Err(s3_error!(NotImplemented, "Session policies are not supported"));
+        }
+
+        let (caller, caller_expiration) = self.auth.caller(&req.credentials);
+        match &self.role_authorizer {
+            Some(authorizer) => authorizer.authorize(&req, caller.as_ref())?,
+            None => return Err(s3_error!(AccessDenied, "Not authorized to perform sts:AssumeRole")),
+        }
+        let Some(role) = self.roles.get(&req.role_arn) else {
+            return Err(s3_error!(AccessDenied, "Not authorized to perform sts:AssumeRole"));
+        };
+
+        let key_bytes: [u8; 16] = random_bytes().map_err(S3Error::internal_error)?;
+        let secret_bytes: [u8; 30] = random_bytes().map_err(S3Error::internal_error)?;
+        let token_bytes: [u8; 48] = random_bytes().map_err(S3Error::internal_error)?;
+
+        let mut access_key = String::from("ASIA");
+        access_key.extend(key_bytes.iter().map(|&b| char::from(ACCESS_KEY_CHARS[usize::from(b % 32)])));
+        let secret_key = base64_simd::STANDARD.encode_to_string(secret_bytes);
+        let session_token = base64_simd::STANDARD.encode_to_string(token_bytes);
+        // chained sessions cannot outlive the session of the caller
+        let mut expiration = SystemTime::now() + req.duration;
+        if let Some(caller_expiration) = caller_expiration {
+            expiration = expiration.min(caller_expiration);
+        }
+
+        let assumed_role_user = assumed_role_user(&req.role_arn, &req.role_session_name, &access_key);
+
+        let identity = Identity {
+            principal: assumed_role_user.as_ref().map(|u| u.arn.clone()),
+            account_id: role.account_id.clone(),
+            groups: role.groups.clone(),
+            tags: role.tags.clone(),
+            policies: role.policies.clone(),
+            ..Default::default()
+        };
+        self.auth.register_session(
+            access_key.clone(),
+            secret_key.clone().into(),
+            session_token.clone(),
+            expiration,
+            Some(identity),
+        );
+
+        Ok(TemporaryCredentials {
+            access_key,
+            secret_key: secret_key.into(),
+            session_token,
+            expiration,
+            assumed_role_user,
+        })
+    }
+}
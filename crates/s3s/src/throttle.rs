@@ -0,0 +1,479 @@
+//! Request throttling
+//!
+//! [`Throttle`] limits the request rate and the in-flight requests
+//! globally, for anonymous requests, per access key and per bucket.
+//! The global limit is checked before the signature, so that requests with bad signatures are also limited.
+//! The other limits are checked after authentication.
+//! Requests over the limits are rejected with `SlowDown` (503) and a `Retry-After` header.
+//!
+//! In-flight requests are counted until the response body finishes streaming.
+//!
+//! Throttling needs a monotonic clock, so this module is not available on `wasm32` targets,
+//! where [`std::time::Instant::now`] panics.
+
+use crate::error::{S3Error, S3Result};
+use crate::path::S3Path;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use hyper::HeaderMap;
+use hyper::header::{HeaderValue, RETRY_AFTER};
+
+/// The limit of a scope
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ThrottleLimit {
+    /// The sustained requests per second, `None` means unlimited.
+    ///
+    /// A rate of zero or less rejects all requests.
+    pub requests_per_second: Option<f64>,
+
+    /// The maximum burst of requests, at least one.
+    pub burst: u32,
+
+    /// The maximum in-flight requests, `None` means unlimited.
+    pub max_in_flight: Option<usize>,
+}
+
+impl ThrottleLimit {
+    /// Creates a rate limit with the burst equal to the rate.
+    ///
+    /// A rate of zero means unlimited.
+    #[must_use]
+    pub fn rate(requests_per_second: u32) -> Self {
+        Self {
+            requests_per_second: (requests_per_second > 0).then_some(f64::from(requests_per_second)),
+            burst: requests_per_second,
+            max_in_flight: None,
+        }
+    }
+
+    /// Creates a concurrency limit.
+    #[must_use]
+    pub fn in_flight(max_in_flight: usize) -> Self {
+        Self {
+            requests_per_second: None,
+            burst: 0,
+            max_in_flight: Some(max_in_flight),
+        }
+    }
+}
+
+/// A throttling component of [`S3Service`](crate::service::S3Service)
+///
+/// It is registered by [`S3ServiceBuilder::set_throttle`](crate::service::S3ServiceBuilder::set_throttle).
+#[derive(Debug, Default)]
+pub struct Throttle {
+    global: Option<ThrottleLimit>,
+    anonymous: Option<ThrottleLimit>,
+    access_key: Option<ThrottleLimit>,
+    bucket: Option<ThrottleLimit>,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    global: Counter,
+    anonymous: Counter,
+    access_keys: HashMap<String, Counter>,
+    buckets: HashMap<String, Counter>,
+}
+
+/// A token bucket and an in-flight counter
+#[derive(Debug, Default, Clone, Copy)]
+struct Counter {
+    tokens: f64,
+    updated_at: Option<Instant>,
+    in_flight: usize,
+}
+
+/// Scopes with more entries are pruned
+const MAX_KEYS: usize = 10_000;
+
+/// A permit of an in-flight request, which is released on drop
+#[derive(Debug)]
+pub(crate) struct ThrottlePermit {
+    state: Arc<Mutex<State>>,
+    global: bool,
+    access_key: Option<String>,
+    bucket: Option<String>,
+    anonymous: bool,
+}
+
+impl Throttle {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the limit of all requests.
+    pub fn set_global_limit(&mut self, limit: ThrottleLimit) {
+        self.global = Some(limit);
+    }
+
+    /// Sets the limit of all anonymous requests.
+    pub fn set_anonymous_limit(&mut self, limit: ThrottleLimit) {
+        self.anonymous = Some(limit);
+    }
+
+    /// Sets the limit of each access key.
+    pub fn set_access_key_limit(&mut self, limit: ThrottleLimit) {
+        self.access_key = Some(limit);
+    }
+
+    /// Sets the limit of each bucket.
+    pub fn set_bucket_limit(&mut self, limit: ThrottleLimit) {
+        self.bucket = Some(limit);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Acquires a permit for a request under the global limit.
+    ///
+    /// It is called before the signature is checked.
+    ///
+    /// # Errors
+    /// Returns `SlowDown` with a `Retry-After` header if the limit is exceeded.
+    pub(crate) fn acquire_global(&self) -> S3Result<ThrottlePermit> {
+        self.acquire_global_at(Instant::now())
+    }
+
+    /// Acquires the limits of the anonymous scope, the access key and the bucket for a request,
+    /// adding them to the permit.
+    ///
+    /// # Errors
+    /// Returns `SlowDown` with a `Retry-After` header if any limit is exceeded.
+    pub(crate) fn acquire(&self, permit: &mut ThrottlePermit, access_key: Option<&str>, s3_path: &S3Path) -> S3Result<()> {
+        self.acquire_at(permit, access_key, s3_path.get_bucket_name(), Instant::now())
+    }
+
+    fn acquire_global_at(&self, now: Instant) -> S3Result<ThrottlePermit> {
+        let mut state = self.lock();
+
+        let mut counters: Vec<(ThrottleLimit, &mut Counter)> = Vec::with_capacity(1);
+        if let Some(limit) = self.global {
+            counters.push((limit, &mut state.global));
+        }
+        take(counters, now)?;
+
+        Ok(ThrottlePermit {
+            state: Arc::clone(&self.state),
+            global: self.global.is_some(),
+            access_key: None,
+            bucket: None,
+            anonymous: false,
+        })
+    }
+
+    fn acquire_at(
+        &self,
+        permit: &mut ThrottlePermit,
+        access_key: Option<&str>,
+        bucket: Option<&str>,
+        now: Instant,
+    ) -> S3Result<()> {
+        let anonymous = access_key.is_none();
+        let access_key = access_key.filter(|_| self.access_key.is_some());
+        let bucket = bucket.filter(|_| self.bucket.is_some());
+
+        let mut state = self.lock();
+        let state = &mut *state;
+
+        if let Some(key) = access_key {
+            if !state.access_keys.contains_key(key) {
+                prune(&mut state.access_keys, self.access_key, now);
+            }
+        }
+        if let Some(bucket) = bucket {
+            if !state.buckets.contains_key(bucket) {
+                prune(&mut state.buckets, self.bucket, now);
+            }
+        }
+
+        let mut counters: Vec<(ThrottleLimit, &mut Counter)> = Vec::with_capacity(3);
+        if let Some(limit) = self.anonymous.filter(|_| anonymous) {
+            counters.push((limit, &mut state.anonymous));
+        }
+        if let (Some(limit), Some(key)) = (self.access_key, access_key) {
+            counters.push((limit, state.access_keys.entry(key.to_owned()).or_default()));
+        }
+        if let (Some(limit), Some(bucket)) = (self.bucket, bucket) {
+            counters.push((limit, state.buckets.entry(bucket.to_owned()).or_default()));
+        }
+        take(counters, now)?;
+
+        permit.access_key = access_key.map(str::to_owned);
+        permit.bucket = bucket.map(str::to_owned);
+        permit.anonymous = anonymous;
+        Ok(())
+    }
+}
+
+/// Takes a request from each counter if none of the limits is exceeded.
+fn take(mut counters: Vec<(ThrottleLimit, &mut Counter)>, now: Instant) -> S3Result<()> {
+    let mut retry_after = Duration::ZERO;
+    for (limit, counter) in &mut counters {
+        counter.refill(limit, now);
+        if let Some(wait) = counter.check(limit) {
+            retry_after = retry_after.max(wait);
+        }
+    }
+    if !retry_after.is_zero() {
+        return Err(slow_down(retry_after));
+    }
+
+    for (limit, counter) in counters {
+        if limit.requests_per_second.is_some() {
+            counter.tokens -= 1.0;
+        }
+        counter.in_flight += 1;
+    }
+    Ok(())
+}
+
+impl Counter {
+    fn refill(&mut self, limit: &ThrottleLimit, now: Instant) {
+        let Some(rate) = limit.requests_per_second else { return };
+        let burst = f64::from(limit.burst.max(1));
+        self.tokens = match self.updated_at {
+            Some(t) => (self.tokens + now.saturating_duration_since(t).as_secs_f64() * rate).min(burst),
+            None => burst,
+        };
+        self.updated_at = Some(now);
+    }
+
+    /// Returns the time to wait if the limit is exceeded.
+    fn check(&self, limit: &ThrottleLimit) -> Option<Duration> {
+        if limit.max_in_flight.is_some_and(|max| self.in_flight >= max) {
+            return Some(Duration::from_secs(1));
+        }
+        let rate = limit.requests_per_second?;
+        if rate <= 0.0 {
+            return Some(Duration::from_secs(1));
+        }
+        if self.tokens >= 1.0 {
+            return None;
+        }
+        Some(Duration::from_secs_f64((1.0 - self.tokens) / rate))
+    }
+
+    fn is_idle(&self, limit: Option<ThrottleLimit>, now: Instant) -> bool {
+        if self.in_flight > 0 {
+            return false;
+        }
+        let Some(limit) = limit else { return true };
+        let mut counter = *self;
+        counter.refill(&limit, now);
+        limit.requests_per_second.is_none() || counter.tokens >= f64::from(limit.burst.max(1))
+    }
+}
+
+/// Removes idle counters if there are too many keys.
+fn prune(counters: &mut HashMap<String, Counter>, limit: Option<ThrottleLimit>, now: Instant) {
+    if counters.len() >= MAX_KEYS {
+        counters.retain(|_, c| !c.is_idle(limit, now));
+    }
+}
+
+fn slow_down(retry_after: Duration) -> S3Error {
+    let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    let mut err = s3_error!(SlowDown, "Please reduce your request rate.");
+    let mut headers = HeaderMap::new();
+    headers.insert(RETRY_AFTER, HeaderValue::from(secs.max(1)));
+    err.set_headers(headers);
+    err
+}
+
+impl Drop for ThrottlePermit {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        let state = &mut *state;
+
+        let mut counters = Vec::with_capacity(4);
+        if self.global {
+            counters.push(&mut state.global);
+        }
+        if self.anonymous {
+            counters.push(&mut state.anonymous);
+        }
+        if let Some(c) = self.access_key.as_ref().and_then(|k| state.access_keys.get_mut(k)) {
+            counters.push(c);
+        }
+        if let Some(c) = self.bucket.as_ref().and_then(|b| state.buckets.get_mut(b)) {
+            counters.push(c);
+        }
+        for c in counters {
+            c.in_flight = c.in_flight.saturating_sub(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::S3ErrorCode;
+    use crate::auth::Credentials;
+    use crate::http::Body;
+    use crate::service::S3ServiceBuilder;
+    use crate::signer::{SigV4Signer, SignPayload};
+    use crate::tests::{ACCESS_KEY, MockS3, SECRET_KEY, credentials};
+
+    use std::time::SystemTime;
+
+    use hyper::StatusCode;
+
+    fn acquire(throttle: &Throttle, access_key: Option<&str>, bucket: Option<&str>, now: Instant) -> S3Result<ThrottlePermit> {
+        let mut permit = throttle.acquire_global_at(now)?;
+        throttle.acquire_at(&mut permit, access_key, bucket, now)?;
+        Ok(permit)
+    }
+
+    fn retry_after(err: &mut S3Error) -> u64 {
+        let headers = err.take_headers().unwrap();
+        headers.get(RETRY_AFTER).unwrap().to_str().unwrap().parse().unwrap()
+    }
+
+    #[test]
+    fn rate() {
+        let mut throttle = Throttle::new();
+        throttle.set_access_key_limit(ThrottleLimit::rate(2));
+        throttle.set_global_limit(ThrottleLimit::rate(100));
+
+        let t0 = Instant::now();
+        for _ in 0..2 {
+            acquire(&throttle, Some("alice"), None, t0).unwrap();
+        }
+        let mut err = acquire(&throttle, Some("alice"), None, t0).unwrap_err();
+        assert_eq!(*err.code(), S3ErrorCode::SlowDown);
+        assert_eq!(err.status_code(), Some(StatusCode::SERVICE_UNAVAILABLE));
+        assert_eq!(retry_after(&mut err), 1);
+
+        // other access keys and anonymous requests are not limited
+        acquire(&throttle, Some("bob"), None, t0).unwrap();
+        acquire(&throttle, None, None, t0).unwrap();
+
+        acquire(&throttle, Some("alice"), None, t0 + Duration::from_millis(500)).unwrap();
+        assert!(acquire(&throttle, Some("alice"), None, t0 + Duration::from_millis(600)).is_err());
+    }
+
+    #[test]
+    fn zero_rate() {
+        assert_eq!(ThrottleLimit::rate(0).requests_per_second, None);
+
+        let mut throttle = Throttle::new();
+        throttle.set_global_limit(ThrottleLimit::rate(0));
+        throttle.set_anonymous_limit(ThrottleLimit {
+            requests_per_second: Some(0.0),
+            burst: 1,
+            max_in_flight: None,
+        });
+
+        let now = Instant::now();
+        for _ in 0..10 {
+            acquire(&throttle, Some("alice"), None, now).unwrap();
+        }
+        assert!(acquire(&throttle, None, None, now).is_err());
+    }
+
+    #[test]
+    fn in_flight() {
+        let mut throttle = Throttle::new();
+        throttle.set_bucket_limit(ThrottleLimit::in_flight(1));
+        throttle.set_anonymous_limit(ThrottleLimit {
+            requests_per_second: Some(0.1),
+            burst: 1,
+            max_in_flight: None,
+        });
+
+        let now = Instant::now();
+        let permit = acquire(&throttle, Some("alice"), Some("a"), now).unwrap();
+        assert!(acquire(&throttle, Some("bob"), Some("a"), now).is_err());
+        acquire(&throttle, Some("bob"), Some("b"), now).unwrap();
+        drop(permit);
+        acquire(&throttle, Some("bob"), Some("a"), now).unwrap();
+
+        acquire(&throttle, None, None, now).unwrap();
+        let mut err = acquire(&throttle, None, None, now).unwrap_err();
+        assert_eq!(retry_after(&mut err), 10);
+    }
+
+    #[test]
+    fn prune_keys() {
+        let mut throttle = Throttle::new();
+        throttle.set_access_key_limit(ThrottleLimit::rate(1));
+
+        let now = Instant::now();
+        for i in 0..MAX_KEYS {
+            drop(acquire(&throttle, Some(&i.to_string()), None, now).unwrap());
+        }
+        let later = now + Duration::from_secs(1);
+        acquire(&throttle, Some("new"), None, later).unwrap();
+        assert_eq!(throttle.lock().access_keys.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn service() {
+        let mut throttle = Throttle::new();
+        throttle.set_anonymous_limit(ThrottleLimit::rate(1));
+        throttle.set_access_key_limit(ThrottleLimit::rate(1));
+
+        let mut builder = S3ServiceBuilder::new(MockS3);
+        builder.set_auth(crate::auth::SimpleAuth::from_single(ACCESS_KEY, SECRET_KEY));
+        builder.set_throttle(throttle);
+        let service = builder.build();
+
+        let request = |sign: bool| {
+            let mut req = http::Request::builder()
+                .uri("http://localhost/bucket/key")
+                .header("host", "localhost")
+                .body(Body::empty())
+                .unwrap();
+            if sign {
+                let signer = SigV4Signer::new(credentials(), "us-east-1");
+                signer.sign(&mut req, SignPayload::Empty, SystemTime::now()).unwrap();
+            }
+            req
+        };
+
+        for sign in [false, true] {
+            let resp = service.call(request(sign)).await.unwrap();
+            assert_ne!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+            let resp = service.call(request(sign)).await.unwrap();
+            assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+            assert_eq!(resp.headers().get(RETRY_AFTER).unwrap(), "1");
+        }
+    }
+
+    #[tokio::test]
+    async fn bad_signature() {
+        let mut throttle = Throttle::new();
+        throttle.set_global_limit(ThrottleLimit::rate(1));
+
+        let mut builder = S3ServiceBuilder::new(MockS3);
+        builder.set_auth(crate::auth::SimpleAuth::from_single(ACCESS_KEY, SECRET_KEY));
+        builder.set_throttle(throttle);
+        let service = builder.build();
+
+        let request = || {
+            let mut req = http::Request::builder()
+                .uri("http://localhost/bucket/key")
+                .header("host", "localhost")
+                .body(Body::empty())
+                .unwrap();
+            let signer = SigV4Signer::new(Credentials::new(ACCESS_KEY, "wrong"), "us-east-1");
+            signer.sign(&mut req, SignPayload::Empty, SystemTime::now()).unwrap();
+            req
+        };
+
+        let resp = service.call(request()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let resp = service.call(request()).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
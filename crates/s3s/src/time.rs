@@ -1,4 +1,5 @@
 use std::fmt;
+use std::time::Duration;
 
 pub fn now_utc() -> impl fmt::Debug {
     #[cfg(not(target_arch = "wasm32"))]
@@ -12,9 +13,11 @@ pub fn now_utc() -> impl fmt::Debug {
 }
 
 #[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy)]
 pub struct Instant(std::time::Instant);
 
 #[cfg(target_arch = "wasm32")]
+#[derive(Clone, Copy)]
 pub struct Instant(());
 
 impl Instant {
@@ -39,4 +42,16 @@ impl Instant {
             ()
         }
     }
+
+    /// Returns the elapsed time, which is always zero on wasm.
+    pub fn elapsed_duration(&self) -> Duration {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.0.elapsed()
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Duration::ZERO
+        }
+    }
 }
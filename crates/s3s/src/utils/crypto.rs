@@ -30,6 +30,14 @@ pub fn hmac_sha256(key: impl AsRef<[u8]>, data: impl AsRef<[u8]>) -> [u8; 32] {
     m.finalize().into_bytes().into()
 }
 
+/// random bytes from the operating system
+#[cfg(not(target_arch = "wasm32"))]
+pub fn random_bytes<const N: usize>() -> Result<[u8; N], getrandom::Error> {
+    let mut buf = [0; N];
+    getrandom::fill(&mut buf)?;
+    Ok(buf)
+}
+
 pub fn hex(data: impl AsRef<[u8]>) -> String {
     hex_simd::encode_to_string(data, hex_simd::AsciiCase::Lower)
 }
@@ -0,0 +1,543 @@
+//! Static website hosting
+//!
+//! Website endpoints are recognized by [`S3Host`](crate::host::S3Host),
+//! such as [`WebsiteDomain`](crate::host::WebsiteDomain).
+//! The website configurations of buckets are provided by [`S3Website`], which is registered by
+//! [`S3ServiceBuilder::set_website`](crate::service::S3ServiceBuilder::set_website).
+//!
+//! Website requests only support `GET` and `HEAD`, and are served like AWS S3 website endpoints:
+//!
+//! + `IndexDocument` is served for the bucket root and keys ending with `/`.
+//! + `RedirectAllRequestsTo` and `RoutingRules` return redirects.
+//! + `ErrorDocument` is served with the error status for client errors.
+//! + Other errors are returned as HTML pages.
+//!
+//! Website requests are anonymous.
+//! Every object fetched for a request, including the index document and the error document,
+//! is checked by [`S3Access`](crate::access::S3Access) if it is set.
+//! Otherwise, the objects are denied if [`S3Auth`](crate::auth::S3Auth) is set, and allowed if not.
+
+use crate::S3Operation;
+use crate::S3Request;
+use crate::access::S3AccessContext;
+use crate::dto::{GetBucketWebsiteInput, RoutingRule, WebsiteConfiguration};
+use crate::error::{S3Error, S3ErrorCode, S3Result};
+use crate::header;
+use crate::http::{Body, Request, Response};
+use crate::ops::{CallContext, GetObject, HeadObject, Operation};
+use crate::path::S3Path;
+use crate::s3_trait::S3;
+
+use std::sync::Arc;
+
+use hyper::HeaderMap;
+use hyper::Method;
+use hyper::StatusCode;
+use hyper::http::HeaderValue;
+use stdx::default::default;
+
+/// A source of website configurations
+#[async_trait::async_trait]
+pub trait S3Website: Send + Sync + 'static {
+    /// Returns the website configuration of a bucket.
+    ///
+    /// `None` means the bucket is not configured as a website.
+    async fn get_config(&self, cx: &S3WebsiteContext<'_>) -> S3Result<Option<WebsiteConfiguration>>;
+}
+
+pub struct S3WebsiteContext<'a> {
+    s3: &'a Arc<dyn S3>,
+    bucket: &'a str,
+}
+
+impl S3WebsiteContext<'_> {
+    /// Returns the S3 implementation of the service.
+    #[must_use]
+    pub fn s3(&self) -> &Arc<dyn S3> {
+        self.s3
+    }
+
+    /// Returns the bucket of current request.
+    #[must_use]
+    pub fn bucket(&self) -> &str {
+        self.bucket
+    }
+}
+
+/// A website source which calls [`S3::get_bucket_website`] of the service.
+///
+/// `NoSuchWebsiteConfiguration` and `NotImplemented` errors mean the bucket is not configured as a website.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BucketWebsite;
+
+#[async_trait::async_trait]
+impl S3Website for BucketWebsite {
+    async fn get_config(&self, cx: &S3WebsiteContext<'_>) -> S3Result<Option<WebsiteConfiguration>> {
+        let input = GetBucketWebsiteInput {
+            bucket: cx.bucket.to_owned(),
+            expected_bucket_owner: None,
+        };
+        let req = S3Request {
+            input,
+            method: Method::GET,
+            uri: format!("/{}?website", cx.bucket).parse().unwrap_or_default(),
+            headers: HeaderMap::new(),
+            extensions: hyper::http::Extensions::new(),
+            credentials: None,
+            identity: None,
+            region: None,
+            service: None,
+            trailing_headers: None,
+        };
+        match cx.s3.get_bucket_website(req).await {
+            Ok(resp) => {
+                let output = resp.output;
+                Ok(Some(WebsiteConfiguration {
+                    error_document: output.error_document,
+                    index_document: output.index_document,
+                    redirect_all_requests_to: output.redirect_all_requests_to,
+                    routing_rules: output.routing_rules,
+                }))
+            }
+            Err(err) if matches!(err.code(), S3ErrorCode::NoSuchWebsiteConfiguration | S3ErrorCode::NotImplemented) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Serves a website request.
+pub(crate) async fn call(website: &dyn S3Website, ccx: &CallContext<'_>, req: &mut Request) -> Response {
+    match serve(website, ccx, req).await {
+        Ok(resp) => resp,
+        Err(err) => {
+            let resp = error_page(&err, ccx, req.method == Method::HEAD);
+            req.s3ext.error_code = Some(err.code().clone());
+            resp
+        }
+    }
+}
+
+async fn serve(website: &dyn S3Website, ccx: &CallContext<'_>, req: &mut Request) -> S3Result<Response> {
+    if req.method != Method::GET && req.method != Method::HEAD {
+        return Err(s3_error!(MethodNotAllowed));
+    }
+    let op: &'static dyn Operation = if req.method == Method::HEAD { &HeadObject } else { &GetObject };
+    req.s3ext.op = Some(op.name());
+
+    let s3_path = req.s3ext.s3_path.take().unwrap_or_else(S3Path::root);
+    let Some(bucket) = s3_path.get_bucket_name() else { return Err(s3_error!(NoSuchBucket)) };
+    let key = s3_path.get_object_key().unwrap_or_default();
+
+    let cx = S3WebsiteContext { s3: ccx.s3, bucket };
+    let Some(config) = website.get_config(&cx).await? else {
+        return Err(s3_error!(
+            NoSuchWebsiteConfiguration,
+            "The specified bucket does not have a website configuration"
+        ));
+    };
+
+    if let Some(redirect_all) = &config.redirect_all_requests_to {
+        let protocol = redirect_all.protocol.as_ref().map_or_else(|| scheme(req), |p| p.as_str());
+        let location = format!("{protocol}://{}/{key}", redirect_all.host_name);
+        return Ok(redirect(StatusCode::MOVED_PERMANENTLY, &location));
+    }
+
+    if let Some(rule) = find_routing_rule(&config, key, None) {
+        return Ok(rule_redirect(req, rule, key));
+    }
+
+    let suffix = config.index_document.as_ref().map(|d| d.suffix.as_str());
+    let object_key = match suffix {
+        Some(suffix) if key.is_empty() || key.ends_with('/') => format!("{key}{suffix}"),
+        _ => key.to_owned(),
+    };
+
+    let err = match fetch(ccx, req, op, req.headers.clone(), bucket, &object_key).await {
+        Ok(resp) => match resp.headers.get(header::X_AMZ_WEBSITE_REDIRECT_LOCATION) {
+            Some(location) => {
+                let mut redirect = Response::with_status(StatusCode::MOVED_PERMANENTLY);
+                redirect.headers.insert(header::LOCATION, location.clone());
+                return Ok(redirect);
+            }
+            None => return Ok(resp),
+        },
+        Err(err) => err,
+    };
+
+    // Redirects `key` to `key/` if `key/` has an index document
+    if let Some(suffix) = suffix {
+        if *err.code() == S3ErrorCode::NoSuchKey && !key.is_empty() && object_key == key {
+            let index_key = format!("{key}/{suffix}");
+            if fetch(ccx, req, &HeadObject, HeaderMap::new(), bucket, &index_key)
+                .await
+                .is_ok()
+            {
+                return Ok(redirect(StatusCode::FOUND, &format!("/{key}/")));
+            }
+        }
+    }
+
+    let status = err.status_code().unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+    if let Some(rule) = find_routing_rule(&config, key, Some(status)) {
+        return Ok(rule_redirect(req, rule, key));
+    }
+
+    if let Some(error_document) = &config.error_document {
+        if status.is_client_error() {
+            if let Ok(mut resp) = fetch(ccx, req, op, HeaderMap::new(), bucket, &error_document.key).await {
+                resp.status = status;
+                req.s3ext.error_code = Some(err.code().clone());
+                return Ok(resp);
+            }
+        }
+    }
+
+    Err(err)
+}
+
+/// Calls `GetObject` or `HeadObject` with the headers of the website request.
+///
+/// The access to the fetched object is checked first.
+async fn fetch(
+    ccx: &CallContext<'_>,
+    req: &Request,
+    op: &'static dyn Operation,
+    headers: HeaderMap,
+    bucket: &str,
+    key: &str,
+) -> S3Result<Response> {
+    let mut sub_req = Request {
+        version: req.version,
+        method: if op.name() == "HeadObject" {
+            Method::HEAD
+        } else {
+            Method::GET
+        },
+        uri: req.uri.clone(),
+        headers,
+        extensions: req.extensions.clone(),
+        body: Body::empty(),
+        s3ext: default(),
+    };
+    let s3_path = S3Path::object(bucket, key);
+    let mut acx = S3AccessContext {
+        credentials: None,
+        identity: None,
+        s3_path: &s3_path,
+        s3_op: &S3Operation { name: op.name() },
+        method: &sub_req.method,
+        uri: &sub_req.uri,
+        headers: &req.headers,
+        extensions: &mut sub_req.extensions,
+    };
+    match (ccx.access, ccx.auth) {
+        (Some(access), _) => access.check(&mut acx).await?,
+        (None, Some(_)) => crate::access::default_check(&mut acx)?,
+        (None, None) => {}
+    }
+    sub_req.s3ext.s3_path = Some(s3_path);
+    if ccx.layers.is_empty() {
+        op.call(ccx, &mut sub_req).await
+    } else {
+        crate::layer::call(ccx, op, &mut sub_req).await
+    }
+}
+
+fn scheme(req: &Request) -> &str {
+    req.uri.scheme_str().unwrap_or("http")
+}
+
+fn redirect(status: StatusCode, location: &str) -> Response {
+    let mut resp = Response::with_status(status);
+    if let Ok(val) = HeaderValue::try_from(location) {
+        resp.headers.insert(header::LOCATION, val);
+    }
+    resp
+}
+
+/// Finds the first routing rule matching the key, and the error status if the object is not served.
+fn find_routing_rule<'c>(config: &'c WebsiteConfiguration, key: &str, status: Option<StatusCode>) -> Option<&'c RoutingRule> {
+    config.routing_rules.as_deref()?.iter().find(|rule| {
+        let Some(condition) = &rule.condition else { return status.is_none() };
+        if let Some(prefix) = &condition.key_prefix_equals {
+            if !key.starts_with(prefix.as_str()) {
+                return false;
+            }
+        }
+        match (&condition.http_error_code_returned_equals, status) {
+            (Some(code), Some(status)) => code.as_str() == status.as_str(),
+            (None, None) => true,
+            _ => false,
+        }
+    })
+}
+
+fn rule_redirect(req: &Request, rule: &RoutingRule, key: &str) -> Response {
+    let redirect = &rule.redirect;
+    let protocol = redirect.protocol.as_ref().map_or_else(|| scheme(req), |p| p.as_str());
+    let host = match &redirect.host_name {
+        Some(host) => host.as_str(),
+        None => req
+            .headers
+            .get(header::HOST)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default(),
+    };
+
+    let prefix = rule.condition.as_ref().and_then(|c| c.key_prefix_equals.as_deref());
+    let new_key = match (&redirect.replace_key_with, &redirect.replace_key_prefix_with) {
+        (Some(new_key), _) => new_key.clone(),
+        (None, Some(new_prefix)) => {
+            let rest = prefix.and_then(|p| key.strip_prefix(p)).unwrap_or(key);
+            format!("{new_prefix}{rest}")
+        }
+        (None, None) => key.to_owned(),
+    };
+
+    let status = redirect
+        .http_redirect_code
+        .as_deref()
+        .and_then(|code| code.parse::<StatusCode>().ok())
+        .filter(StatusCode::is_redirection)
+        .unwrap_or(StatusCode::MOVED_PERMANENTLY);
+
+    self::redirect(status, &format!("{protocol}://{host}/{new_key}"))
+}
+
+fn error_page(err: &S3Error, ccx: &CallContext<'_>, is_head: bool) -> Response {
+    let status = err.status_code().unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let mut resp = Response::with_status(status);
+    resp.headers
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static("text/html; charset=utf-8"));
+    if is_head {
+        return resp;
+    }
+
+    let escape = |s: &str| quick_xml::escape::escape(s).into_owned();
+    let title = format!("{} {}", status.as_str(), status.canonical_reason().unwrap_or_default());
+    let message = err
+        .message()
+        .map(|msg| format!("<li>Message: {}</li>\n", escape(msg)))
+        .unwrap_or_default();
+    let html = format!(
+        "<html>\n<head><title>{title}</title></head>\n<body>\n<h1>{title}</h1>\n<ul>\n\
+        <li>Code: {code}</li>\n{message}<li>RequestId: {request_id}</li>\n<li>HostId: {host_id}</li>\n\
+        </ul>\n<hr/>\n</body>\n</html>\n",
+        code = escape(err.code().as_str()),
+        request_id = escape(&ccx.request_id.request_id),
+        host_id = escape(&ccx.request_id.host_id),
+    );
+    resp.body = Body::from(html);
+    resp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::access::S3Access;
+    use crate::auth::SimpleAuth;
+    use crate::dto::{
+        Condition, ErrorDocument, GetBucketWebsiteOutput, GetObjectInput, GetObjectOutput, HeadObjectInput, HeadObjectOutput,
+        IndexDocument, ListBucketsInput, ListBucketsOutput, Redirect, RedirectAllRequestsTo, StreamingBlob,
+    };
+    use crate::host::{SingleDomain, WebsiteDomain};
+    use crate::service::{S3Service, S3ServiceBuilder};
+    use crate::tests::{ACCESS_KEY, SECRET_KEY};
+    use crate::{S3Request, S3Response};
+
+    use bytes::Bytes;
+
+    const OBJECTS: &[&str] = &["index.html", "docs/index.html", "error.html", "page.html"];
+
+    struct WebsiteS3;
+
+    #[async_trait::async_trait]
+    impl S3 for WebsiteS3 {
+        async fn get_bucket_website(
+            &self,
+            req: S3Request<GetBucketWebsiteInput>,
+        ) -> S3Result<S3Response<GetBucketWebsiteOutput>> {
+            let output = match req.input.bucket.as_str() {
+                "site" => GetBucketWebsiteOutput {
+                    index_document: Some(IndexDocument {
+                        suffix: "index.html".to_owned(),
+                    }),
+                    error_document: Some(ErrorDocument {
+                        key: "error.html".to_owned(),
+                    }),
+                    routing_rules: Some(vec![RoutingRule {
+                        condition: Some(Condition {
+                            key_prefix_equals: Some("old/".to_owned()),
+                            http_error_code_returned_equals: None,
+                        }),
+                        redirect: Redirect {
+                            replace_key_prefix_with: Some("new/".to_owned()),
+                            ..Default::default()
+                        },
+                    }]),
+                    redirect_all_requests_to: None,
+                },
+                "moved" => GetBucketWebsiteOutput {
+                    redirect_all_requests_to: Some(RedirectAllRequestsTo {
+                        host_name: "example.org".to_owned(),
+                        protocol: Some("https".parse().unwrap()),
+                    }),
+                    ..Default::default()
+                },
+                _ => return Err(s3_error!(NoSuchWebsiteConfiguration)),
+            };
+            Ok(S3Response::new(output))
+        }
+
+        async fn get_object(&self, req: S3Request<GetObjectInput>) -> S3Result<S3Response<GetObjectOutput>> {
+            let key = req.input.key;
+            if !OBJECTS.contains(&key.as_str()) {
+                return Err(s3_error!(NoSuchKey));
+            }
+            let output = GetObjectOutput {
+                body: Some(StreamingBlob::from(Body::from(Bytes::from(key)))),
+                ..Default::default()
+            };
+            Ok(S3Response::new(output))
+        }
+
+        async fn head_object(&self, req: S3Request<HeadObjectInput>) -> S3Result<S3Response<HeadObjectOutput>> {
+            if !OBJECTS.contains(&req.input.key.as_str()) {
+                return Err(s3_error!(NoSuchKey));
+            }
+            Ok(S3Response::new(HeadObjectOutput::default()))
+        }
+
+        async fn list_buckets(&self, _req: S3Request<ListBucketsInput>) -> S3Result<S3Response<ListBucketsOutput>> {
+            Ok(S3Response::new(ListBucketsOutput::default()))
+        }
+    }
+
+    #[tokio::test]
+    async fn service() {
+        let api = SingleDomain::new("s3.localhost").unwrap();
+        let mut builder = S3ServiceBuilder::new(WebsiteS3);
+        builder.set_host(WebsiteDomain::new(["s3-website.localhost"], api).unwrap());
+        builder.set_website(BucketWebsite);
+        let service = builder.build();
+
+        let call = |method: Method, host: &str, path: &str| {
+            let req = http::Request::builder()
+                .method(method)
+                .uri(path)
+                .header(header::HOST, host)
+                .body(Body::empty())
+                .unwrap();
+            service.call(req)
+        };
+
+        let cases = [
+            ("/", 200, None, Some("index.html")),
+            ("/docs/", 200, None, Some("docs/index.html")),
+            ("/docs", 302, Some("/docs/"), None),
+            ("/page.html", 200, None, Some("page.html")),
+            ("/missing.html", 404, None, Some("error.html")),
+            ("/old/page.html", 301, Some("http://site.s3-website.localhost/new/page.html"), None),
+        ];
+        for (path, status, location, body) in cases {
+            let mut resp = call(Method::GET, "site.s3-website.localhost", path).await.unwrap();
+            assert_eq!(resp.status().as_u16(), status, "{path}");
+            let actual_location = resp.headers().get(header::LOCATION).map(|v| v.to_str().unwrap());
+            assert_eq!(actual_location, location, "{path}");
+            if let Some(body) = body {
+                let actual_body = resp.body_mut().store_all_unlimited().await.unwrap();
+                assert_eq!(actual_body.as_ref(), body.as_bytes(), "{path}");
+            }
+        }
+
+        let resp = call(Method::GET, "moved.s3-website.localhost", "/a/b").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(resp.headers().get(header::LOCATION).unwrap(), "https://example.org/a/b");
+
+        for (method, host, status, code) in [
+            (Method::PUT, "site.s3-website.localhost", 405, "MethodNotAllowed"),
+            (Method::GET, "other.s3-website.localhost", 404, "NoSuchWebsiteConfiguration"),
+        ] {
+            let mut resp = call(method, host, "/").await.unwrap();
+            assert_eq!(resp.status().as_u16(), status);
+            assert_eq!(resp.headers().get(header::CONTENT_TYPE).unwrap(), "text/html; charset=utf-8");
+            let body = resp.body_mut().store_all_unlimited().await.unwrap();
+            let body = std::str::from_utf8(&body).unwrap();
+            assert!(body.contains(&format!("<li>Code: {code}</li>")), "{body}");
+        }
+
+        let resp = call(Method::GET, "s3.localhost", "/").await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(
+            resp.headers()
+                .get(header::CONTENT_TYPE)
+                .is_none_or(|v| v != "text/html; charset=utf-8")
+        );
+    }
+
+    /// Denies the access to `docs/index.html` and `error.html`
+    struct DenyKeys;
+
+    #[async_trait::async_trait]
+    impl S3Access for DenyKeys {
+        async fn check(&self, cx: &mut S3AccessContext<'_>) -> S3Result<()> {
+            match cx.s3_path().get_object_key() {
+                Some("docs/index.html" | "error.html") => Err(s3_error!(AccessDenied)),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    async fn get(service: &S3Service, path: &str) -> (u16, Option<String>, String) {
+        let req = http::Request::builder()
+            .uri(path)
+            .header(header::HOST, "site.s3-website.localhost")
+            .body(Body::empty())
+            .unwrap();
+        let mut resp = service.call(req).await.unwrap();
+        let location = resp.headers().get(header::LOCATION).map(|v| v.to_str().unwrap().to_owned());
+        let body = resp.body_mut().store_all_unlimited().await.unwrap();
+        (resp.status().as_u16(), location, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    fn website_service(access: Option<DenyKeys>) -> S3Service {
+        let api = SingleDomain::new("s3.localhost").unwrap();
+        let mut builder = S3ServiceBuilder::new(WebsiteS3);
+        builder.set_host(WebsiteDomain::new(["s3-website.localhost"], api).unwrap());
+        builder.set_website(BucketWebsite);
+        builder.set_auth(SimpleAuth::from_single(ACCESS_KEY, SECRET_KEY));
+        if let Some(access) = access {
+            builder.set_access(access);
+        }
+        builder.build()
+    }
+
+    #[tokio::test]
+    async fn access() {
+        // anonymous requests are denied if the auth is set without an access provider
+        let service = website_service(None);
+        for path in ["/", "/page.html", "/missing.html"] {
+            let (status, _, body) = get(&service, path).await;
+            assert_eq!(status, 403, "{path}");
+            assert!(body.contains("<li>Code: AccessDenied</li>"), "{body}");
+        }
+
+        // every fetched object is checked
+        let service = website_service(Some(DenyKeys));
+        assert_eq!(get(&service, "/page.html").await.2, "page.html");
+
+        let (status, _, body) = get(&service, "/docs/").await;
+        assert_eq!(status, 403);
+        assert!(body.contains("<li>Code: AccessDenied</li>"), "{body}");
+
+        let (status, location, body) = get(&service, "/docs").await;
+        assert_eq!((status, location), (404, None));
+        assert!(body.contains("<li>Code: NoSuchKey</li>"), "{body}");
+
+        let (status, _, body) = get(&service, "/missing.html").await;
+        assert_eq!(status, 404);
+        assert!(body.contains("<li>Code: NoSuchKey</li>"), "{body}");
+    }
+}